@@ -22,6 +22,20 @@ pub enum Error {
         error("Error: Node {0} not found in the tree.")
     )]
     NodeNotFound(String),
+    /// The input could not be parsed into a tree.
+    #[allow(clippy::enum_variant_names)]
+    #[cfg_attr(
+        not(feature = "no_std"),
+        error("Error: Failed to parse the input at line {line}, column {column}. {message}")
+    )]
+    ParseError {
+        /// The line at which the error occurred, starting from 1.
+        line: usize,
+        /// The column at which the error occurred, starting from 1.
+        column: usize,
+        /// A description of the error.
+        message: String,
+    },
     /// An error occurred while formatting the output.
     #[allow(clippy::enum_variant_names)]
     #[cfg_attr(not(feature = "no_std"), error(transparent))]
@@ -39,6 +53,14 @@ impl Display for Error {
             ),
             Error::InvalidOperation(s) => write!(f, "Error: {s}"),
             Error::NodeNotFound(s) => write!(f, "Error: Node {s} not found in the tree."),
+            Error::ParseError {
+                line,
+                column,
+                message,
+            } => write!(
+                f,
+                "Error: Failed to parse the input at line {line}, column {column}. {message}"
+            ),
             Error::FmtError(_) => {
                 write!(f, "Error: An error occurred while formatting the output.")
             }
@@ -46,6 +68,16 @@ impl Display for Error {
     }
 }
 
+impl Error {
+    /// Create a `NodeNotFound` error for a node id.
+    ///
+    /// The id is formatted with its `Debug` implementation so that node ids are not required to
+    /// implement `Display`.
+    pub(crate) fn node_not_found<K: Debug + ?Sized>(node_id: &K) -> Self {
+        Error::NodeNotFound(format!("{node_id:?}"))
+    }
+}
+
 impl Debug for Error {
     /// Formats the error message.
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
@@ -78,6 +110,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_error_fmt_parse_error() {
+        let err = Error::ParseError {
+            line: 2,
+            column: 5,
+            message: "Unexpected ')'".to_string(),
+        };
+        assert_eq!(
+            format!("{err:?}"),
+            "Error: Failed to parse the input at line 2, column 5. Unexpected ')'"
+        );
+    }
+
     #[test]
     fn test_error_from_fmt_error() {
         let err = Error::FmtError(FmtError);
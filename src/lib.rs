@@ -29,13 +29,25 @@
 //!
 //! ```
 //!
+//! The same tree can be written as a literal with the `tree!` macro.
+//!
+//! ```rust
+//! use tree_ds::prelude::*;
+//!
+//! let tree: Tree<i32, i32> = tree! { 1 => 2 { 2 => 3 { 3 => 4 { 4 => 5 } } } };
+//! ```
+//!
 //! ## Nodes
 //! A Node is the building blocks of the tree data structure. Each node in the tree can have a value
-//! and a unique ID. The value can be of any type that implements the `Eq`, `PartialEq` and `Clone`
-//! traits.
+//! and a unique ID. The value can be of any type, such as `f64`, a `Box<dyn Trait>` or a channel:
+//! nodes and trees put no bound on it. Only the methods that need more ask for it, for example
+//! `Clone` to get a copy of a value with `get_value` or to `deep_clone` a tree, `PartialEq`, `Eq`
+//! and `Hash` to compare or hash nodes and trees, and `Display` to print them.
 //!
 //! By default, the tree requires you to provide unique IDs for the nodes. This node Ids can be of
-//! any type that implements the `Eq` and `Clone` traits.
+//! any type that implements the `Eq` and `Clone` traits. The methods of a tree also need the ids to
+//! implement `Debug`, `Hash` and `Ord`, and lookups accept any borrowed form of the id, such as a
+//! `&str` for `String` ids.
 //!
 //! ```rust
 //! use tree_ds::prelude::*;
@@ -54,6 +66,27 @@
 //! assert_ne!(node.get_node_id(), node_2.get_node_id());//!
 //! ```
 //!
+//! The `auto_id` feature uses one generator for the whole program, and its ids are `u128` values
+//! because that is what this generator produces. A node created with `new_with_auto_id` does not
+//! belong to a tree yet and can be added to any tree, or moved between trees with `add_subtree`, so
+//! its id has to be unique across all the trees of the program, which a generator owned by one tree
+//! can not ensure. A tree can instead own an `IdGenerator`, such as a `SequentialIdGenerator` or a
+//! `SeededIdGenerator`, and create its nodes with `Tree::add_value`. This gives each tree its own
+//! reproducible ids of any type, which are only unique within the tree.
+//!
+//! ```rust
+//! use tree_ds::prelude::*;
+//!
+//! # fn main() -> Result<()> {
+//! let mut tree: Tree<u64, &str> = Tree::new(None);
+//! tree.set_id_generator(SequentialIdGenerator::default());
+//! let root = tree.add_value(Some("Harry Doe"), None)?;
+//! let child = tree.add_value(Some("Jane Doe"), Some(&root))?;
+//! assert_eq!((root, child), (1, 2));
+//! # Ok(())
+//! # }
+//! ```
+//!
 //! ## Traversal
 //! The tree supports three traversal strategies:
 //! - Pre-order
@@ -118,6 +151,27 @@
 //! the tree has to be reconstructed from the serialized data.
 //!
 //!
+//! ## Thread Safety
+//! Nodes and trees are generic over the storage used to share the data of each node. `LocalTree`
+//! keeps the data in an `Rc<RefCell<_>>` and `ThreadSafeTree` keeps it in an `Arc<RwLock<_>>`, so
+//! it can be sent to and shared between threads. Both can be used in the same program. `Tree` uses
+//! the `DefaultStorage`, which is always the `Rc` based storage, so no feature changes it.
+//! With the standard library, `ThreadSafeTree` also offers `par_traverse`, `par_fold_up` and
+//! `par_find`, which spread the work over sibling subtrees on scoped threads.
+//! `SharedTree` publishes each change to a `ThreadSafeTree` as a new version, so readers can hold a
+//! consistent `TreeSnapshot` while writers update the tree.
+//! With the `async` feature, `AsyncTree` wraps a `ThreadSafeTree` in a lock that tasks wait for
+//! without blocking their thread.
+//!
+//! ```rust
+//! use tree_ds::prelude::*;
+//!
+//! let mut tree: ThreadSafeTree<i32, i32> = ThreadSafeTree::new_in(Some("Shared"), ArcStorage);
+//! let root = tree.add_node(Node::new_in(1, Some(2), ArcStorage), None).unwrap();
+//! let handle = std::thread::spawn(move || tree.get_node_by_id(&root).unwrap().get_value().unwrap());
+//! assert_eq!(handle.join().unwrap(), Some(2));
+//! ```
+//!
 //! ## `no_std` Environments.
 //! This crate can be used in `no_std` environments by enabling the `no_std` feature.
 //!
@@ -135,7 +189,7 @@
 //! ## Cargo Features
 //! The following cargo features are also available:
 //! - By default the library is synchronous, and you need to manually provide ids for the nodes.
-//! - `async`: Enables support for async operations on the tree.
+//! - `async`: Adds `AsyncTree`, a thread safe tree with an async API that works with any executor.
 //! - `serde`: Enables serialization and deserialization of the tree.
 //! - `auto_id`: Enables auto-generation of node IDs.
 //! - `no_std`: Disables the standard library.
@@ -149,46 +203,57 @@ extern crate alloc;
 extern crate core;
 
 mod lib {
-    #[cfg(all(feature = "no_std", not(feature = "async")))]
-    pub use alloc::rc::Rc;
-    #[cfg(all(feature = "no_std", feature = "async"))]
-    pub use alloc::sync::Arc;
     #[cfg(feature = "no_std")]
     pub use alloc::{
-        collections::BTreeSet,
+        boxed::Box,
+        collections::{BTreeMap, BTreeSet},
         format,
+        rc::Rc,
         string::{String, ToString},
+        sync::Arc,
         vec,
         vec::Vec,
     };
-    #[cfg(feature = "async")]
-    pub use spin::RwLock;
+    pub use spin::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
     #[cfg(all(test, not(feature = "no_std")))]
     pub use std::format;
-    #[cfg(all(not(feature = "no_std"), not(feature = "async")))]
-    pub use std::rc::Rc;
-    #[cfg(all(not(feature = "no_std"), feature = "async"))]
-    pub use std::sync::Arc;
     #[cfg(not(feature = "no_std"))]
     pub use std::{
-        collections::HashSet,
+        boxed::Box,
+        collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+        rc::Rc,
         string::{String, ToString},
+        sync::Arc,
         vec,
         vec::Vec,
     };
 
-    #[cfg(not(feature = "async"))]
-    pub use self::core::cell::RefCell;
+    pub use self::core::any::{type_name, Any, TypeId};
+    pub use self::core::borrow::Borrow;
+    pub use self::core::cell::{Ref, RefCell, RefMut};
     pub use self::core::clone::Clone;
     pub use self::core::cmp::{Eq, Ordering, PartialEq};
     pub use self::core::convert::{AsRef, From};
     pub use self::core::default::Default;
     pub use self::core::fmt::{Debug, Display, Error as FmtError, Formatter, Result as FmtResult};
+    #[cfg(feature = "async")]
+    pub use self::core::future::poll_fn;
+    #[cfg(all(test, feature = "async", not(feature = "no_std")))]
+    pub use self::core::future::Future;
     pub use self::core::hash::{Hash, Hasher};
+    pub use self::core::marker::PhantomData;
+    pub use self::core::mem;
+    pub use self::core::ops::{Add, Deref, DerefMut, Index};
     pub use self::core::option::Option;
+    #[cfg(all(test, feature = "async", not(feature = "no_std")))]
+    pub use self::core::pin::pin;
     pub use self::core::result::Result;
     pub use self::core::slice::Iter;
+    #[cfg(feature = "auto_id")]
+    pub use self::core::str::FromStr;
+    #[cfg(feature = "async")]
+    pub use self::core::task::{Poll, Waker};
 
     mod core {
         #[cfg(feature = "no_std")]
@@ -200,6 +265,7 @@ mod lib {
 }
 
 mod error;
+mod macros;
 mod node;
 mod tree;
 
@@ -207,13 +273,32 @@ pub mod prelude {
     //! A module to re-export the necessary types for the tree data structure.
 
     pub use crate::{
-        node::{Node, Nodes},
-        tree::{NodeRemovalStrategy, SubTree, TraversalStrategy, Tree},
+        node::{
+            ArcStorage, DefaultStorage, LocalNode, Node, NodeStorage, Nodes, RcStorage,
+            ThreadSafeNode,
+        },
+        tree::{
+            CollisionPolicy, DisplayEdges, IdGenerator, ImmutableTree, IndentStyle, LocalTree,
+            MermaidStyle, NewickValue, NodeAttributes, NodeRemovalStrategy, PlantUmlStyle,
+            SeededIdGenerator, SequentialIdGenerator, SharedTree, SubTree, ThreadSafeTree,
+            TraversalStrategy, Tree, TreeBuilder, TreeCursor, TreeCursorMut, TreeGlyphs, TreeIter,
+            TreeRenderer, TreeSnapshot, TreeVisitor, WalkControl, WalkEvent, WalkEvents,
+        },
     };
 
+    #[cfg(feature = "serde")]
+    pub use crate::tree::WithAttributes;
+    #[cfg(feature = "async")]
+    pub use crate::tree::{AsyncTree, AsyncTreeReadGuard, AsyncTreeWriteGuard};
+    #[cfg(not(feature = "no_std"))]
+    pub use crate::tree::{EpochIdGenerator, RandomIdGenerator};
+
+    pub use crate::tree;
+
     /// Defines the default type for the node id.
     ///
-    /// The default type for the node id is `u128`.
+    /// The default type for the node id is `u128`, the type of the ids of the program-wide
+    /// generator used by `Node::new_with_auto_id`.
     #[cfg(feature = "auto_id")]
     pub type AutomatedId = u128;
 
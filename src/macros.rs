@@ -0,0 +1,330 @@
+/// Create a tree from a literal description of its nodes.
+///
+/// Each node is written as `id => value`, optionally followed by its children between braces.
+/// Sibling nodes are separated by commas and the tree has a single root node. The value of each
+/// node is stored as `Some(value)`. A value that contains braces, such as a struct literal, must be
+/// wrapped in parentheses so that the braces are not read as the children of the node. A value that
+/// is a name or a path, such as `Kind::Dir`, should also be wrapped in parentheses when the node has
+/// children, since the name and the children can otherwise be read as a struct literal.
+///
+/// The depth of the expansion grows with the nesting of the nodes, not with the number of
+/// siblings, as long as the values of a list of siblings are all single tokens or parenthesized,
+/// or none of the siblings has children.
+///
+/// In the auto id form, every id is written as `_` and the ids are generated by a
+/// `SequentialIdGenerator` that is set as the id generator of the tree. The nodes are then numbered
+/// from `1` in pre-order, and more nodes can be added with `Tree::add_value`. Both forms can not be
+/// mixed in the same tree.
+///
+/// The tree has no name.
+///
+/// # Panics
+///
+/// Panics if two nodes are given the same id, as in `tree! { 1 => "a" { 1 => "b" } }`, since the
+/// ids are only compared when the tree is built. The auto id form can not repeat an id.
+///
+/// # Example
+///
+/// ```rust
+/// use tree_ds::prelude::*;
+///
+/// # fn main() -> Result<()> {
+/// let tree: Tree<i32, &str> = tree! {
+///     1 => "root" {
+///         2 => "a" {
+///             4 => "x"
+///         },
+///         3 => "b"
+///     }
+/// };
+/// assert_eq!(tree.traverse(&1, TraversalStrategy::PreOrder)?, vec![1, 2, 4, 3]);
+///
+/// let tree: Tree<u32, &str> = tree! {
+///     _ => "root" {
+///         _ => "a" { _ => "x" },
+///         _ => "b"
+///     }
+/// };
+/// assert_eq!(tree.get_node_by_id(&3).unwrap().get_value()?, Some("x"));
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! tree {
+    // The root node, once its value has been read.
+    (@root [$mode:tt] $tree:ident, [$($id:tt)*], [$($value:tt)+]; {} $(,)?) => {
+        $crate::tree!(@add [$mode] $tree, ::core::option::Option::None, [$($id)*], [$($value)+]);
+    };
+    (@root [$mode:tt] $tree:ident, [$($id:tt)*], [$($value:tt)+]; { $($children:tt)* } $(,)?) => {
+        let root_id = $crate::tree!(@add [$mode] $tree, ::core::option::Option::None, [$($id)*], [$($value)+]);
+        $crate::tree!(@nodes [$mode] $tree, ::core::option::Option::Some(&root_id); $($children)*);
+    };
+    (@root [$mode:tt] $tree:ident, [$($id:tt)*], [$($value:tt)+]; $(,)?) => {
+        $crate::tree!(@add [$mode] $tree, ::core::option::Option::None, [$($id)*], [$($value)+]);
+    };
+    (@root [$mode:tt] $tree:ident, [$($id:tt)*], [$($value:tt)+]; , $($rest:tt)+) => {
+        ::core::compile_error!("A tree has a single root node.")
+    };
+    (@root [$mode:tt] $tree:ident, [$($id:tt)*], [$($value:tt)*]; $next:tt $($rest:tt)*) => {
+        $crate::tree!(@root [$mode] $tree, [$($id)*], [$($value)* $next]; $($rest)*)
+    };
+
+    // A list of sibling nodes. Lists whose values are all single token trees, or whose nodes
+    // have no children, are read with one repetition, so that the depth of the expansion does not
+    // grow with the number of siblings. Other lists are read one token at a time.
+    (@nodes [$mode:tt] $tree:ident, $parent:expr;) => {};
+    (@nodes [auto] $tree:ident, $parent:expr; $(_ => $value:tt $({ $($children:tt)* })?),+ $(,)?) => {
+        $(
+            $crate::tree!(
+                @children [auto] $tree, $crate::tree!(@add [auto] $tree, $parent, [], [$value]);
+                $($($children)*)?
+            );
+        )+
+    };
+    (@nodes [auto] $tree:ident, $parent:expr; $(_ => $value:expr),+ $(,)?) => {
+        $($crate::tree!(@add [auto] $tree, $parent, [], [$value]);)+
+    };
+    (@nodes [id] $tree:ident, $parent:expr; $($id:expr => $value:tt $({ $($children:tt)* })?),+ $(,)?) => {
+        $(
+            $crate::tree!(
+                @children [id] $tree, $crate::tree!(@add [id] $tree, $parent, [$id], [$value]);
+                $($($children)*)?
+            );
+        )+
+    };
+    (@nodes [id] $tree:ident, $parent:expr; $($id:expr => $value:expr),+ $(,)?) => {
+        $($crate::tree!(@add [id] $tree, $parent, [$id], [$value]);)+
+    };
+    (@nodes [auto] $tree:ident, $parent:expr; _ => $($rest:tt)+) => {
+        $crate::tree!(@node [auto] $tree, $parent, [], []; $($rest)+)
+    };
+    (@nodes [id] $tree:ident, $parent:expr; $id:expr => $($rest:tt)+) => {
+        $crate::tree!(@node [id] $tree, $parent, [$id], []; $($rest)+)
+    };
+
+    // A node below the root node, once its value has been read, followed by its siblings.
+    (@node [$mode:tt] $tree:ident, $parent:expr, [$($id:tt)*], [$($value:tt)+]; {} $($rest:tt)*) => {
+        $crate::tree!(@add [$mode] $tree, $parent, [$($id)*], [$($value)+]);
+        $crate::tree!(@next [$mode] $tree, $parent; $($rest)*);
+    };
+    (@node [$mode:tt] $tree:ident, $parent:expr, [$($id:tt)*], [$($value:tt)+]; { $($children:tt)* } $($rest:tt)*) => {
+        {
+            let parent_id = $crate::tree!(@add [$mode] $tree, $parent, [$($id)*], [$($value)+]);
+            $crate::tree!(@nodes [$mode] $tree, ::core::option::Option::Some(&parent_id); $($children)*);
+        }
+        $crate::tree!(@next [$mode] $tree, $parent; $($rest)*);
+    };
+    (@node [$mode:tt] $tree:ident, $parent:expr, [$($id:tt)*], [$($value:tt)+]; , $($rest:tt)*) => {
+        $crate::tree!(@add [$mode] $tree, $parent, [$($id)*], [$($value)+]);
+        $crate::tree!(@nodes [$mode] $tree, $parent; $($rest)*);
+    };
+    (@node [$mode:tt] $tree:ident, $parent:expr, [$($id:tt)*], [$($value:tt)+];) => {
+        $crate::tree!(@add [$mode] $tree, $parent, [$($id)*], [$($value)+]);
+    };
+    (@node [$mode:tt] $tree:ident, $parent:expr, [$($id:tt)*], [$($value:tt)*]; $next:tt $($rest:tt)*) => {
+        $crate::tree!(@node [$mode] $tree, $parent, [$($id)*], [$($value)* $next]; $($rest)*)
+    };
+
+    // Add the children of a node once the node has been added.
+    (@children [$mode:tt] $tree:ident, $node_id:expr;) => {
+        $node_id
+    };
+    (@children [$mode:tt] $tree:ident, $node_id:expr; $($children:tt)+) => {{
+        let parent_id = $node_id;
+        $crate::tree!(@nodes [$mode] $tree, ::core::option::Option::Some(&parent_id); $($children)+);
+    }};
+
+    // The siblings that follow the children of a node.
+    (@next [$mode:tt] $tree:ident, $parent:expr;) => {};
+    (@next [$mode:tt] $tree:ident, $parent:expr; , $($rest:tt)*) => {
+        $crate::tree!(@nodes [$mode] $tree, $parent; $($rest)*)
+    };
+
+    // Add a node to the tree and return its id.
+    (@add [auto] $tree:ident, $parent:expr, [], [$($value:tt)+]) => {
+        $tree
+            .add_value(::core::option::Option::Some($($value)+), $parent)
+            .expect("Error: Failed to add the node to the tree.")
+    };
+    (@add [id] $tree:ident, $parent:expr, [$id:expr], [$($value:tt)+]) => {{
+        // `add_node` does not check that the id is unused.
+        let node_id = $id;
+        if $tree.get_node_ref(&node_id).is_some() {
+            ::core::panic!("Error: Node {:?} is already in the tree.", node_id);
+        }
+        $tree
+            .add_node(
+                $crate::prelude::Node::new(node_id, ::core::option::Option::Some($($value)+)),
+                $parent,
+            )
+            .expect("Error: Failed to add the node to the tree.")
+    }};
+
+    (_ => $($rest:tt)+) => {{
+        let mut tree = $crate::prelude::Tree::new(::core::option::Option::None);
+        tree.set_id_generator($crate::prelude::SequentialIdGenerator::default());
+        $crate::tree!(@root [auto] tree, [], []; $($rest)+);
+        tree
+    }};
+    ($id:expr => $($rest:tt)+) => {{
+        let mut tree = $crate::prelude::Tree::new(::core::option::Option::None);
+        $crate::tree!(@root [id] tree, [$id], []; $($rest)+);
+        tree
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lib::*;
+    use crate::prelude::{Node, Result, TraversalStrategy, Tree};
+
+    #[test]
+    fn test_tree_macro() -> Result<()> {
+        let tree: Tree<i32, &str> = tree! {
+            1 => "root" {
+                2 => "a" {
+                    4 => "x",
+                },
+                3 => "b"
+            }
+        };
+        let mut expected = Tree::new(None);
+        let root = expected.add_node(Node::new(1, Some("root")), None)?;
+        let node_2 = expected.add_node(Node::new(2, Some("a")), Some(&root))?;
+        expected.add_node(Node::new(4, Some("x")), Some(&node_2))?;
+        expected.add_node(Node::new(3, Some("b")), Some(&root))?;
+        assert_eq!(tree, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_macro_with_expressions() -> Result<()> {
+        #[derive(Debug, PartialEq)]
+        struct Point {
+            x: i32,
+        }
+
+        let offset = 10;
+        let tree: Tree<String, Option<Point>> = tree! {
+            "root".to_string() => None {
+                format!("child-{}", 1) => Some(Point { x: offset + 1 }),
+                String::from("leaf") => (Some(Point { x: -offset })) {}
+            }
+        };
+        assert_eq!(
+            tree.traverse("root", TraversalStrategy::PreOrder)?,
+            vec!["root", "child-1", "leaf"]
+        );
+        tree.get_node_by_id("leaf")
+            .unwrap()
+            .with_value(|x| assert_eq!(x, Some(&Some(Point { x: -10 }))));
+
+        let single: Tree<i32, i32> = tree! { 1 => 2 + 3 {} };
+        assert_eq!(single.get_node_by_id(&1).unwrap().get_value()?, Some(5));
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "Error: Node 1 is already in the tree.")]
+    fn test_tree_macro_with_duplicate_ids() {
+        let _: Tree<i32, &str> = tree! { 1 => "a" { 1 => "b" } };
+    }
+
+    #[test]
+    fn test_tree_macro_with_auto_ids() -> Result<()> {
+        let mut tree: Tree<u64, &str> = tree! {
+            _ => "root" {
+                _ => "a" { _ => "x", _ => "y" },
+                _ => "b",
+            }
+        };
+        assert_eq!(
+            tree.traverse(&1, TraversalStrategy::PreOrder)?,
+            vec![1, 2, 3, 4, 5]
+        );
+        assert_eq!(tree.get_node_by_id(&5).unwrap().get_value()?, Some("b"));
+        assert_eq!(tree.add_value(Some("c"), Some(&1))?, 6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_macro_with_many_siblings() -> Result<()> {
+        // The depth of the expansion does not grow with the number of siblings.
+        let tree: Tree<i32, String> = tree! {
+            0 => String::from("root") {
+                1 => String::from("x"), 2 => String::from("x"), 3 => String::from("x"), 4 => String::from("x"),
+                5 => String::from("x"), 6 => String::from("x"), 7 => String::from("x"), 8 => String::from("x"),
+                9 => String::from("x"), 10 => String::from("x"), 11 => String::from("x"), 12 => String::from("x"),
+                13 => String::from("x"), 14 => String::from("x"), 15 => String::from("x"), 16 => String::from("x"),
+                17 => String::from("x"), 18 => String::from("x"), 19 => String::from("x"), 20 => String::from("x"),
+                21 => String::from("x"), 22 => String::from("x"), 23 => String::from("x"), 24 => String::from("x"),
+                25 => String::from("x"), 26 => String::from("x"), 27 => String::from("x"), 28 => String::from("x"),
+                29 => String::from("x"), 30 => String::from("x"), 31 => String::from("x"), 32 => String::from("x"),
+                33 => String::from("x"), 34 => String::from("x"), 35 => String::from("x"), 36 => String::from("x"),
+                37 => String::from("x"), 38 => String::from("x"), 39 => String::from("x"), 40 => String::from("x"),
+                41 => String::from("x"), 42 => String::from("x"), 43 => String::from("x"), 44 => String::from("x"),
+                45 => String::from("x"), 46 => String::from("x"), 47 => String::from("x"), 48 => String::from("x"),
+                49 => String::from("x"), 50 => String::from("x"), 51 => String::from("x"), 52 => String::from("x"),
+                53 => String::from("x"), 54 => String::from("x"), 55 => String::from("x"), 56 => String::from("x"),
+                57 => String::from("x"), 58 => String::from("x"), 59 => String::from("x"), 60 => String::from("x"),
+                61 => String::from("x"), 62 => String::from("x"), 63 => String::from("x"), 64 => String::from("x"),
+                65 => String::from("x"), 66 => String::from("x"), 67 => String::from("x"), 68 => String::from("x"),
+                69 => String::from("x"), 70 => String::from("x"), 71 => String::from("x"), 72 => String::from("x"),
+                73 => String::from("x"), 74 => String::from("x"), 75 => String::from("x"), 76 => String::from("x"),
+                77 => String::from("x"), 78 => String::from("x"), 79 => String::from("x"), 80 => String::from("x"),
+                81 => String::from("x"), 82 => String::from("x"), 83 => String::from("x"), 84 => String::from("x"),
+                85 => String::from("x"), 86 => String::from("x"), 87 => String::from("x"), 88 => String::from("x"),
+                89 => String::from("x"), 90 => String::from("x"), 91 => String::from("x"), 92 => String::from("x"),
+                93 => String::from("x"), 94 => String::from("x"), 95 => String::from("x"), 96 => String::from("x"),
+                97 => String::from("x"), 98 => String::from("x"), 99 => String::from("x"), 100 => String::from("x"),
+                101 => String::from("x"), 102 => String::from("x"), 103 => String::from("x"), 104 => String::from("x"),
+                105 => String::from("x"), 106 => String::from("x"), 107 => String::from("x"), 108 => String::from("x"),
+                109 => String::from("x"), 110 => String::from("x"), 111 => String::from("x"), 112 => String::from("x"),
+                113 => String::from("x"), 114 => String::from("x"), 115 => String::from("x"), 116 => String::from("x"),
+                117 => String::from("x"), 118 => String::from("x"), 119 => String::from("x"), 120 => String::from("x")
+            }
+        };
+        assert_eq!(tree.len(), 121);
+        assert_eq!(
+            tree.get_node_by_id(&0).unwrap().get_children_ids()?.len(),
+            120
+        );
+        assert_eq!(
+            tree.get_node_by_id(&120).unwrap().get_value()?,
+            Some(String::from("x"))
+        );
+
+        let tree: Tree<u32, i32> = tree! {
+            _ => 0 {
+                _ => 1 { _ => 1 }, _ => 2 { _ => 2 }, _ => 3 { _ => 3 }, _ => 4 { _ => 4 }, _ => 5 { _ => 5 },
+                _ => 6 { _ => 6 }, _ => 7 { _ => 7 }, _ => 8 { _ => 8 }, _ => 9 { _ => 9 }, _ => 10 { _ => 10 },
+                _ => 11 { _ => 11 }, _ => 12 { _ => 12 }, _ => 13 { _ => 13 }, _ => 14 { _ => 14 }, _ => 15 { _ => 15 },
+                _ => 16 { _ => 16 }, _ => 17 { _ => 17 }, _ => 18 { _ => 18 }, _ => 19 { _ => 19 }, _ => 20 { _ => 20 },
+                _ => 21 { _ => 21 }, _ => 22 { _ => 22 }, _ => 23 { _ => 23 }, _ => 24 { _ => 24 }, _ => 25 { _ => 25 },
+                _ => 26 { _ => 26 }, _ => 27 { _ => 27 }, _ => 28 { _ => 28 }, _ => 29 { _ => 29 }, _ => 30 { _ => 30 },
+                _ => 31 { _ => 31 }, _ => 32 { _ => 32 }, _ => 33 { _ => 33 }, _ => 34 { _ => 34 }, _ => 35 { _ => 35 },
+                _ => 36 { _ => 36 }, _ => 37 { _ => 37 }, _ => 38 { _ => 38 }, _ => 39 { _ => 39 }, _ => 40 { _ => 40 },
+                _ => 41 { _ => 41 }, _ => 42 { _ => 42 }, _ => 43 { _ => 43 }, _ => 44 { _ => 44 }, _ => 45 { _ => 45 },
+                _ => 46 { _ => 46 }, _ => 47 { _ => 47 }, _ => 48 { _ => 48 }, _ => 49 { _ => 49 }, _ => 50 { _ => 50 },
+                _ => 51 { _ => 51 }, _ => 52 { _ => 52 }, _ => 53 { _ => 53 }, _ => 54 { _ => 54 }, _ => 55 { _ => 55 },
+                _ => 56 { _ => 56 }, _ => 57 { _ => 57 }, _ => 58 { _ => 58 }, _ => 59 { _ => 59 }, _ => 60 { _ => 60 },
+                _ => 61 { _ => 61 }, _ => 62 { _ => 62 }, _ => 63 { _ => 63 }, _ => 64 { _ => 64 }, _ => 65 { _ => 65 },
+                _ => 66 { _ => 66 }, _ => 67 { _ => 67 }, _ => 68 { _ => 68 }, _ => 69 { _ => 69 }, _ => 70 { _ => 70 },
+                _ => 71 { _ => 71 }, _ => 72 { _ => 72 }, _ => 73 { _ => 73 }, _ => 74 { _ => 74 }, _ => 75 { _ => 75 },
+                _ => 76 { _ => 76 }, _ => 77 { _ => 77 }, _ => 78 { _ => 78 }, _ => 79 { _ => 79 }, _ => 80 { _ => 80 },
+                _ => 81 { _ => 81 }, _ => 82 { _ => 82 }, _ => 83 { _ => 83 }, _ => 84 { _ => 84 }, _ => 85 { _ => 85 },
+                _ => 86 { _ => 86 }, _ => 87 { _ => 87 }, _ => 88 { _ => 88 }, _ => 89 { _ => 89 }, _ => 90 { _ => 90 },
+                _ => 91 { _ => 91 }, _ => 92 { _ => 92 }, _ => 93 { _ => 93 }, _ => 94 { _ => 94 }, _ => 95 { _ => 95 },
+                _ => 96 { _ => 96 }, _ => 97 { _ => 97 }, _ => 98 { _ => 98 }, _ => 99 { _ => 99 }, _ => 100 { _ => 100 },
+                _ => 101 { _ => 101 }, _ => 102 { _ => 102 }, _ => 103 { _ => 103 }, _ => 104 { _ => 104 }, _ => 105 { _ => 105 }
+            }
+        };
+        assert_eq!(tree.len(), 211);
+        assert_eq!(tree.get_node_by_id(&211).unwrap().get_value()?, Some(105));
+        assert_eq!(
+            tree.get_node_by_id(&211).unwrap().get_parent_id()?,
+            Some(210)
+        );
+        Ok(())
+    }
+}
@@ -1,12 +1,12 @@
 use crate::lib::*;
-use crate::node::_Node;
+use crate::node::{_Node, DefaultStorage, NodeStorage};
 
 #[cfg(feature = "auto_id")]
 use crate::node::GENERATOR;
-#[cfg(feature = "auto_id")]
-use sequential_gen::prelude::Generator;
 #[cfg(feature = "serde")]
 use ::serde::{Deserialize, Serialize};
+#[cfg(feature = "auto_id")]
+use sequential_gen::prelude::Generator;
 
 /// A node in a tree.
 ///
@@ -18,7 +18,9 @@ use ::serde::{Deserialize, Serialize};
 ///
 /// * `Q` - The type of the unique id of the node. Odd, I know but this is for flexibility. Some people might want to use
 ///   a string as the unique id of the node. Others might want to use an integer. This is why the unique id is a generic type.
-/// * `T` - The type of the value of the node.
+/// * `T` - The type of the value of the node. It has no required traits: `get_value` requires
+///   `T: Clone`, while `value_ref` and `with_value` give access to any value.
+/// * `P` - The storage used to share the data of the node. See `NodeStorage`.
 ///
 /// # Fields
 ///
@@ -34,16 +36,14 @@ use ::serde::{Deserialize, Serialize};
 ///
 /// let node: Node<i32, i32> = Node::new(1, Some(2));
 /// ```
-#[derive(Clone, Debug, Eq)]
-pub struct Node<Q, T>(Rc<RefCell<_Node<Q, T>>>)
+pub struct Node<Q, T, P = DefaultStorage>(P::Pointer<_Node<Q, T>>)
 where
     Q: PartialEq + Eq + Clone,
-    T: PartialEq + Eq + Clone;
+    P: NodeStorage;
 
 impl<Q, T> Node<Q, T>
 where
     Q: PartialEq + Eq + Clone,
-    T: PartialEq + Eq + Clone,
 {
     /// Create a new node.
     ///
@@ -68,12 +68,46 @@ where
     /// let node = Node::new(1, Some(2));
     /// ```
     pub fn new(node_id: Q, value: Option<T>) -> Self {
-        Node(Rc::new(RefCell::new(_Node {
+        Node::new_in(node_id, value, DefaultStorage::default())
+    }
+}
+
+impl<Q, T, P> Node<Q, T, P>
+where
+    Q: PartialEq + Eq + Clone,
+    P: NodeStorage,
+{
+    /// Create a new node with the given storage.
+    ///
+    /// This method creates a new node in the same way as `Node::new` but lets the storage of the
+    /// node be chosen. It is used to create the nodes of a tree that does not use the default
+    /// storage.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node.
+    /// * `value` - The value of the node.
+    /// * `storage` - The storage used to share the data of the node.
+    ///
+    /// # Returns
+    ///
+    /// A new node with the given node id and value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{ArcStorage, Node};
+    ///
+    /// let node = Node::new_in(1, Some(2), ArcStorage);
+    /// std::thread::spawn(move || assert_eq!(node.get_value().unwrap(), Some(2)));
+    /// ```
+    pub fn new_in(node_id: Q, value: Option<T>, _storage: P) -> Self {
+        Node(P::new_pointer(_Node {
             node_id,
             value,
             children: vec![],
             parent: None,
-        })))
+        }))
     }
 
     /// Add a child to the node.
@@ -93,13 +127,28 @@ where
     /// let parent_node = Node::new(1, Some(2));
     /// parent_node.add_child(Node::new(2, Some(3))).unwrap();
     /// ```
-    pub fn add_child(&self, child: Node<Q, T>) -> crate::prelude::Result<()> {
+    pub fn add_child(&self, child: Node<Q, T, P>) -> crate::prelude::Result<()> {
         {
             // This block is to ensure that the borrow_mut() is dropped before the next borrow_mut() call.
-            let mut node = self.0.borrow_mut();
+            let mut node = P::write(&self.0);
             node.children.push(child.get_node_id()?);
         }
-        let mut child = child.0.borrow_mut();
+        let mut child = P::write(&child.0);
+        child.parent = Some(self.get_node_id()?);
+        Ok(())
+    }
+
+    /// Add a child to the node at the given position among its children.
+    pub(crate) fn insert_child(
+        &self,
+        index: usize,
+        child: Node<Q, T, P>,
+    ) -> crate::prelude::Result<()> {
+        {
+            let mut node = P::write(&self.0);
+            node.children.insert(index, child.get_node_id()?);
+        }
+        let mut child = P::write(&child.0);
         child.parent = Some(self.get_node_id()?);
         Ok(())
     }
@@ -127,14 +176,14 @@ where
     /// parent_node.add_child(child_node.clone()).unwrap();
     /// parent_node.remove_child(child_node).unwrap();
     /// ```
-    pub fn remove_child(&self, child: Node<Q, T>) -> crate::prelude::Result<()> {
-        let mut node = self.0.borrow_mut();
+    pub fn remove_child(&self, child: Node<Q, T, P>) -> crate::prelude::Result<()> {
+        let mut node = P::write(&self.0);
         node.children.retain(|x| {
             x != &child
                 .get_node_id()
                 .expect("Error: Could not fetch id of Node.")
         });
-        let mut child = child.0.borrow_mut();
+        let mut child = P::write(&child.0);
         child.parent = None;
         Ok(())
     }
@@ -156,7 +205,35 @@ where
     /// assert_eq!(node.get_node_id().unwrap(), 1);
     /// ```
     pub fn get_node_id(&self) -> crate::prelude::Result<Q> {
-        Ok(self.0.borrow().node_id.clone())
+        Ok(P::read(&self.0).node_id.clone())
+    }
+
+    /// Check if the node has the given id without cloning it.
+    pub(crate) fn has_id<K>(&self, node_id: &K) -> bool
+    where
+        Q: Borrow<K>,
+        K: Eq + ?Sized,
+    {
+        P::read(&self.0).node_id.borrow() == node_id
+    }
+
+    /// Replace the ids of the node, of its parent and of its children that are keys of `ids` with
+    /// the matching values.
+    pub(crate) fn rename_ids(&self, ids: &BTreeMap<Q, Q>)
+    where
+        Q: Ord,
+    {
+        let mut node = P::write(&self.0);
+        let rename = |id: &mut Q| {
+            if let Some(new_id) = ids.get(id) {
+                *id = new_id.clone();
+            }
+        };
+        rename(&mut node.node_id);
+        if let Some(parent) = node.parent.as_mut() {
+            rename(parent);
+        }
+        node.children.iter_mut().for_each(rename);
     }
 
     /// Get the ids of the children of the node.
@@ -184,7 +261,7 @@ where
     /// assert_eq!(node.get_children_ids().unwrap().len(), 1);
     /// ```
     pub fn get_children_ids(&self) -> crate::prelude::Result<Vec<Q>> {
-        Ok(self.0.borrow().children.clone())
+        Ok(P::read(&self.0).children.clone())
     }
 
     /// Get the node id of the parent of the node.
@@ -208,7 +285,7 @@ where
     /// assert!(parent_node.get_parent_id().unwrap().is_none());
     /// ```
     pub fn get_parent_id(&self) -> crate::prelude::Result<Option<Q>> {
-        Ok(self.0.borrow().parent.clone())
+        Ok(P::read(&self.0).parent.clone())
     }
 
     /// Get the value of the node.
@@ -227,8 +304,81 @@ where
     /// let node = Node::new(1, Some(2));
     /// assert_eq!(node.get_value().unwrap(), Some(2));
     /// ```
-    pub fn get_value(&self) -> crate::prelude::Result<Option<T>> {
-        Ok(self.0.borrow().value.clone())
+    pub fn get_value(&self) -> crate::prelude::Result<Option<T>>
+    where
+        T: Clone,
+    {
+        Ok(P::read(&self.0).value.clone())
+    }
+
+    /// Borrow the value of the node.
+    ///
+    /// This method gives access to the value of the node without cloning it. The returned guard
+    /// keeps the node locked for reading until it is dropped, so the node must not be changed while
+    /// the guard is alive: doing so panics for an `RcStorage` node and never returns for an
+    /// `ArcStorage` node.
+    ///
+    /// # Returns
+    ///
+    /// A guard that dereferences to the value of the node.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::Node;
+    ///
+    /// let node = Node::new(1, Some(vec![2, 3]));
+    /// assert_eq!(node.value_ref().as_deref(), Some(&[2, 3][..]));
+    /// ```
+    pub fn value_ref(&self) -> impl Deref<Target = Option<T>> + '_ {
+        ValueRef::<Q, T, P>(P::read(&self.0))
+    }
+
+    /// Call a closure with a reference to the value of the node.
+    ///
+    /// This method gives access to the value of the node without cloning it. The node is locked for
+    /// reading while the closure runs, so the closure must not change the node.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The closure to call with the value of the node.
+    ///
+    /// # Returns
+    ///
+    /// The result of the closure.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::Node;
+    ///
+    /// let node = Node::new(1, Some(String::from("value")));
+    /// assert_eq!(node.with_value(|value| value.map(|x| x.len())), Some(5));
+    /// ```
+    pub fn with_value<R>(&self, reader: impl FnOnce(Option<&T>) -> R) -> R {
+        reader(P::read(&self.0).value.as_ref())
+    }
+
+    /// Borrow the ids of the children of the node.
+    ///
+    /// This method gives access to the ids of the children without cloning them. The returned guard
+    /// keeps the node locked for reading until it is dropped, like the guard of `value_ref`.
+    ///
+    /// # Returns
+    ///
+    /// A guard that dereferences to the ids of the children of the node.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::Node;
+    ///
+    /// let node = Node::new(1, Some(2));
+    /// node.add_child(Node::new(2, Some(3))).unwrap();
+    /// assert_eq!(&*node.children_ref(), &[2]);
+    /// ```
+    pub fn children_ref(&self) -> impl Deref<Target = [Q]> + '_ {
+        ChildrenRef::<Q, T, P>(P::read(&self.0))
     }
 
     /// Set the value of the node.
@@ -250,7 +400,7 @@ where
     /// assert_eq!(node.get_value().unwrap(), Some(3));
     /// ```
     pub fn set_value(&self, value: Option<T>) -> crate::prelude::Result<()> {
-        self.0.borrow_mut().value = value;
+        P::write(&self.0).value = value;
         Ok(())
     }
 
@@ -275,7 +425,7 @@ where
         &self,
         modifier: impl FnOnce(&mut Option<T>),
     ) -> crate::prelude::Result<()> {
-        let mut node = self.0.borrow_mut();
+        let mut node = P::write(&self.0);
         modifier(&mut node.value);
         Ok(())
     }
@@ -298,11 +448,11 @@ where
     /// child_node.set_parent(Some(parent_node.clone())).unwrap();
     /// assert_eq!(child_node.get_parent_id().unwrap().as_ref(), Some(&parent_node.get_node_id().unwrap()));
     /// ```
-    pub fn set_parent(&self, parent: Option<Node<Q, T>>) -> crate::prelude::Result<()> {
+    pub fn set_parent(&self, parent: Option<Node<Q, T, P>>) -> crate::prelude::Result<()> {
         if let Some(parent) = parent.as_ref() {
             parent.add_child(self.clone())?;
         }
-        self.0.borrow_mut().parent =
+        P::write(&self.0).parent =
             parent.map(|x| x.get_node_id().expect("Error: Could not fetch id of Node."));
         Ok(())
     }
@@ -331,79 +481,141 @@ where
     where
         Q: Debug,
     {
-        let mut children = self.0.borrow().children.clone();
+        let mut children = P::read(&self.0).children.clone();
         children.sort_by(|a, b| compare(a, b));
         self.update_children(children)?;
         Ok(())
     }
 
     fn update_children(&self, update: impl AsRef<[Q]>) -> crate::prelude::Result<()> {
-        let children = &mut self.0.borrow_mut().children;
+        let children = &mut P::write(&self.0).children;
         children.clear();
         children.extend_from_slice(update.as_ref());
         Ok(())
     }
 }
 
-impl<Q, T> PartialEq for Node<Q, T>
+impl<Q, T, P> Clone for Node<Q, T, P>
 where
     Q: PartialEq + Eq + Clone,
-    T: PartialEq + Eq + Clone,
+    P: NodeStorage,
+{
+    /// Clone the handle to the node.
+    ///
+    /// The clone shares its id, value, children and parent with the original node. The value does
+    /// not need to implement `Clone`.
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+/// A guard that dereferences to the value of a node.
+struct ValueRef<'a, Q, T, P>(P::Ref<'a, _Node<Q, T>>)
+where
+    Q: PartialEq + Eq + Clone + 'a,
+    T: 'a,
+    P: NodeStorage;
+
+impl<'a, Q, T, P> Deref for ValueRef<'a, Q, T, P>
+where
+    Q: PartialEq + Eq + Clone + 'a,
+    T: 'a,
+    P: NodeStorage,
+{
+    type Target = Option<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0.value
+    }
+}
+
+/// A guard that dereferences to the ids of the children of a node.
+struct ChildrenRef<'a, Q, T, P>(P::Ref<'a, _Node<Q, T>>)
+where
+    Q: PartialEq + Eq + Clone + 'a,
+    T: 'a,
+    P: NodeStorage;
+
+impl<'a, Q, T, P> Deref for ChildrenRef<'a, Q, T, P>
+where
+    Q: PartialEq + Eq + Clone + 'a,
+    T: 'a,
+    P: NodeStorage,
+{
+    type Target = [Q];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0.children
+    }
+}
+
+impl<Q, T, P> PartialEq for Node<Q, T, P>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq,
+    P: NodeStorage,
 {
     /// Compare two nodes for equality.
     fn eq(&self, other: &Self) -> bool {
-        self.get_node_id() == other.get_node_id() && self.get_value() == other.get_value()
+        self.get_node_id() == other.get_node_id() && *self.value_ref() == *other.value_ref()
     }
 }
 
-impl<Q, T> Display for Node<Q, T>
+impl<Q, T, P> Eq for Node<Q, T, P>
+where
+    Q: PartialEq + Eq + Clone,
+    T: Eq,
+    P: NodeStorage,
+{
+}
+
+impl<Q, T, P> Debug for Node<Q, T, P>
+where
+    Q: PartialEq + Eq + Clone + Debug,
+    T: Debug,
+    P: NodeStorage,
+{
+    /// Debug the node.
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_tuple("Node").field(&*P::read(&self.0)).finish()
+    }
+}
+
+impl<Q, T, P> Display for Node<Q, T, P>
 where
     Q: PartialEq + Eq + Clone + Display,
-    T: PartialEq + Eq + Clone + Display + Default,
+    T: Display + Default,
+    P: NodeStorage,
 {
     /// Display the node.
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         #[cfg(feature = "print_node_id")]
-        return write!(
-            f,
-            "{}: {}",
-            self.get_node_id()
-                .expect("Error: Could not fetch id of Node."),
-            self.get_value()
-                .expect("Error: Could not fetch value of Node.")
-                .as_ref()
-                .cloned()
-                .unwrap_or_default()
-        );
-        #[cfg(not(feature = "print_node_id"))]
         write!(
             f,
-            "{}",
-            self.get_value()
-                .expect("Error: Could not fetch value of Node.")
-                .as_ref()
-                .cloned()
-                .unwrap_or_default()
-        )
+            "{}: ",
+            self.get_node_id()
+                .expect("Error: Could not fetch id of Node.")
+        )?;
+        match self.value_ref().as_ref() {
+            Some(value) => write!(f, "{value}"),
+            None => write!(f, "{}", T::default()),
+        }
     }
 }
 
-impl<Q, T> Hash for Node<Q, T>
+impl<Q, T, P> Hash for Node<Q, T, P>
 where
     Q: PartialEq + Eq + Clone + Hash,
-    T: PartialEq + Eq + Clone + Hash,
+    T: Hash,
+    P: NodeStorage,
 {
     /// Hash the node.
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.get_node_id()
             .expect("Error: Could not fetch id of Node.")
             .hash(state);
-        self.get_value()
-            .expect("Error: Could not fetch value of Node.")
-            .hash(state);
-        self.get_children_ids()
-            .expect("Error: Could not fetch the children ids of  the Node.")
-            .hash(state);
+        self.value_ref().hash(state);
+        self.children_ref().hash(state);
         self.get_parent_id()
             .expect("Error: Could not fetch the parent id of the Node.")
             .hash(state);
@@ -419,20 +631,20 @@ where
 ///
 /// * `Q` - The type of the unique id of the node.
 /// * `T` - The type of the value of the node.
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
-pub struct Nodes<Q, T>
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct Nodes<Q, T, P = DefaultStorage>
 where
     Q: PartialEq + Eq + Clone,
-    T: PartialEq + Eq + Clone,
+    P: NodeStorage,
 {
-    nodes: Vec<Node<Q, T>>,
+    nodes: Vec<Node<Q, T, P>>,
     index: usize,
 }
 
-impl<Q, T> Nodes<Q, T>
+impl<Q, T, P> Nodes<Q, T, P>
 where
     Q: PartialEq + Eq + Clone,
-    T: PartialEq + Eq + Clone,
+    P: NodeStorage,
 {
     /// Create a new iterator over the nodes in a tree.
     ///
@@ -453,7 +665,7 @@ where
     ///
     /// let nodes = Nodes::new(vec![Node::new(1, Some(2))]);
     /// ```
-    pub fn new(nodes: Vec<Node<Q, T>>) -> Self {
+    pub fn new(nodes: Vec<Node<Q, T, P>>) -> Self {
         Nodes { nodes, index: 0 }
     }
 
@@ -476,7 +688,7 @@ where
     ///     // Do something with the node.
     /// }
     /// ```
-    pub fn iter(&self) -> Iter<'_, Node<Q, T>> {
+    pub fn iter(&self) -> Iter<'_, Node<Q, T, P>> {
         self.nodes.iter()
     }
 
@@ -531,7 +743,7 @@ where
     /// let nodes = Nodes::new(vec![Node::new(1, Some(2))]);
     /// assert_eq!(nodes.get(0).unwrap().get_node_id().unwrap(), 1);
     /// ```
-    pub fn get(&self, index: usize) -> Option<&Node<Q, T>> {
+    pub fn get(&self, index: usize) -> Option<&Node<Q, T, P>> {
         self.nodes.get(index)
     }
 
@@ -541,7 +753,8 @@ where
     ///
     /// # Arguments
     ///
-    /// * `node_id` - The node id of the node to get.
+    /// * `node_id` - The node id of the node to get. Any borrowed form of the id can be used, for
+    ///   instance a `&str` for `String` ids.
     ///
     /// # Returns
     ///
@@ -555,12 +768,12 @@ where
     /// let nodes = Nodes::new(vec![Node::new(1, Some(2))]);
     /// assert_eq!(nodes.get_by_node_id(&1).unwrap().get_node_id().unwrap(), 1);
     /// ```
-    pub fn get_by_node_id(&self, node_id: &Q) -> Option<&Node<Q, T>> {
-        self.nodes.iter().find(|x| {
-            &x.get_node_id()
-                .expect("Error: Could not get teh id of the node")
-                == node_id
-        })
+    pub fn get_by_node_id<K>(&self, node_id: &K) -> Option<&Node<Q, T, P>>
+    where
+        Q: Borrow<K>,
+        K: Eq + ?Sized,
+    {
+        self.nodes.iter().find(|x| x.has_id(node_id))
     }
 
     /// Push a node to the nodes list.
@@ -580,7 +793,7 @@ where
     /// nodes.push(Node::new(2, Some(3)));
     /// assert_eq!(nodes.len(), 2);
     /// ```
-    pub fn push(&mut self, node: Node<Q, T>) {
+    pub fn push(&mut self, node: Node<Q, T, P>) {
         self.nodes.push(node);
     }
 
@@ -606,7 +819,7 @@ where
     /// assert_eq!(removed_node.get_node_id().unwrap(), 1);
     /// assert_eq!(nodes.len(), 0);
     /// ```
-    pub fn remove(&mut self, index: usize) -> Node<Q, T> {
+    pub fn remove(&mut self, index: usize) -> Node<Q, T, P> {
         self.nodes.remove(index)
     }
 
@@ -632,7 +845,7 @@ where
     /// ```
     pub fn retain<F>(&mut self, f: F)
     where
-        F: FnMut(&Node<Q, T>) -> bool,
+        F: FnMut(&Node<Q, T, P>) -> bool,
     {
         self.nodes.retain(f);
     }
@@ -695,7 +908,7 @@ where
     /// nodes.append_raw(&mut other_nodes);
     /// assert_eq!(nodes.len(), 2);
     /// ```
-    pub fn append_raw(&mut self, other: &mut Vec<Node<Q, T>>) {
+    pub fn append_raw(&mut self, other: &mut Vec<Node<Q, T, P>>) {
         self.nodes.append(other);
     }
 
@@ -715,39 +928,53 @@ where
     /// let nodes = Nodes::new(vec![Node::new(1, Some(2)), Node::new(2, Some(3))]);
     /// assert_eq!(nodes.first().unwrap().get_node_id().unwrap(), 1);
     /// ```
-    pub fn first(&self) -> Option<&Node<Q, T>> {
+    pub fn first(&self) -> Option<&Node<Q, T, P>> {
         self.nodes.first()
     }
 }
 
-impl<Q, T> AsRef<Nodes<Q, T>> for Nodes<Q, T>
+impl<Q, T, P> Clone for Nodes<Q, T, P>
+where
+    Q: PartialEq + Eq + Clone,
+    P: NodeStorage,
+{
+    /// Clone the list of handles to the nodes.
+    fn clone(&self) -> Self {
+        Self {
+            nodes: self.nodes.clone(),
+            index: self.index,
+        }
+    }
+}
+
+impl<Q, T, P> AsRef<Nodes<Q, T, P>> for Nodes<Q, T, P>
 where
     Q: PartialEq + Eq + Clone,
-    T: PartialEq + Eq + Clone,
+    P: NodeStorage,
 {
     /// Get a reference to the nodes list.
-    fn as_ref(&self) -> &Nodes<Q, T> {
+    fn as_ref(&self) -> &Nodes<Q, T, P> {
         self
     }
 }
 
-impl<Q, T> FromIterator<Node<Q, T>> for Nodes<Q, T>
+impl<Q, T, P> FromIterator<Node<Q, T, P>> for Nodes<Q, T, P>
 where
     Q: PartialEq + Eq + Clone,
-    T: PartialEq + Eq + Clone,
+    P: NodeStorage,
 {
     /// Create a nodes list from an iterator.
-    fn from_iter<I: IntoIterator<Item = Node<Q, T>>>(iter: I) -> Self {
+    fn from_iter<I: IntoIterator<Item = Node<Q, T, P>>>(iter: I) -> Self {
         Nodes::new(iter.into_iter().collect())
     }
 }
 
-impl<Q, T> Iterator for Nodes<Q, T>
+impl<Q, T, P> Iterator for Nodes<Q, T, P>
 where
     Q: PartialEq + Eq + Clone,
-    T: PartialEq + Eq + Clone,
+    P: NodeStorage,
 {
-    type Item = Node<Q, T>;
+    type Item = Node<Q, T, P>;
 
     /// Get the next node in the nodes list.
     #[allow(clippy::iter_next_slice)]
@@ -763,10 +990,10 @@ where
     }
 }
 
-impl<Q, T> Default for Nodes<Q, T>
+impl<Q, T, P> Default for Nodes<Q, T, P>
 where
     Q: PartialEq + Eq + Clone,
-    T: PartialEq + Eq + Clone,
+    P: NodeStorage,
 {
     /// Create an empty nodes list.
     fn default() -> Self {
@@ -774,10 +1001,11 @@ where
     }
 }
 
-impl<Q, T> Display for Nodes<Q, T>
+impl<Q, T, P> Display for Nodes<Q, T, P>
 where
     Q: PartialEq + Eq + Clone + Display,
-    T: PartialEq + Eq + Clone + Display + Default,
+    T: Display + Default,
+    P: NodeStorage,
 {
     /// Display the nodes list.
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
@@ -789,25 +1017,27 @@ where
 }
 
 #[cfg(feature = "serde")]
-impl<Q, T> Serialize for Node<Q, T>
+impl<Q, T, P> Serialize for Node<Q, T, P>
 where
     Q: PartialEq + Eq + Clone + Serialize,
-    T: PartialEq + Eq + Clone + Serialize,
+    T: Serialize,
+    P: NodeStorage,
 {
     /// Serialize the node.
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        self.0.borrow().serialize(serializer)
+        P::read(&self.0).serialize(serializer)
     }
 }
 
 #[cfg(feature = "serde")]
-impl<'de, Q, T> Deserialize<'de> for Node<Q, T>
+impl<'de, Q, T, P> Deserialize<'de> for Node<Q, T, P>
 where
     Q: PartialEq + Eq + Clone + Deserialize<'de>,
-    T: PartialEq + Eq + Clone + Deserialize<'de>,
+    T: Deserialize<'de>,
+    P: NodeStorage,
 {
     /// Deserialize the node.
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -816,15 +1046,16 @@ where
     {
         let node: _Node<Q, T> = Deserialize::deserialize(deserializer)?;
 
-        Ok(Node(Rc::new(RefCell::new(node))))
+        Ok(Node(P::new_pointer(node)))
     }
 }
 
 #[cfg(feature = "serde")]
-impl<Q, T> Serialize for Nodes<Q, T>
+impl<Q, T, P> Serialize for Nodes<Q, T, P>
 where
     Q: PartialEq + Eq + Clone + Serialize,
-    T: PartialEq + Eq + Clone + Serialize,
+    T: Serialize,
+    P: NodeStorage,
 {
     /// Serialize the nodes list.
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -836,17 +1067,18 @@ where
 }
 
 #[cfg(feature = "serde")]
-impl<'de, Q, T> Deserialize<'de> for Nodes<Q, T>
+impl<'de, Q, T, P> Deserialize<'de> for Nodes<Q, T, P>
 where
     Q: PartialEq + Eq + Clone + Deserialize<'de>,
-    T: PartialEq + Eq + Clone + Deserialize<'de>,
+    T: Deserialize<'de>,
+    P: NodeStorage,
 {
     /// Deserialize the nodes list.
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        let nodes: Vec<Node<Q, T>> = Deserialize::deserialize(deserializer)?;
+        let nodes: Vec<Node<Q, T, P>> = Deserialize::deserialize(deserializer)?;
         if cfg!(feature = "compact_serde") {
             // Rebuild the children data from the parent data.
             for node in nodes.iter() {
@@ -876,7 +1108,6 @@ where
 impl<Q, T> Node<Q, T>
 where
     Q: PartialEq + Eq + Clone + From<u128>,
-    T: PartialEq + Eq + Clone,
 {
     /// Creates a new node with an auto-generated ID.
     ///
@@ -884,6 +1115,10 @@ where
     /// This is useful when you want to create a node without specifying the ID. For a node to be
     /// created with an auto-generated ID, the `Q` type must be of type `AutomatedId`.
     ///
+    /// The generator is shared by the whole program rather than owned by a tree, since the node does
+    /// not belong to a tree yet and its id must not collide in whichever tree it is added to. Use
+    /// `Tree::add_value` to take ids from the generator of a tree instead.
+    ///
     /// # Arguments
     ///
     /// * `value` - The value to store in the node.
@@ -907,11 +1142,32 @@ where
     ///
     /// This is available only when the `auto_id` feature is enabled.
     pub fn new_with_auto_id(value: Option<T>) -> Self {
-        Self(Rc::new(RefCell::new(_Node {
-            node_id: Q::from(GENERATOR.generate()),
-            value,
-            children: vec![],
-            parent: None,
-        })))
+        Node::new_with_auto_id_in(value, DefaultStorage::default())
+    }
+}
+
+#[cfg(feature = "auto_id")]
+impl<Q, T, P> Node<Q, T, P>
+where
+    Q: PartialEq + Eq + Clone + From<u128>,
+    P: NodeStorage,
+{
+    /// Creates a new node with an auto-generated ID and the given storage.
+    ///
+    /// This method creates a new node in the same way as `Node::new_with_auto_id` but lets the
+    /// storage of the node be chosen.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to store in the node.
+    /// * `storage` - The storage used to share the data of the node.
+    ///
+    /// # Returns
+    ///
+    /// A new node with an auto-generated ID.
+    ///
+    /// This is available only when the `auto_id` feature is enabled.
+    pub fn new_with_auto_id_in(value: Option<T>, storage: P) -> Self {
+        Node::new_in(Q::from(GENERATOR.generate()), value, storage)
     }
 }
@@ -1,18 +1,26 @@
 use crate::lib::*;
-#[cfg(feature = "async")]
-pub use crate::node::async_node::{Node, Nodes};
 #[cfg(feature = "serde")]
 use ::serde::{ser::SerializeStruct, Deserialize, Serialize};
 
-#[cfg(not(feature = "async"))]
-pub use crate::node::sync_node::{Node, Nodes};
+pub use crate::node::base::{Node, Nodes};
+pub use crate::node::storage::{ArcStorage, DefaultStorage, NodeStorage, RcStorage};
 
-#[cfg(feature = "async")]
-mod async_node;
+mod base;
+mod storage;
 
-#[cfg(not(feature = "async"))]
-mod sync_node;
+/// A node that keeps its data in an `Rc<RefCell<_>>`.
+///
+/// This is the node type of a `LocalTree`. It can not be sent to other threads.
+pub type LocalNode<Q, T> = Node<Q, T, RcStorage>;
 
+/// A node that keeps its data in an `Arc<RwLock<_>>`.
+///
+/// This is the node type of a `ThreadSafeTree`. It is `Send` and `Sync` as long as the id and the
+/// value are.
+pub type ThreadSafeNode<Q, T> = Node<Q, T, ArcStorage>;
+
+// The generator of `Node::new_with_auto_id`. Nodes created this way do not belong to a tree yet, so
+// one generator serves the whole program to keep their ids unique across trees.
 #[cfg(all(feature = "no_std", feature = "auto_id"))]
 lazy_static::lazy_static! {
     static ref GENERATOR: sequential_gen::prelude::SimpleGenerator<u128> =
@@ -28,7 +36,6 @@ pub const GENERATOR: sequential_gen::prelude::EpochBasedGenerator =
 pub(crate) struct _Node<Q, T>
 where
     Q: PartialEq + Eq + Clone,
-    T: PartialEq + Eq + Clone,
 {
     /// The user supplied id of the node.
     node_id: Q,
@@ -44,7 +51,7 @@ where
 impl<Q, T> Serialize for _Node<Q, T>
 where
     Q: PartialEq + Eq + Clone + Serialize,
-    T: PartialEq + Eq + Clone + Serialize,
+    T: Serialize,
 {
     /// Serialize the node.
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -65,7 +72,7 @@ where
 impl<'de, Q, T> Deserialize<'de> for _Node<Q, T>
 where
     Q: PartialEq + Eq + Clone + Deserialize<'de>,
-    T: PartialEq + Eq + Clone + Deserialize<'de>,
+    T: Deserialize<'de>,
 {
     /// Deserialize the node.
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -112,6 +119,15 @@ mod tests {
     use super::*;
     use crate::prelude::Result;
 
+    #[test]
+    fn test_default_storage_is_rc_with_any_feature() {
+        assert_eq!(TypeId::of::<DefaultStorage>(), TypeId::of::<RcStorage>());
+        assert_eq!(
+            TypeId::of::<Node<u32, u32>>(),
+            TypeId::of::<LocalNode<u32, u32>>()
+        );
+    }
+
     #[test]
     fn test_node_new() -> Result<()> {
         let node = Node::new(1, Some(2));
@@ -197,6 +213,35 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_node_borrowing_accessors() -> Result<()> {
+        let node = Node::new(1, Some(String::from("value")));
+        node.add_child(Node::new(2, None))?;
+        node.add_child(Node::new(3, None))?;
+        assert_eq!(node.value_ref().as_deref(), Some("value"));
+        assert_eq!(node.with_value(|value| value.map(|x| x.len())), Some(5));
+        assert_eq!(&*node.children_ref(), &[2, 3]);
+        {
+            let first = node.value_ref();
+            let second = node.value_ref();
+            assert_eq!(*first, *second);
+        }
+        node.update_value(|value| *value = None)?;
+        assert!(node.value_ref().is_none());
+        assert!(node.with_value(|value| value.is_none()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_node_borrowing_accessors_thread_safe() -> Result<()> {
+        let node = ThreadSafeNode::new_in(1, Some(vec![1, 2, 3]), ArcStorage);
+        node.add_child(ThreadSafeNode::new_in(2, None, ArcStorage))?;
+        assert_eq!(node.value_ref().as_deref(), Some(&[1, 2, 3][..]));
+        assert_eq!(node.with_value(|value| value.map(|x| x.len())), Some(3));
+        assert_eq!(&*node.children_ref(), &[2]);
+        Ok(())
+    }
+
     #[test]
     fn test_node_eq() {
         let node1 = Node::new(1, Some(2));
@@ -365,6 +410,17 @@ mod tests {
         #[cfg(not(feature = "print_node_id"))]
         assert_eq!(format!("{nodes}"), "2");
     }
+
+    #[test]
+    fn test_node_new_in_storage() -> Result<()> {
+        let local_node = Node::new_in(1, Some(2), RcStorage);
+        let thread_safe_node = ThreadSafeNode::new_in(1, Some(2), ArcStorage);
+        local_node.add_child(LocalNode::new_in(2, Some(3), RcStorage))?;
+        thread_safe_node.add_child(Node::new_in(2, Some(3), ArcStorage))?;
+        assert_eq!(local_node.get_children_ids()?, vec![2]);
+        assert_eq!(thread_safe_node.get_children_ids()?, vec![2]);
+        Ok(())
+    }
 }
 
 #[cfg(all(test, feature = "serde"))]
@@ -0,0 +1,89 @@
+use crate::lib::*;
+
+/// The strategy used to share and lock the data of a node.
+///
+/// A node is a cheap handle to data that is shared with the tree that holds it, so both the node
+/// and the tree see the same value, children and parent. This trait defines the pointer used to
+/// share that data and how it is borrowed. Nodes and trees are generic over their storage, which
+/// lets single threaded and thread safe trees be used side by side in the same program.
+///
+/// Two storages are provided:
+///
+/// * `RcStorage` keeps the data in an `Rc<RefCell<_>>`. It is the cheapest option but the nodes
+///   can not be sent to other threads.
+/// * `ArcStorage` keeps the data in an `Arc<RwLock<_>>`. The nodes are `Send` and `Sync` as long
+///   as the ids and values are.
+///
+/// The `DefaultStorage` is the storage used by `Tree<Q, T>` and `Node<Q, T>` when no storage is
+/// given. It is always `RcStorage`, so enabling a feature never changes the storage of a tree.
+pub trait NodeStorage:
+    Clone + Copy + Debug + Default + PartialEq + Eq + Hash + Send + Sync + 'static
+{
+    /// The shared pointer to the data of a node.
+    type Pointer<V>: Clone;
+
+    /// A guard that gives read access to the data of a node.
+    type Ref<'a, V: 'a>: Deref<Target = V>;
+
+    /// A guard that gives write access to the data of a node.
+    type RefMut<'a, V: 'a>: DerefMut<Target = V>;
+
+    /// Move a value into a new shared pointer.
+    fn new_pointer<V>(value: V) -> Self::Pointer<V>;
+
+    /// Borrow the value behind a pointer for reading.
+    fn read<V>(pointer: &Self::Pointer<V>) -> Self::Ref<'_, V>;
+
+    /// Borrow the value behind a pointer for writing.
+    fn write<V>(pointer: &Self::Pointer<V>) -> Self::RefMut<'_, V>;
+}
+
+/// A single threaded storage that keeps the data of each node in an `Rc<RefCell<_>>`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RcStorage;
+
+impl NodeStorage for RcStorage {
+    type Pointer<V> = Rc<RefCell<V>>;
+    type Ref<'a, V: 'a> = Ref<'a, V>;
+    type RefMut<'a, V: 'a> = RefMut<'a, V>;
+
+    fn new_pointer<V>(value: V) -> Self::Pointer<V> {
+        Rc::new(RefCell::new(value))
+    }
+
+    fn read<V>(pointer: &Self::Pointer<V>) -> Self::Ref<'_, V> {
+        RefCell::borrow(pointer)
+    }
+
+    fn write<V>(pointer: &Self::Pointer<V>) -> Self::RefMut<'_, V> {
+        pointer.borrow_mut()
+    }
+}
+
+/// A thread safe storage that keeps the data of each node in an `Arc<RwLock<_>>`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ArcStorage;
+
+impl NodeStorage for ArcStorage {
+    type Pointer<V> = Arc<RwLock<V>>;
+    type Ref<'a, V: 'a> = RwLockReadGuard<'a, V>;
+    type RefMut<'a, V: 'a> = RwLockWriteGuard<'a, V>;
+
+    fn new_pointer<V>(value: V) -> Self::Pointer<V> {
+        Arc::new(RwLock::new(value))
+    }
+
+    fn read<V>(pointer: &Self::Pointer<V>) -> Self::Ref<'_, V> {
+        pointer.read()
+    }
+
+    fn write<V>(pointer: &Self::Pointer<V>) -> Self::RefMut<'_, V> {
+        pointer.write()
+    }
+}
+
+/// The storage used when none is given.
+///
+/// This is always `RcStorage`, whatever features are enabled. Thread safe trees use `ArcStorage`
+/// explicitly, see `ThreadSafeTree` and `ThreadSafeNode`.
+pub type DefaultStorage = RcStorage;
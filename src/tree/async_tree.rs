@@ -1,19 +1,17 @@
-use crate::error::Error::{InvalidOperation, NodeNotFound, RootNodeAlreadyPresent};
 use crate::lib::*;
-use crate::node::{Node, Nodes};
-use crate::tree::{NodeRemovalStrategy, SubTree, TraversalStrategy};
-#[cfg(feature = "serde")]
-use ::serde::{ser::SerializeStruct, Deserialize, Serialize};
+use crate::node::{ArcStorage, Node};
+use crate::tree::{NodeRemovalStrategy, SubTree, ThreadSafeTree, TraversalStrategy};
 
-/// A tree data structure.
+/// A thread safe tree with an async API.
 ///
-/// This struct represents a tree data structure. A tree is a data structure that consists of nodes
-/// connected by edges. Each node has a parent node and zero or more child nodes. The tree has a root
-/// node that is the topmost node in the tree. The tree can be used to represent hierarchical data
-/// structures such as file systems, organization charts, and family trees. A tree can have any number
-/// of nodes and each node can have any number of children. The tree can be traversed in different
-/// orders such as pre-order, post-order, and in-order. The tree can be named for easy identification
-/// when working with multiple trees or subtrees.
+/// This struct wraps a `ThreadSafeTree` in an async read-write lock. Reading and writing the tree are
+/// `async fn`s that wait for the lock by returning to the executor instead of spinning, so a task
+/// that is waiting for a writer does not keep a thread busy. The lock does not depend on any async
+/// runtime and works with any executor.
+///
+/// Any number of readers can hold the lock at the same time, while a writer holds it alone. Writers
+/// are preferred: once a writer is waiting, new readers wait until it is done. A task must
+/// therefore not wait for a second guard while it is holding one.
 ///
 /// # Type Parameters
 ///
@@ -23,951 +21,478 @@ use ::serde::{ser::SerializeStruct, Deserialize, Serialize};
 /// # Example
 ///
 /// ```rust
-/// # use tree_ds::prelude::Tree;
+/// # use tree_ds::prelude::*;
+///
+/// # async fn run() -> Result<()> {
+/// let tree: AsyncTree<i32, i32> = AsyncTree::new(ThreadSafeTree::new_in(None, ArcStorage));
+/// let root = tree.add_node(Node::new_in(1, Some(2), ArcStorage), None).await?;
+/// tree.add_node(Node::new_in(2, Some(3), ArcStorage), Some(&root)).await?;
 ///
-/// let tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+/// let height = tree.read().await.get_height()?;
+/// assert_eq!(height, 1);
+/// # Ok(())
+/// # }
 /// ```
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
-pub struct Tree<Q, T>
+///
+/// This is available only when the `async` feature is enabled.
+pub struct AsyncTree<Q, T>
 where
-    Q: PartialEq + Eq + Clone + Send + Sync,
-    T: PartialEq + Eq + Clone + Send + Sync,
+    Q: PartialEq + Eq + Clone,
 {
-    name: Option<String>,
-    nodes: Nodes<Q, T>,
+    state: Mutex<LockState>,
+    tree: RwLock<ThreadSafeTree<Q, T>>,
 }
 
-impl<Q, T> Tree<Q, T>
-where
-    Q: PartialEq + Eq + Clone + Display + Hash + Ord + Send + Sync,
-    T: PartialEq + Eq + Clone + Send + Sync,
-{
-    /// Create a new tree.
-    ///
-    /// This method creates a new tree with no nodes.
-    ///
-    /// # Returns
-    ///
-    /// A new tree with no nodes.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// # use tree_ds::prelude::Tree;
-    ///
-    /// let tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    /// ```
-    pub fn new(tree_name: Option<&str>) -> Self {
-        Self {
-            name: tree_name.map(|x| x.to_string()),
-            nodes: Nodes::default(),
-        }
-    }
+/// The state of the lock of an `AsyncTree`.
+#[derive(Default)]
+struct LockState {
+    /// The number of readers holding the lock.
+    readers: usize,
+    /// Whether a writer is holding the lock.
+    writer: bool,
+    /// The number of writers waiting for the lock.
+    waiting_writers: usize,
+    /// The wakers of the tasks waiting for the lock.
+    wakers: Vec<Waker>,
+}
 
-    /// Add a node to the tree.
-    ///
-    /// This method adds a node to the tree. The node is added as a child of the parent node with the
-    /// given parent id. If the parent id is `None`, the node is added as a root node. The node id is
-    /// used to identify the node and the value is the value of the node. The value can be used to store
-    /// any data that you want to associate with the node.
-    ///
-    /// # Arguments
-    ///
-    /// * `node_id` - The id of the node.
-    /// * `value` - The value of the node.
-    /// * `parent_id` - The id of the parent node. If `None`, the node is added as a root node.
-    ///
-    /// # Returns
-    ///
-    /// The id of the node that was added to the tree. However, if no parent id is provided and the tree already
-    /// has a root node, an error is returned.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// # use tree_ds::prelude::{Tree, Node};
-    ///
-    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    /// let node_id = tree.add_node(Node::new(1, Some(2)), None);
-    ///
-    /// assert!(node_id.is_ok());
-    /// // This should return an error because the tree already has a root node.
-    /// let another_node_id = tree.add_node(Node::new(2, Some(3)), None);
-    /// assert!(another_node_id.is_err());
-    /// ```
-    pub fn add_node(
-        &mut self,
-        node: Node<Q, T>,
-        parent_id: Option<&Q>,
-    ) -> crate::prelude::Result<Q> {
-        if let Some(parent_id) = parent_id {
-            let parent = self
-                .nodes
-                .iter()
-                .find(|n| &n.get_node_id().expect("Error: Failed to get the node Id.") == parent_id)
-                .ok_or(NodeNotFound(parent_id.to_string()))?;
-            parent.add_child(node.clone())?;
-        } else if self.get_root_node().is_some() {
-            return Err(RootNodeAlreadyPresent);
+impl LockState {
+    /// Register a task to be woken up when the lock is released.
+    fn register(&mut self, waker: &Waker) {
+        if !self.wakers.iter().any(|x| x.will_wake(waker)) {
+            self.wakers.push(waker.clone());
         }
-        self.nodes.push(node.clone());
-        node.get_node_id()
-    }
-
-    /// Get the name of the tree.
-    ///
-    /// This method gets the name of the tree.
-    ///
-    /// # Returns
-    ///
-    /// The name of the tree.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// # use tree_ds::prelude::Tree;
-    ///
-    /// let tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    ///
-    /// assert_eq!(tree.get_name(), Some("Sample Tree"));
-    /// ```
-    pub fn get_name(&self) -> Option<&str> {
-        self.name.as_deref()
-    }
-
-    /// Set the name of the tree.
-    ///
-    /// This method sets the name of the tree.
-    ///
-    /// # Arguments
-    ///
-    /// * `name` - The name of the tree.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// # use tree_ds::prelude::Tree;
-    ///
-    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    /// tree.rename(Some("New Name"));
-    /// assert_eq!(tree.get_name(), Some("New Name"));
-    /// ```
-    pub fn rename(&mut self, name: Option<&str>) {
-        self.name = name.map(|x| x.to_string());
     }
+}
 
-    /// Get a node in the tree.
-    ///
-    /// This method gets the node with the given node id in the tree.
+impl<Q, T> AsyncTree<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+{
+    /// Create a new async tree.
     ///
     /// # Arguments
     ///
-    /// * `node_id` - The id of the node.
-    ///
-    /// # Returns
-    ///
-    /// The node with the given node id in the tree or `None` if the node is not found.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// # use tree_ds::prelude::{Node, Tree};
-    ///
-    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    ///
-    /// let node = Node::new(1, Some(2));
-    /// let node_id = tree.add_node(node.clone(), None).unwrap();
-    ///
-    /// assert_eq!(tree.get_node_by_id(&node_id), Some(node));
-    /// ```
-    pub fn get_node_by_id(&self, node_id: &Q) -> Option<Node<Q, T>> {
-        self.nodes
-            .iter()
-            .find(|n| &n.get_node_id().expect("Error: Failed to get the node Id.") == node_id)
-            .cloned()
-    }
-
-    /// Get the root node of the tree.
-    ///
-    /// This method gets the root node of the tree. The root node is the topmost node in the tree. The
-    /// root node has no parent node.
+    /// * `tree` - The tree to wrap.
     ///
     /// # Returns
     ///
-    /// The root node of the tree or `None` if the tree has no root node.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// # use tree_ds::prelude::{Node, Tree};
-    ///
-    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    ///
-    /// let node = Node::new(1, Some(2));
-    /// tree.add_node(node.clone(), None).unwrap();
-    ///
-    /// assert_eq!(tree.get_root_node(), Some(node));
-    /// ```
-    pub fn get_root_node(&self) -> Option<Node<Q, T>> {
-        self.nodes
-            .iter()
-            .find(|n| {
-                n.get_parent_id()
-                    .expect("Error: Failed to get the node Id of the parent.")
-                    .is_none()
-            })
-            .cloned()
-    }
-
-    /// Get the height of the node.
-    ///
-    /// This method gets the height of the node. The height of the node is the number of edges present
-    /// in the longest path connecting the node to a leaf node.
-    ///
-    /// # Returns
-    ///
-    /// The height of the node. If the node is a leaf node, the height is 0.  This method returns an
-    /// error if the node is not found in the tree.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// # use tree_ds::prelude::{Node, Tree};
-    ///
-    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    ///
-    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None).unwrap();
-    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1)).unwrap();
-    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2)).unwrap();
-    ///
-    /// assert!(tree.get_node_height(&node_2).is_ok());
-    /// assert_eq!(tree.get_node_height(&node_2).unwrap(), 1);
-    /// ```
-    pub fn get_node_height(&self, node_id: &Q) -> crate::prelude::Result<i32> {
-        let node = self
-            .get_node_by_id(node_id)
-            .ok_or(NodeNotFound(node_id.to_string()))?;
-        let children = node.get_children_ids()?;
-        if children.is_empty() {
-            return Ok(0);
-        }
-        let mut height = 0;
-        for child in children {
-            let child_height = self.get_node_height(&child)?;
-            if child_height > height {
-                height = child_height;
-            }
+    /// A new async tree holding the given tree.
+    pub fn new(tree: ThreadSafeTree<Q, T>) -> Self {
+        Self {
+            state: Mutex::new(LockState::default()),
+            tree: RwLock::new(tree),
         }
-        Ok(height + 1)
     }
 
-    /// Get the depth of a node in the tree.
-    ///
-    /// This method gets the depth of a node in the tree. The depth of a node is the length of the path
-    /// from the root node to the node. The depth of the node is the number of edges on the path from the
-    /// root node to the node.
-    ///
-    /// # Arguments
+    /// Get shared access to the tree.
     ///
-    /// * `node_id` - The id of the node.
+    /// This method waits until no writer holds or is waiting for the lock.
     ///
     /// # Returns
     ///
-    /// The depth of the node in the tree.  This method returns an error if the node is not found in the tree.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// # use tree_ds::prelude::{Node, Tree};
-    ///
-    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    ///
-    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None).unwrap();
-    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1)).unwrap();
-    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2)).unwrap();
-    /// let depth_result = tree.get_node_depth(&node_3);
-    /// assert!(depth_result.is_ok());
-    /// assert_eq!(depth_result.unwrap(), 2);
-    /// ```
-    pub fn get_node_depth(&self, node_id: &Q) -> crate::prelude::Result<i32> {
-        let node = self
-            .get_node_by_id(node_id)
-            .ok_or(NodeNotFound(node_id.to_string()))?;
-        let mut depth = 0;
-        let mut parent = node.get_parent_id()?;
-        while let Some(parent_id) = parent {
-            depth += 1;
-            parent = self
-                .get_node_by_id(&parent_id)
-                .ok_or(NodeNotFound(parent_id.to_string()))?
-                .get_parent_id()?;
+    /// A guard that gives read access to the tree until it is dropped.
+    pub async fn read(&self) -> AsyncTreeReadGuard<'_, Q, T> {
+        poll_fn(|cx| {
+            let mut state = self.state.lock();
+            if state.writer || state.waiting_writers > 0 {
+                state.register(cx.waker());
+                return Poll::Pending;
+            }
+            state.readers += 1;
+            Poll::Ready(())
+        })
+        .await;
+        AsyncTreeReadGuard {
+            tree: self,
+            guard: Some(self.tree.read()),
         }
-        Ok(depth)
     }
 
-    /// Get the ancestors of a node in the tree.
-    ///
-    /// This method gets the ancestors of a node in the tree. The ancestors of a node are all the nodes
-    /// that are on the path from the root node to the node, not including the node itself.
-    ///
-    /// # Arguments
+    /// Get exclusive access to the tree.
     ///
-    /// * `node_id` - The id of the node.
+    /// This method waits until no reader or writer holds the lock.
     ///
     /// # Returns
     ///
-    /// The ancestors of the node from closest to furthest.  This method returns an error if the node is not found in the tree.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// # use tree_ds::prelude::{Node, Tree};
-    ///
-    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    ///
-    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None).unwrap();
-    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1)).unwrap();
-    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2)).unwrap();
-    /// let depth_result = tree.get_ancestor_ids(&node_3);
-    /// assert!(depth_result.is_ok());
-    /// assert_eq!(depth_result.unwrap(), vec![2, 1]);
-    /// ```
-    pub fn get_ancestor_ids(&self, node_id: &Q) -> crate::prelude::Result<Vec<Q>> {
-        let node = self
-            .get_node_by_id(node_id)
-            .ok_or(NodeNotFound(node_id.to_string()))?;
-        let mut ancestors = vec![];
-        let mut parent = node.get_parent_id()?;
-        while let Some(parent_id) = parent {
-            ancestors.push(parent_id.clone());
-            parent = self
-                .get_node_by_id(&parent_id)
-                .ok_or(NodeNotFound(parent_id.to_string()))?
-                .get_parent_id()?;
+    /// A guard that gives write access to the tree until it is dropped.
+    pub async fn write(&self) -> AsyncTreeWriteGuard<'_, Q, T> {
+        // Stop counting this writer as waiting if the future is dropped before it gets the lock.
+        let mut waiting = WaitingWriter {
+            tree: self,
+            waiting: false,
+        };
+        poll_fn(|cx| {
+            let mut state = self.state.lock();
+            if state.writer || state.readers > 0 {
+                if !waiting.waiting {
+                    waiting.waiting = true;
+                    state.waiting_writers += 1;
+                }
+                state.register(cx.waker());
+                return Poll::Pending;
+            }
+            if waiting.waiting {
+                waiting.waiting = false;
+                state.waiting_writers -= 1;
+            }
+            state.writer = true;
+            Poll::Ready(())
+        })
+        .await;
+        AsyncTreeWriteGuard {
+            tree: self,
+            guard: Some(self.tree.write()),
         }
-        Ok(ancestors)
     }
 
-    /// Get the height of the tree.
-    ///
-    /// This method gets the height of the tree. The height of the tree is the length of the longest path
-    /// from the root node to a leaf node. The height of the tree is the number of edges on the longest
-    /// path from the root node to a leaf node.
-    ///
-    /// # Returns
-    ///
-    /// The height of the tree. This method returns an error if the tree has no root node.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// # use tree_ds::prelude::{Node, Tree, Result};
-    ///
-    /// # fn main() -> Result<()> {
-    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    ///
-    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
-    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
-    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
-    /// let tree_height = tree.get_height();
-    /// assert!(tree_height.is_ok());
-    /// assert_eq!(tree_height?, 2);
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub fn get_height(&self) -> crate::prelude::Result<i32> {
-        let root = self
-            .get_root_node()
-            .ok_or(InvalidOperation(String::from("Tree has no root node")))?;
-        self.get_node_height(&root.get_node_id()?)
+    /// Consume the async tree and return the tree it holds.
+    pub fn into_inner(self) -> ThreadSafeTree<Q, T> {
+        self.tree.into_inner()
     }
 
-    /// Get the degree of a node in the tree.
-    ///
-    /// This method gets the degree of a node in the tree. The degree of a node is the number of children
-    /// that the node has.
-    ///
-    /// # Arguments
-    ///
-    /// * `node_id` - The id of the node.
-    ///
-    /// # Returns
-    ///
-    /// The degree of the node in the tree. This method returns an error if the node is not found in the tree.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// # use tree_ds::prelude::{Result, Node, Tree};
-    ///
-    /// # fn main() -> Result<()> {
-    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    ///
-    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
-    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
-    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
-    ///
-    /// assert_eq!(tree.get_node_degree(&node_1)?, 2);
-    /// assert_eq!(tree.get_node_degree(&node_2)?, 0);
-    /// assert_eq!(tree.get_node_degree(&node_3)?, 0);
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub fn get_node_degree(&self, node_id: &Q) -> crate::prelude::Result<i32> {
-        let node = self
-            .get_node_by_id(node_id)
-            .ok_or(NodeNotFound(node_id.to_string()))?;
-        Ok(node.get_children_ids()?.len() as i32)
+    /// Release the lock held by a reader or a writer and wake up the waiting tasks.
+    fn release(&self, writer: bool) {
+        let wakers = {
+            let mut state = self.state.lock();
+            if writer {
+                state.writer = false;
+            } else {
+                state.readers -= 1;
+                if state.readers > 0 {
+                    return;
+                }
+            }
+            mem::take(&mut state.wakers)
+        };
+        wakers.into_iter().for_each(Waker::wake);
     }
+}
 
-    /// Get the nodes in the tree.
-    ///
-    /// This method gets the nodes in the tree.
-    ///
-    /// # Returns
-    ///
-    /// The nodes in the tree.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// # use tree_ds::prelude::{Node, Tree};
-    ///
-    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    ///
-    /// let node = Node::new(1, Some(2));
-    /// tree.add_node(node.clone(), None).unwrap();
+impl<Q, T> AsyncTree<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Debug + Hash + Ord,
+{
+    /// Add a node to the tree.
     ///
-    /// assert_eq!(tree.get_nodes().len(), 1);
-    /// ```
-    pub fn get_nodes(&self) -> &Nodes<Q, T> {
-        self.nodes.as_ref()
+    /// This method waits for exclusive access and then behaves like `Tree::add_node`.
+    pub async fn add_node(
+        &self,
+        node: Node<Q, T, ArcStorage>,
+        parent_id: Option<&Q>,
+    ) -> crate::prelude::Result<Q> {
+        self.write().await.add_node(node, parent_id)
     }
 
     /// Remove a node from the tree.
     ///
-    /// This method removes a node from the tree. The node is removed using the given removal strategy.
-    /// The removal strategy determines how the node and its children are removed from the tree. The
-    /// `RetainChildren` strategy retains the children of the node when the node is removed. The
-    /// `RemoveNodeAndChildren` strategy removes the node and its children when the node is removed.
-    ///
-    /// # Arguments
-    ///
-    /// * `node_id` - The id of the node to remove.
-    /// * `strategy` - The strategy to use when removing the node.
-    ///
-    /// # Returns
-    /// An error if the node is not found in the tree or if the node is the root node and the removal
-    /// strategy is `RetainChildren`.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// # use tree_ds::prelude::{Node, Tree, NodeRemovalStrategy, Result};
-    ///
-    /// # fn main() -> Result<()> {
-    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    ///
-    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
-    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
-    /// tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
-    ///
-    /// tree.remove_node(&node_2, NodeRemovalStrategy::RetainChildren)?;
-    /// assert_eq!(tree.get_nodes().len(), 2);
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub fn remove_node(
-        &mut self,
+    /// This method waits for exclusive access and then behaves like `Tree::remove_node`.
+    pub async fn remove_node(
+        &self,
         node_id: &Q,
         strategy: NodeRemovalStrategy,
     ) -> crate::prelude::Result<()> {
-        match strategy {
-            NodeRemovalStrategy::RetainChildren => {
-                let node = self
-                    .get_node_by_id(node_id)
-                    .ok_or(NodeNotFound(node_id.to_string()))?;
-                let parent_node_id = &node.get_parent_id()?.ok_or(InvalidOperation(
-                    String::from("Cannot remove root node with RetainChildren strategy"),
-                ))?;
-                let parent_node = self
-                    .get_node_by_id(parent_node_id)
-                    .ok_or(NodeNotFound(parent_node_id.to_string()))?;
-                parent_node.remove_child(node.clone())?;
-                let children = node.get_children_ids()?;
-                for child in children {
-                    if let Some(child) = self.get_node_by_id(&child) {
-                        parent_node.add_child(child)?;
-                    }
-                }
-                self.nodes.retain(|n| {
-                    &n.get_node_id().expect("Error: Failed to get the node Id.") != node_id
-                });
-                Ok(())
-            }
-            NodeRemovalStrategy::RemoveNodeAndChildren => {
-                let node = self
-                    .get_node_by_id(node_id)
-                    .ok_or(NodeNotFound(node_id.to_string()))?;
-                let children = node.get_children_ids()?;
-                if let Some(parent_id) = node.get_parent_id()? {
-                    let parent = self
-                        .get_node_by_id(&parent_id)
-                        .ok_or(NodeNotFound(parent_id.to_string()))?;
-                    parent.remove_child(node.clone())?;
-                }
-                self.nodes.retain(|n| {
-                    &n.get_node_id().expect("Error: Failed to get the node Id.") != node_id
-                });
-                for child in children {
-                    let child = self
-                        .get_node_by_id(&child)
-                        .ok_or(NodeNotFound(child.to_string()))?;
-                    node.remove_child(child.clone())?;
-                    self.remove_node(&child.get_node_id()?, strategy)?;
-                }
-                Ok(())
-            }
-        }
+        self.write().await.remove_node(node_id, strategy)
     }
 
-    /// Get a subsection of the tree.
-    ///
-    /// This method gets a subsection of the tree starting from the node with the given node id. The
-    /// subsection is a list of nodes that are descendants of the node with the given node id upto the
-    /// given number of descendants. If the number of descendants is `None`, all the descendants of the
-    /// node are included in the subsection.
-    ///
-    /// # Arguments
-    ///
-    /// * `node_id` - The id of the node to get the subsection from.
-    /// * `generations` - The number of descendants to include in the subsection. If `None`, all the descendants of the node are included in the subsection.
-    ///
-    /// # Returns
-    ///
-    /// The subsection of the tree starting from the node with the given node id.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// # use tree_ds::prelude::{Node, Tree};
-    ///
-    /// # fn main() -> tree_ds::prelude::Result<()> {
-    /// # let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// Add a subtree to the tree.
     ///
-    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
-    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
-    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
-    ///
-    /// let subsection = tree.get_subtree(&node_2, None)?;
-    /// assert_eq!(subsection.get_nodes().len(), 2);
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub fn get_subtree(
+    /// This method waits for exclusive access and then behaves like `Tree::add_subtree`.
+    pub async fn add_subtree(
         &self,
         node_id: &Q,
-        generations: Option<i32>,
-    ) -> crate::prelude::Result<SubTree<Q, T>> {
-        let mut subsection = Nodes::default();
-        let node = self
-            .get_node_by_id(node_id)
-            .ok_or(NodeNotFound(node_id.to_string()))?;
-        subsection.push(node.clone());
-        // Get the subsequent children of the node recursively for the number of generations and add them to the subsection.
-        if let Some(generations) = generations {
-            let children = node.get_children_ids()?;
-            for current_generation in 0..generations {
-                for child in children.clone() {
-                    subsection.append(
-                        &mut self
-                            .get_subtree(&child, Some(current_generation))?
-                            .get_nodes()
-                            .clone(),
-                    );
-                }
-            }
-        } else {
-            let children = node.get_children_ids()?;
-            for child in children {
-                subsection.append(&mut self.get_subtree(&child, None)?.get_nodes().clone());
-            }
-        }
-
-        Ok(SubTree {
-            name: Some(node_id.to_string()),
-            nodes: subsection,
-        })
+        subtree: SubTree<Q, T, ArcStorage>,
+    ) -> crate::prelude::Result<()> {
+        self.write().await.add_subtree(node_id, subtree)
     }
 
-    /// Get the siblings of a node in the tree.
-    ///
-    /// This method gets the siblings of a node in the tree. The siblings of a node are the children
-    /// that share the same parent as the node.
-    ///
-    /// # Arguments
-    ///
-    /// * `node_id` - The id of the node to get the siblings of.
-    /// * `inclusive` - A flag that indicates whether to include the node in the siblings list.
-    ///
-    /// # Returns
-    ///
-    /// The siblings of the node in the tree.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// # use tree_ds::prelude::{Node, Tree};
-    ///
-    /// # fn main() -> tree_ds::prelude::Result<()> {
-    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
-    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
-    /// tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
-    /// tree.add_node(Node::new(4, Some(7)), Some(&node_1))?;
+    /// Get a node by its id.
     ///
-    /// let siblings = tree.get_sibling_ids(&node_2, false)?;
-    /// assert_eq!(siblings.len(), 2);
-    ///
-    /// let siblings = tree.get_sibling_ids(&node_2, true)?;
-    /// assert_eq!(siblings.len(), 3);
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub fn get_sibling_ids(&self, node_id: &Q, inclusive: bool) -> crate::prelude::Result<Vec<Q>> {
-        let node = self
-            .get_node_by_id(node_id)
-            .ok_or(NodeNotFound(node_id.to_string()))?;
-        if let Some(parent_id) = node.get_parent_id()? {
-            let parent = self
-                .get_node_by_id(&parent_id)
-                .ok_or(NodeNotFound(parent_id.to_string()))?;
-            if inclusive {
-                parent.get_children_ids()
-            } else {
-                Ok(parent
-                    .get_children_ids()?
-                    .iter()
-                    .filter(|x| *x != node_id)
-                    .cloned()
-                    .collect())
-            }
-        } else if inclusive {
-            // We need to clone this since Q does not implement Copy.
-            Ok(vec![node_id.clone()])
-        } else {
-            Ok(vec![])
-        }
+    /// This method waits for shared access and then behaves like `Tree::get_node_by_id`.
+    pub async fn get_node_by_id<K>(&self, node_id: &K) -> Option<Node<Q, T, ArcStorage>>
+    where
+        Q: Borrow<K>,
+        K: Eq + ?Sized,
+    {
+        self.read().await.get_node_by_id(node_id)
     }
 
-    /// Add a subsection to the tree.
-    ///
-    /// This method adds a subsection to the tree. The subsection is a list of nodes that are descendants
-    /// of the node with the given node id. The subsection is added as children of the node with the
-    /// given node id.
-    ///
-    /// # Arguments
-    ///
-    /// * `node_id` - The id of the node to add the subsection to.
-    /// * `subtree` - The subsection to add to the tree.
+    /// Get a subtree of the tree.
     ///
-    /// # Returns
-    /// This function return an error if:
-    /// - The node is not found in the tree.
-    /// - The subsection has no root node.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// # use tree_ds::prelude::{Node, Tree, SubTree};
-    ///
-    /// # fn main() -> tree_ds::prelude::Result<()> {
-    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    /// let node_id = tree.add_node(Node::new(1, Some(2)), None)?;
-    /// let mut subtree = SubTree::new(Some("Sample Tree"));
-    /// let node_2 = subtree.add_node(Node::new(2, Some(3)), None)?;
-    /// subtree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
-    /// tree.add_subtree(&node_id, subtree)?;
-    /// assert_eq!(tree.get_nodes().len(), 3);
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub fn add_subtree(
-        &mut self,
-        node_id: &Q,
-        subtree: SubTree<Q, T>,
-    ) -> crate::prelude::Result<()> {
-        let node = self
-            .get_node_by_id(node_id)
-            .ok_or(NodeNotFound(node_id.to_string()))?;
-        // Get the root node in the subsection and add it as a child of the node.
-        let subtree_nodes = subtree.get_nodes();
-        let root_node = subtree
-            .get_root_node()
-            .ok_or(InvalidOperation(String::from("Subtree has no root node.")))?;
-        node.add_child(root_node.clone())?;
-        self.nodes.append(&mut subtree_nodes.clone());
-        Ok(())
+    /// This method waits for shared access and then behaves like `Tree::get_subtree`.
+    pub async fn get_subtree<K>(
+        &self,
+        node_id: &K,
+        generations: Option<i32>,
+    ) -> crate::prelude::Result<SubTree<Q, T, ArcStorage>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+    {
+        self.read().await.get_subtree(node_id, generations)
     }
 
-    /// Traverse the subtree from the given node.
-    ///
-    /// This method traverses the subtree from the given node in the given order.
-    ///
-    /// # Arguments
-    ///
-    /// * `order` - The order to traverse the tree.
-    /// * `node_id` - The id of the node to start the traversal from.
-    ///
-    /// # Returns
-    ///
-    /// The nodes in the tree in the given order. This method returns an error if the node is not found
-    /// in the tree.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// # use tree_ds::prelude::{Node, Tree, TraversalStrategy};
+    /// Traverse the tree.
     ///
-    /// # fn main() -> tree_ds::prelude::Result<()> {
-    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
-    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
-    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
-    ///
-    /// let ordered_nodes = tree.traverse(&node_1, TraversalStrategy::PreOrder)?;
-    /// # let expected = vec![1, 2, 3];
-    /// # assert_eq!(ordered_nodes, expected);
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub fn traverse(
+    /// This method waits for shared access and then behaves like `Tree::traverse`.
+    pub async fn traverse<K>(
         &self,
-        node_id: &Q,
+        node_id: &K,
         order: TraversalStrategy,
-    ) -> crate::prelude::Result<Vec<Q>> {
-        let mut nodes = vec![];
-        let node = self
-            .get_node_by_id(node_id)
-            .ok_or(NodeNotFound(node_id.to_string()))?;
-        match &order {
-            TraversalStrategy::PreOrder => {
-                nodes.push(node_id.clone());
-                for child_id in node.get_children_ids()?.iter() {
-                    nodes.append(&mut self.traverse(child_id, order)?);
-                }
-            }
-            TraversalStrategy::PostOrder => {
-                for child_id in node.get_children_ids()?.iter() {
-                    nodes.append(&mut self.traverse(child_id, order)?);
-                }
-                nodes.push(node_id.clone());
-            }
-            TraversalStrategy::InOrder => {
-                for (index, child_id) in node.get_children_ids()?.iter().enumerate() {
-                    if index == 0 {
-                        nodes.append(&mut self.traverse(child_id, order)?);
-                        if !nodes.contains(child_id) {
-                            nodes.push(child_id.clone());
-                        }
-                        if !nodes.contains(node_id) {
-                            nodes.push(node_id.clone());
-                        }
-                    } else {
-                        nodes.push(child_id.clone());
-                        nodes.append(&mut self.traverse(child_id, order)?);
-                    }
-                }
-            }
-        }
-        #[cfg(not(feature = "no_std"))]
-        let mut seen = HashSet::new();
-        #[cfg(feature = "no_std")]
-        let mut seen = BTreeSet::new();
-        nodes.retain(|x| seen.insert(x.clone()));
-        Ok(nodes)
-    }
-
-    /// Print the tree.
-    ///
-    /// This method prints the tree to the standard output.
-    #[doc(hidden)]
-    fn print_tree(tree: &Tree<Q, T>, f: &mut Formatter<'_>) -> crate::prelude::Result<()>
+    ) -> crate::prelude::Result<Vec<Q>>
     where
-        Q: PartialEq + Eq + Clone + Display + Hash,
-        T: PartialEq + Eq + Clone + Display + Default,
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
     {
-        Tree::print_sub_tree(
-            tree,
-            f,
-            &tree.get_root_node().ok_or(FmtError)?,
-            String::new(),
-            true,
-        )?;
-        Ok(())
+        self.read().await.traverse(node_id, order)
     }
+}
 
-    fn print_sub_tree(
-        tree: &Tree<Q, T>,
-        f: &mut Formatter<'_>,
-        root_node: &Node<Q, T>,
-        mut parent_prefix: String,
-        is_last_child: bool,
-    ) -> crate::prelude::Result<()>
-    where
-        Q: PartialEq + Eq + Clone + Display + Hash,
-        T: PartialEq + Eq + Clone + Display + Default,
-    {
-        write!(f, "{parent_prefix}")?;
-        if is_last_child {
-            if tree
-                .get_root_node()
-                .is_some_and(|x| x.get_node_id() == root_node.get_node_id())
-            {
-                writeln!(f, "{root_node}")?;
-            } else {
-                writeln!(f, "└── {root_node}")?;
-                parent_prefix = format!("{parent_prefix}    ");
-            }
-        } else {
-            writeln!(f, "├── {root_node}")?;
-            parent_prefix = format!("{parent_prefix}│   ");
-        }
-        let children = root_node.get_children_ids()?;
-        for (index, node_id) in children.iter().enumerate() {
-            let node = tree
-                .get_node_by_id(node_id)
-                .ok_or(NodeNotFound(node_id.to_string()))?;
-
-            Tree::print_sub_tree(
-                tree,
-                f,
-                &node,
-                parent_prefix.clone(),
-                index == children.len() - 1,
-            )?;
-        }
-        Ok(())
+impl<Q, T> Debug for AsyncTree<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+{
+    /// Debug the async tree without waiting for the lock.
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("AsyncTree").finish_non_exhaustive()
     }
 }
 
-impl<Q, T> Default for Tree<Q, T>
+/// A writer of an `AsyncTree` that may be counted as waiting for the lock.
+struct WaitingWriter<'a, Q, T>
 where
-    Q: PartialEq + Eq + Clone + Send + Sync,
-    T: PartialEq + Eq + Clone + Send + Sync,
+    Q: PartialEq + Eq + Clone,
 {
-    /// Create a new tree with no nodes.
-    fn default() -> Self {
-        Tree {
-            name: None,
-            nodes: Nodes::default(),
+    tree: &'a AsyncTree<Q, T>,
+    waiting: bool,
+}
+
+impl<Q, T> Drop for WaitingWriter<'_, Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+{
+    /// Stop counting the writer as waiting and let the readers it was holding back continue.
+    fn drop(&mut self) {
+        if !self.waiting {
+            return;
         }
+        let wakers = {
+            let mut state = self.tree.state.lock();
+            state.waiting_writers -= 1;
+            mem::take(&mut state.wakers)
+        };
+        wakers.into_iter().for_each(Waker::wake);
     }
 }
 
-impl<Q, T> Display for Tree<Q, T>
+/// A guard that gives read access to the tree of an `AsyncTree`.
+///
+/// The lock is released when the guard is dropped.
+pub struct AsyncTreeReadGuard<'a, Q, T>
 where
-    Q: PartialEq + Eq + Clone + Display + Hash + Ord + Send + Sync,
-    T: PartialEq + Eq + Clone + Display + Default + Send + Sync,
+    Q: PartialEq + Eq + Clone,
 {
-    /// Print the tree.
-    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        if let Some(name) = &self.name {
-            writeln!(f, "{name}")?;
-            writeln!(
-                f,
-                "{}",
-                name.clone().chars().map(|_| "*").collect::<String>()
-            )?;
-        }
-        Tree::print_tree(self, f).map_err(|_| FmtError)?;
-        Ok(())
+    tree: &'a AsyncTree<Q, T>,
+    guard: Option<RwLockReadGuard<'a, ThreadSafeTree<Q, T>>>,
+}
+
+impl<Q, T> Deref for AsyncTreeReadGuard<'_, Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+{
+    type Target = ThreadSafeTree<Q, T>;
+
+    fn deref(&self) -> &Self::Target {
+        self.guard.as_ref().expect("Error: The guard was released.")
     }
 }
 
-impl<Q, T> Drop for Tree<Q, T>
+impl<Q, T> Drop for AsyncTreeReadGuard<'_, Q, T>
 where
-    Q: PartialEq + Eq + Clone + Send + Sync,
-    T: PartialEq + Eq + Clone + Send + Sync,
+    Q: PartialEq + Eq + Clone,
 {
-    /// Drop the tree.
-    #[doc(hidden)]
+    /// Release the lock.
     fn drop(&mut self) {
-        self.nodes.clear();
+        self.guard.take();
+        self.tree.release(false);
     }
 }
 
-#[cfg(feature = "serde")]
-impl<Q, T> Serialize for Tree<Q, T>
+/// A guard that gives write access to the tree of an `AsyncTree`.
+///
+/// The lock is released when the guard is dropped.
+pub struct AsyncTreeWriteGuard<'a, Q, T>
 where
-    Q: PartialEq + Eq + Clone + Serialize + Send + Sync,
-    T: PartialEq + Eq + Clone + Serialize + Send + Sync,
+    Q: PartialEq + Eq + Clone,
 {
-    /// Serialize the tree.
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        if self.name.is_none() {
-            let mut state = serializer.serialize_struct("Tree", 1)?;
-            state.serialize_field("nodes", &self.nodes)?;
-            return state.end();
-        }
-        let mut state = serializer.serialize_struct("Tree", 2)?;
-        state.serialize_field("name", &self.name)?;
-        state.serialize_field("nodes", &self.nodes)?;
-        state.end()
+    tree: &'a AsyncTree<Q, T>,
+    guard: Option<RwLockWriteGuard<'a, ThreadSafeTree<Q, T>>>,
+}
+
+impl<Q, T> Deref for AsyncTreeWriteGuard<'_, Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+{
+    type Target = ThreadSafeTree<Q, T>;
+
+    fn deref(&self) -> &Self::Target {
+        self.guard.as_ref().expect("Error: The guard was released.")
     }
 }
 
-#[cfg(feature = "serde")]
-impl<'de, Q, T> Deserialize<'de> for Tree<Q, T>
+impl<Q, T> DerefMut for AsyncTreeWriteGuard<'_, Q, T>
 where
-    Q: PartialEq + Eq + Clone + Send + Sync + Deserialize<'de>,
-    T: PartialEq + Eq + Clone + Send + Sync + Deserialize<'de>,
+    Q: PartialEq + Eq + Clone,
 {
-    /// Deserialize the tree.
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        #[derive(Deserialize)]
-        struct TreeVisitor<Q, T>
-        where
-            Q: PartialEq + Eq + Send + Sync + Clone,
-            T: PartialEq + Eq + Send + Sync + Clone,
-        {
-            name: Option<String>,
-            nodes: Nodes<Q, T>,
-        }
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.guard.as_mut().expect("Error: The guard was released.")
+    }
+}
 
-        let tree_visitor: TreeVisitor<Q, T> = Deserialize::deserialize(deserializer)?;
-        let tree = Tree {
-            name: tree_visitor.name,
-            nodes: tree_visitor.nodes,
-        };
-        Ok(tree)
+impl<Q, T> Drop for AsyncTreeWriteGuard<'_, Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+{
+    /// Release the lock.
+    fn drop(&mut self) {
+        self.guard.take();
+        self.tree.release(true);
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, not(feature = "no_std")))]
 mod tests {
+    use super::*;
+    use crate::prelude::Result;
+    use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+    use std::task::{Context, Wake};
+
+    /// A waker that unparks the thread that created it.
+    struct ThreadWaker(std::thread::Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    /// A waker that counts how many times it was woken up.
+    #[derive(Default)]
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, AtomicOrdering::SeqCst);
+        }
+    }
+
+    /// Run a future to completion on the current thread.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => std::thread::park(),
+            }
+        }
+    }
+
+    fn create_tree() -> AsyncTree<u32, u32> {
+        AsyncTree::new(ThreadSafeTree::new_in(None, ArcStorage))
+    }
+
+    #[test]
+    fn test_async_tree_operations() -> Result<()> {
+        let tree = create_tree();
+        block_on(async {
+            let root = tree
+                .add_node(Node::new_in(1, Some(2), ArcStorage), None)
+                .await?;
+            let node_2 = tree
+                .add_node(Node::new_in(2, Some(3), ArcStorage), Some(&root))
+                .await?;
+            tree.add_node(Node::new_in(3, Some(4), ArcStorage), Some(&node_2))
+                .await?;
+            assert_eq!(
+                tree.traverse(&root, TraversalStrategy::PreOrder).await?,
+                vec![1, 2, 3]
+            );
+            let subtree = tree.get_subtree(&node_2, None).await?;
+            assert_eq!(subtree.get_nodes().len(), 2);
+            tree.remove_node(&node_2, NodeRemovalStrategy::RetainChildren)
+                .await?;
+            assert_eq!(
+                tree.get_node_by_id(&3).await.unwrap().get_parent_id()?,
+                Some(1)
+            );
+            tree.write().await.rename(Some("Renamed"));
+            assert_eq!(tree.read().await.get_name(), Some("Renamed"));
+            Ok(())
+        })
+    }
 
-    #[cfg(not(feature = "no_std"))]
     #[test]
-    fn test_thread_safety() {
-        use crate::lib::*;
-        use crate::prelude::*;
-        use std::sync::Mutex;
-
-        let tree = Arc::new(Mutex::new(Tree::<String, i32>::new(Some("My Tree"))));
-        let handle = std::thread::spawn(move || {
-            let mut tree = tree.lock().unwrap();
-            let root_id = tree
-                .add_node(Node::new("root".to_string(), Some(2)), None)
-                .unwrap();
-            assert_eq!(root_id, "root".to_string());
-        });
-        handle.join().unwrap();
+    fn test_async_tree_writer_waits_for_readers() {
+        let tree = create_tree();
+        let counter = Arc::new(CountingWaker::default());
+        let waker = Waker::from(counter.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        let first_reader = block_on(tree.read());
+        let second_reader = block_on(tree.read());
+        let mut writer = pin!(tree.write());
+        assert!(writer.as_mut().poll(&mut cx).is_pending());
+        drop(first_reader);
+        assert_eq!(counter.0.load(AtomicOrdering::SeqCst), 0);
+        drop(second_reader);
+        assert_eq!(counter.0.load(AtomicOrdering::SeqCst), 1);
+        let Poll::Ready(mut guard) = writer.as_mut().poll(&mut cx) else {
+            panic!("The writer should hold the lock.");
+        };
+        guard.rename(Some("Written"));
+        drop(guard);
+        assert_eq!(block_on(tree.read()).get_name(), Some("Written"));
+    }
+
+    #[test]
+    fn test_async_tree_waiting_writer_blocks_new_readers() {
+        let tree = create_tree();
+        let counter = Arc::new(CountingWaker::default());
+        let waker = Waker::from(counter.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        let reader = block_on(tree.read());
+        let mut writer = Box::pin(tree.write());
+        assert!(writer.as_mut().poll(&mut cx).is_pending());
+        let mut second_reader = pin!(tree.read());
+        assert!(second_reader.as_mut().poll(&mut cx).is_pending());
+
+        // Dropping the waiting writer lets the second reader in.
+        drop(writer);
+        assert!(counter.0.load(AtomicOrdering::SeqCst) > 0);
+        assert!(second_reader.as_mut().poll(&mut cx).is_ready());
+        drop(reader);
+    }
+
+    #[test]
+    fn test_async_tree_across_threads() {
+        let tree = Arc::new(create_tree());
+        block_on(tree.add_node(Node::new_in(0, None, ArcStorage), None)).unwrap();
+        let handles = (1..=4)
+            .map(|thread| {
+                let tree = tree.clone();
+                std::thread::spawn(move || {
+                    for index in 0..25 {
+                        let node = Node::new_in(thread * 100 + index, Some(index), ArcStorage);
+                        block_on(tree.add_node(node, Some(&0))).unwrap();
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        handles.into_iter().for_each(|x| x.join().unwrap());
+        let tree = Arc::into_inner(tree).unwrap().into_inner();
+        assert_eq!(tree.get_nodes().len(), 101);
     }
 }
@@ -0,0 +1,376 @@
+use crate::lib::*;
+#[cfg(feature = "serde")]
+use ::serde::{Deserialize, Serialize};
+
+/// A table of attributes of type `A` attached to the nodes of a tree.
+///
+/// This struct maps node ids to attributes that are kept next to the nodes instead of inside their
+/// values, such as the collapsed state of a node, a selection or a cached aggregate. A tree holds
+/// at most one table per attribute type and keeps it in sync with its nodes. See
+/// `Tree::attributes`.
+///
+/// A table can also be created on its own, for instance to restore attributes saved with serde,
+/// and then be attached to a tree with `Tree::insert_attributes`.
+///
+/// # Type Parameters
+///
+/// * `Q` - The type of the node id.
+/// * `A` - The type of the attribute.
+///
+/// # Example
+///
+/// ```rust
+/// # use tree_ds::prelude::NodeAttributes;
+///
+/// let mut attributes: NodeAttributes<i32, bool> = NodeAttributes::new();
+/// attributes.insert(1, true);
+///
+/// assert_eq!(attributes.get(&1), Some(&true));
+/// assert_eq!(attributes.len(), 1);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NodeAttributes<Q, A> {
+    values: BTreeMap<Q, A>,
+}
+
+impl<Q, A> NodeAttributes<Q, A>
+where
+    Q: Ord,
+{
+    /// Create a new table with no attributes.
+    pub fn new() -> Self {
+        Self {
+            values: BTreeMap::new(),
+        }
+    }
+
+    /// Get the number of nodes that have an attribute.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Check if no node has an attribute.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Get the attribute of a node.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node. Any borrowed form of the id can be used.
+    ///
+    /// # Returns
+    ///
+    /// The attribute of the node, or `None` if the node has no attribute.
+    pub fn get<K>(&self, node_id: &K) -> Option<&A>
+    where
+        Q: Borrow<K>,
+        K: Ord + ?Sized,
+    {
+        self.values.get(node_id)
+    }
+
+    /// Check if a node has an attribute.
+    pub fn contains<K>(&self, node_id: &K) -> bool
+    where
+        Q: Borrow<K>,
+        K: Ord + ?Sized,
+    {
+        self.values.contains_key(node_id)
+    }
+
+    /// Set the attribute of a node.
+    ///
+    /// # Returns
+    ///
+    /// The previous attribute of the node, if any.
+    pub fn insert(&mut self, node_id: Q, attribute: A) -> Option<A> {
+        self.values.insert(node_id, attribute)
+    }
+
+    /// Remove the attribute of a node.
+    ///
+    /// # Returns
+    ///
+    /// The removed attribute, or `None` if the node had no attribute.
+    pub fn remove<K>(&mut self, node_id: &K) -> Option<A>
+    where
+        Q: Borrow<K>,
+        K: Ord + ?Sized,
+    {
+        self.values.remove(node_id)
+    }
+
+    /// Iterate over the node ids and their attributes in the order of the ids.
+    pub fn iter(&self) -> impl Iterator<Item = (&Q, &A)> {
+        self.values.iter()
+    }
+}
+
+impl<Q, A> Default for NodeAttributes<Q, A>
+where
+    Q: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Q, A> Serialize for NodeAttributes<Q, A>
+where
+    Q: Serialize,
+    A: Serialize,
+{
+    /// Serialize the table as a sequence of `(node id, attribute)` pairs.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_seq(self.values.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Q, A> Deserialize<'de> for NodeAttributes<Q, A>
+where
+    Q: Ord + Deserialize<'de>,
+    A: Deserialize<'de>,
+{
+    /// Deserialize the table from a sequence of `(node id, attribute)` pairs.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let values: Vec<(Q, A)> = Deserialize::deserialize(deserializer)?;
+        Ok(Self {
+            values: values.into_iter().collect(),
+        })
+    }
+}
+
+/// A table of attributes whose attribute type has been erased.
+trait AttributeTable<Q>: Send + Sync {
+    /// Get the table as `Any` to recover its attribute type.
+    fn as_any(&self) -> &dyn Any;
+
+    /// Get the table as a mutable `Any` to recover its attribute type.
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    /// Get the table as a boxed `Any` to recover its attribute type.
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+
+    /// Get the name of the attribute type.
+    fn type_name(&self) -> &'static str;
+
+    /// Copy the table.
+    fn clone_table(&self) -> Box<dyn AttributeTable<Q>>;
+
+    /// Remove the attribute of a node.
+    fn remove_node(&mut self, node_id: &Q);
+
+    /// Keep only the attributes of the nodes for which `keep` returns `true`.
+    fn retain_nodes(&mut self, keep: &dyn Fn(&Q) -> bool);
+
+    /// Move the attributes of the nodes that are keys of `ids` to the matching new ids.
+    fn rename_nodes(&mut self, ids: &BTreeMap<Q, Q>);
+
+    /// Add the attributes of another table of the same type, replacing those of the same nodes.
+    fn merge(&mut self, other: Box<dyn AttributeTable<Q>>);
+}
+
+impl<Q, A> AttributeTable<Q> for NodeAttributes<Q, A>
+where
+    Q: Ord + Clone + Send + Sync + 'static,
+    A: Clone + Send + Sync + 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    fn type_name(&self) -> &'static str {
+        type_name::<A>()
+    }
+
+    fn clone_table(&self) -> Box<dyn AttributeTable<Q>> {
+        Box::new(self.clone())
+    }
+
+    fn remove_node(&mut self, node_id: &Q) {
+        self.values.remove(node_id);
+    }
+
+    fn retain_nodes(&mut self, keep: &dyn Fn(&Q) -> bool) {
+        self.values.retain(|node_id, _| keep(node_id));
+    }
+
+    fn rename_nodes(&mut self, ids: &BTreeMap<Q, Q>) {
+        let renamed = ids
+            .iter()
+            .filter_map(|(old_id, new_id)| Some((new_id.clone(), self.values.remove(old_id)?)))
+            .collect::<Vec<_>>();
+        self.values.extend(renamed);
+    }
+
+    fn merge(&mut self, other: Box<dyn AttributeTable<Q>>) {
+        if let Ok(other) = other.into_any().downcast::<Self>() {
+            self.values.extend(other.values);
+        }
+    }
+}
+
+/// The attribute tables of a tree, one per attribute type.
+///
+/// Attributes are annotations kept next to the tree, so they are not part of its identity: two
+/// trees with the same nodes are equal and hash the same whatever their attributes.
+pub(crate) struct AttributeTables<Q> {
+    tables: BTreeMap<TypeId, Box<dyn AttributeTable<Q>>>,
+}
+
+impl<Q> AttributeTables<Q> {
+    /// Remove the attributes of a node from every table.
+    pub(crate) fn remove_node(&mut self, node_id: &Q) {
+        self.tables
+            .values_mut()
+            .for_each(|x| x.remove_node(node_id));
+    }
+
+    /// Copy the attributes of the nodes for which `keep` returns `true`.
+    pub(crate) fn subset(&self, keep: &dyn Fn(&Q) -> bool) -> Self {
+        let mut subset = self.clone();
+        subset
+            .tables
+            .values_mut()
+            .for_each(|x| x.retain_nodes(keep));
+        subset
+    }
+
+    /// Move the attributes of the nodes that are keys of `ids` to the matching new ids.
+    pub(crate) fn rename_nodes(&mut self, ids: &BTreeMap<Q, Q>) {
+        self.tables.values_mut().for_each(|x| x.rename_nodes(ids));
+    }
+
+    /// Add the attributes of other tables, replacing those of the same nodes.
+    pub(crate) fn merge(&mut self, other: Self) {
+        for (type_id, table) in other.tables {
+            match self.tables.get_mut(&type_id) {
+                Some(existing) => existing.merge(table),
+                None => {
+                    self.tables.insert(type_id, table);
+                }
+            }
+        }
+    }
+}
+
+impl<Q> AttributeTables<Q>
+where
+    Q: 'static,
+{
+    /// Get the table of an attribute type.
+    pub(crate) fn get<A>(&self) -> Option<&NodeAttributes<Q, A>>
+    where
+        A: 'static,
+    {
+        self.tables
+            .get(&TypeId::of::<A>())
+            .and_then(|x| x.as_any().downcast_ref())
+    }
+
+    /// Get the table of an attribute type to change it.
+    pub(crate) fn get_mut<A>(&mut self) -> Option<&mut NodeAttributes<Q, A>>
+    where
+        A: 'static,
+    {
+        self.tables
+            .get_mut(&TypeId::of::<A>())
+            .and_then(|x| x.as_any_mut().downcast_mut())
+    }
+
+    /// Remove the table of an attribute type.
+    pub(crate) fn take<A>(&mut self) -> Option<NodeAttributes<Q, A>>
+    where
+        A: 'static,
+    {
+        let table = self.tables.remove(&TypeId::of::<A>())?;
+        table.into_any().downcast().ok().map(|x| *x)
+    }
+}
+
+impl<Q> AttributeTables<Q>
+where
+    Q: Ord + Clone + Send + Sync + 'static,
+{
+    /// Replace the table of an attribute type.
+    pub(crate) fn insert<A>(&mut self, table: NodeAttributes<Q, A>) -> Option<NodeAttributes<Q, A>>
+    where
+        A: Clone + Send + Sync + 'static,
+    {
+        let previous = self.take();
+        self.tables.insert(TypeId::of::<A>(), Box::new(table));
+        previous
+    }
+
+    /// Get the table of an attribute type, creating it if it does not exist.
+    pub(crate) fn get_or_insert<A>(&mut self) -> &mut NodeAttributes<Q, A>
+    where
+        A: Clone + Send + Sync + 'static,
+    {
+        self.tables
+            .entry(TypeId::of::<A>())
+            .or_insert_with(|| Box::new(NodeAttributes::<Q, A>::new()))
+            .as_any_mut()
+            .downcast_mut()
+            .expect("Error: The attribute table does not match its type.")
+    }
+}
+
+impl<Q> Clone for AttributeTables<Q> {
+    fn clone(&self) -> Self {
+        Self {
+            tables: self
+                .tables
+                .iter()
+                .map(|(type_id, table)| (*type_id, table.clone_table()))
+                .collect(),
+        }
+    }
+}
+
+impl<Q> Default for AttributeTables<Q> {
+    fn default() -> Self {
+        Self {
+            tables: BTreeMap::new(),
+        }
+    }
+}
+
+impl<Q> Debug for AttributeTables<Q> {
+    /// List the attribute types of the tables.
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_list()
+            .entries(self.tables.values().map(|x| x.type_name()))
+            .finish()
+    }
+}
+
+impl<Q> PartialEq for AttributeTables<Q> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<Q> Eq for AttributeTables<Q> {}
+
+impl<Q> Hash for AttributeTables<Q> {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
+}
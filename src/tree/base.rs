@@ -0,0 +1,3778 @@
+use crate::error::Error;
+use crate::error::Error::{InvalidOperation, ParseError, RootNodeAlreadyPresent};
+use crate::lib::*;
+use crate::node::{DefaultStorage, Node, NodeStorage, Nodes};
+use crate::prelude::{
+    CollisionPolicy, IndentStyle, MermaidStyle, NodeAttributes, NodeRemovalStrategy, PlantUmlStyle,
+    SubTree, TraversalStrategy, TreeRenderer,
+};
+use crate::tree::{
+    escape_mermaid_label, escape_plantuml_label, parse_newick, parse_outline, traverse_subtree,
+    AttributeTables, IdGenerator, NewickValue, OutlineEntry, SharedIdGenerator, TreeCursor,
+    TreeCursorMut, TreeLine, TreeVisitor, WalkControl, WalkEvent, WalkEvents,
+};
+#[cfg(feature = "serde")]
+use ::serde::{de::IgnoredAny, ser::SerializeStruct, Deserialize, Serialize};
+
+/// A tree data structure.
+///
+/// This struct represents a tree data structure. A tree is a data structure that consists of nodes
+/// connected by edges. Each node has a parent node and zero or more child nodes. The tree has a root
+/// node that is the topmost node in the tree. The tree can be used to represent hierarchical data
+/// structures such as file systems, organization charts, and family trees. A tree can have any number
+/// of nodes and each node can have any number of children. The tree can be traversed in different
+/// orders such as pre-order, post-order, and in-order. The tree can be named for easy identification
+/// when working with multiple trees or subtrees.
+///
+/// # Type Parameters
+///
+/// * `Q` - The type of the node id. Most operations only require the id to implement `Debug`, `Hash`
+///   and `Ord`, and errors show the id with its `Debug` implementation. Methods that write ids as
+///   text, such as the diagram and table conversions, also require `Display`. Methods that look nodes up accept any borrowed form of the id, so a
+///   `Tree<String, _>` can be queried with a `&str`.
+/// * `T` - The type of the node value. The value type has no required traits, so values such as
+///   floats, boxed trait objects or file handles can be stored. Methods that copy values, such as
+///   `deep_clone`, require `T: Clone`, and comparing or hashing trees requires the matching trait.
+/// * `P` - The storage used to share the data of the nodes. See `NodeStorage`.
+/// * `E` - The type of the data attached to the edge between a node and its parent, such as a
+///   branch length, a cost or a relationship label. Trees without edge data use `()`. See
+///   `get_edge`.
+///
+/// # Cloning
+///
+/// A `Node` is a handle to data shared with the tree that holds it, and cloning a tree clones these
+/// handles. A clone therefore has its own list of nodes, so adding or removing nodes in one tree does
+/// not affect the other, but both trees share the same nodes: changing the value, the children or
+/// the parent of a node through one tree is visible through the other. The same applies to the
+/// nodes of a subtree returned by `get_subtree`. Use `deep_clone` or `get_subtree_owned` to get a
+/// copy that shares no data with the original tree. The attributes of the nodes, see `attributes`,
+/// are not shared: every clone gets its own copy. The id generator of the tree, see `set_id_generator`, is
+/// shared by all clones and subtrees, including deep ones, so that the ids they generate do not
+/// collide.
+///
+/// # Example
+///
+/// ```rust
+/// # use tree_ds::prelude::Tree;
+///
+/// let tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct Tree<Q, T, P = DefaultStorage, E = ()>
+where
+    Q: PartialEq + Eq + Clone,
+    P: NodeStorage,
+{
+    name: Option<String>,
+    nodes: Nodes<Q, T, P>,
+    edges: BTreeMap<Q, E>,
+    attributes: AttributeTables<Q>,
+    id_generator: SharedIdGenerator<Q>,
+}
+
+impl<Q, T> Tree<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Debug + Hash + Ord,
+{
+    /// Create a new tree.
+    ///
+    /// This method creates a new tree with no nodes.
+    ///
+    /// # Returns
+    ///
+    /// A new tree with no nodes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::Tree;
+    ///
+    /// let tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// ```
+    pub fn new(tree_name: Option<&str>) -> Self {
+        Tree::new_in(tree_name, DefaultStorage::default())
+    }
+}
+
+impl<Q, T, P, E> Tree<Q, T, P, E>
+where
+    Q: PartialEq + Eq + Clone + Debug + Hash + Ord,
+    P: NodeStorage,
+{
+    /// Create a new tree whose edges hold data of type `E`.
+    ///
+    /// This method creates a new tree with no nodes in the same way as `Tree::new_in`, for trees
+    /// whose edges carry data such as weights or labels. The type of the edge data is usually given
+    /// by annotating the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree_name` - The name of the tree.
+    /// * `storage` - The storage used to share the data of the nodes.
+    ///
+    /// # Returns
+    ///
+    /// A new tree with no nodes and no edge data.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{DefaultStorage, Tree};
+    ///
+    /// let tree: Tree<i32, i32, DefaultStorage, f64> =
+    ///     Tree::new_with_edges(Some("Sample Tree"), DefaultStorage::default());
+    /// assert!(tree.get_nodes().is_empty());
+    /// ```
+    pub fn new_with_edges(tree_name: Option<&str>, _storage: P) -> Self {
+        Self {
+            name: tree_name.map(|x| x.to_string()),
+            nodes: Nodes::default(),
+            edges: BTreeMap::new(),
+            attributes: AttributeTables::default(),
+            id_generator: SharedIdGenerator::default(),
+        }
+    }
+
+    /// Add a node to the tree.
+    ///
+    /// This method adds a node to the tree. The node is added as a child of the parent node with the
+    /// given parent id. If the parent id is `None`, the node is added as a root node. The node id is
+    /// used to identify the node and the value is the value of the node. The value can be used to store
+    /// any data that you want to associate with the node.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node.
+    /// * `value` - The value of the node.
+    /// * `parent_id` - The id of the parent node. If `None`, the node is added as a root node.
+    ///
+    /// # Returns
+    ///
+    /// The id of the node that was added to the tree. However, if no parent id is provided and the tree already
+    /// has a root node, an error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Tree, Node};
+    ///
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_id = tree.add_node(Node::new(1, Some(2)), None);
+    ///
+    /// assert!(node_id.is_ok());
+    /// // This should return an error because the tree already has a root node.
+    /// let another_node_id = tree.add_node(Node::new(2, Some(3)), None);
+    /// assert!(another_node_id.is_err());
+    /// ```
+    pub fn add_node(
+        &mut self,
+        node: Node<Q, T, P>,
+        parent_id: Option<&Q>,
+    ) -> crate::prelude::Result<Q> {
+        if let Some(parent_id) = parent_id {
+            let parent = self
+                .nodes
+                .iter()
+                .find(|n| &n.get_node_id().expect("Error: Failed to get the node Id.") == parent_id)
+                .ok_or_else(|| Error::node_not_found(parent_id))?;
+            parent.add_child(node.clone())?;
+        } else if self.get_root_node().is_some() {
+            return Err(RootNodeAlreadyPresent);
+        }
+        self.nodes.push(node.clone());
+        node.get_node_id()
+    }
+
+    /// Add a node to the tree at the given position among the children of its parent.
+    pub(crate) fn insert_node(
+        &mut self,
+        node: Node<Q, T, P>,
+        parent_id: &Q,
+        index: usize,
+    ) -> crate::prelude::Result<Q> {
+        self.find_node(parent_id)?
+            .insert_child(index, node.clone())?;
+        self.nodes.push(node.clone());
+        node.get_node_id()
+    }
+
+    /// Set the id generator of the tree.
+    ///
+    /// The generator gives the ids of the nodes added with `add_value`. It replaces any previous
+    /// generator of the tree, and is shared with the clones and subtrees of the tree made after
+    /// this call.
+    ///
+    /// # Arguments
+    ///
+    /// * `generator` - The id generator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{SequentialIdGenerator, Tree};
+    ///
+    /// let mut tree: Tree<u32, i32> = Tree::new(Some("Sample Tree"));
+    /// tree.set_id_generator(SequentialIdGenerator::new(100, 10));
+    /// ```
+    pub fn set_id_generator<G>(&mut self, generator: G)
+    where
+        G: IdGenerator<Q> + 'static,
+    {
+        self.id_generator.set(generator);
+    }
+
+    /// Add a value to the tree in a new node whose id is given by the id generator of the tree.
+    ///
+    /// This method adds a node in the same way as `add_node`. Ids that are already used by a node
+    /// of the tree are skipped. A generator that does not repeat its ids produces an unused id
+    /// after skipping at most one id per node of the tree, so the method gives up after that many
+    /// ids.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value of the new node.
+    /// * `parent_id` - The id of the parent node. If `None`, the node is added as the root node.
+    ///
+    /// # Returns
+    ///
+    /// The id of the new node. This method returns an error if the tree has no id generator, if the
+    /// generator does not produce an unused id, if the parent node is not found or if the node is
+    /// added as a root node to a tree that already has one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{SequentialIdGenerator, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<u32, &str> = Tree::new(Some("Sample Tree"));
+    /// tree.set_id_generator(SequentialIdGenerator::default());
+    /// let root = tree.add_value(Some("root"), None)?;
+    /// let child = tree.add_value(Some("child"), Some(&root))?;
+    ///
+    /// assert_eq!((root, child), (1, 2));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_value(
+        &mut self,
+        value: Option<T>,
+        parent_id: Option<&Q>,
+    ) -> crate::prelude::Result<Q> {
+        if let Some(parent_id) = parent_id {
+            self.find_node(parent_id)?;
+        } else if self.get_root_node().is_some() {
+            return Err(RootNodeAlreadyPresent);
+        }
+        let node_id = self.generate_id(self.nodes.len(), |x| self.get_node_ref(x).is_some())?;
+        self.add_node(Node::new_in(node_id, value, P::default()), parent_id)
+    }
+
+    /// Generate an id that is not used, with the id generator of the tree.
+    ///
+    /// `is_used` tells whether an id is used and accepts at most `used` different ids, so a
+    /// generator that does not repeat its ids produces an unused one within `used + 1` ids. A
+    /// generator that has not produced one by then is given up on instead of being asked forever.
+    fn generate_id(&self, used: usize, is_used: impl Fn(&Q) -> bool) -> crate::prelude::Result<Q> {
+        for _ in 0..=used {
+            let node_id = self
+                .id_generator
+                .next_id()
+                .ok_or(InvalidOperation("The tree has no id generator".to_string()))?;
+            if !is_used(&node_id) {
+                return Ok(node_id);
+            }
+        }
+        Err(InvalidOperation(
+            "The id generator of the tree did not produce an unused id".to_string(),
+        ))
+    }
+
+    /// Get the name of the tree.
+    ///
+    /// This method gets the name of the tree.
+    ///
+    /// # Returns
+    ///
+    /// The name of the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::Tree;
+    ///
+    /// let tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    ///
+    /// assert_eq!(tree.get_name(), Some("Sample Tree"));
+    /// ```
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Set the name of the tree.
+    ///
+    /// This method sets the name of the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::Tree;
+    ///
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// tree.rename(Some("New Name"));
+    /// assert_eq!(tree.get_name(), Some("New Name"));
+    /// ```
+    pub fn rename(&mut self, name: Option<&str>) {
+        self.name = name.map(|x| x.to_string());
+    }
+
+    /// Get a node in the tree.
+    ///
+    /// This method gets the node with the given node id in the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node. Any borrowed form of the id can be used, for instance a
+    ///   `&str` for `String` ids.
+    ///
+    /// # Returns
+    ///
+    /// The node with the given node id in the tree or `None` if the node is not found.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    ///
+    /// let node = Node::new(1, Some(2));
+    /// let node_id = tree.add_node(node.clone(), None).unwrap();
+    ///
+    /// assert_eq!(tree.get_node_by_id(&node_id), Some(node));
+    ///
+    /// let mut tree: Tree<String, i32> = Tree::new(None);
+    /// tree.add_node(Node::new(String::from("root"), Some(1)), None).unwrap();
+    /// assert!(tree.get_node_by_id("root").is_some());
+    /// ```
+    pub fn get_node_by_id<K>(&self, node_id: &K) -> Option<Node<Q, T, P>>
+    where
+        Q: Borrow<K>,
+        K: Eq + ?Sized,
+    {
+        self.get_node_ref(node_id).cloned()
+    }
+
+    /// Borrow a node by its id.
+    ///
+    /// This method gets a reference to the node held by the tree instead of a new handle to it, and
+    /// compares the ids without cloning them.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node.
+    ///
+    /// # Returns
+    ///
+    /// A reference to the node with the given id, or `None` if the node is not in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, String> = Tree::new(Some("Sample Tree"));
+    /// let node_id = tree.add_node(Node::new(1, Some(String::from("value"))), None)?;
+    ///
+    /// let node = tree.get_node_ref(&node_id).unwrap();
+    /// assert_eq!(node.value_ref().as_deref(), Some("value"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_node_ref<K>(&self, node_id: &K) -> Option<&Node<Q, T, P>>
+    where
+        Q: Borrow<K>,
+        K: Eq + ?Sized,
+    {
+        self.nodes.iter().find(|n| n.has_id(node_id))
+    }
+
+    /// Borrow a node by its id, or fail with a `NodeNotFound` error.
+    fn find_node(&self, node_id: &Q) -> crate::prelude::Result<&Node<Q, T, P>> {
+        self.get_node_ref(node_id)
+            .ok_or_else(|| Error::node_not_found(node_id))
+    }
+
+    /// Get the root node of the tree.
+    ///
+    /// This method gets the root node of the tree. The root node is the topmost node in the tree. The
+    /// root node has no parent node.
+    ///
+    /// # Returns
+    ///
+    /// The root node of the tree or `None` if the tree has no root node.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    ///
+    /// let node = Node::new(1, Some(2));
+    /// tree.add_node(node.clone(), None).unwrap();
+    ///
+    /// assert_eq!(tree.get_root_node(), Some(node));
+    /// ```
+    pub fn get_root_node(&self) -> Option<Node<Q, T, P>> {
+        self.nodes
+            .iter()
+            .find(|n| {
+                n.get_parent_id()
+                    .expect("Error: Failed to get the node Id of the parent.")
+                    .is_none()
+            })
+            .cloned()
+    }
+
+    /// Get the height of the node.
+    ///
+    /// This method gets the height of the node. The height of the node is the number of edges present
+    /// in the longest path connecting the node to a leaf node.
+    ///
+    /// # Returns
+    ///
+    /// The height of the node. If the node is a leaf node, the height is 0.  This method returns an
+    /// error if the node is not found in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    ///
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None).unwrap();
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1)).unwrap();
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2)).unwrap();
+    ///
+    /// assert!(tree.get_node_height(&node_2).is_ok());
+    /// assert_eq!(tree.get_node_height(&node_2).unwrap(), 1);
+    /// ```
+    pub fn get_node_height<K>(&self, node_id: &K) -> crate::prelude::Result<i32>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+    {
+        let node = self
+            .get_node_by_id(node_id)
+            .ok_or_else(|| Error::node_not_found(node_id))?;
+        let children = node.get_children_ids()?;
+        if children.is_empty() {
+            return Ok(0);
+        }
+        let mut height = 0;
+        for child in children {
+            let child_height = self.get_node_height::<Q>(&child)?;
+            if child_height > height {
+                height = child_height;
+            }
+        }
+        Ok(height + 1)
+    }
+
+    /// Get the depth of a node in the tree.
+    ///
+    /// This method gets the depth of a node in the tree. The depth of a node is the length of the path
+    /// from the root node to the node. The depth of the node is the number of edges on the path from the
+    /// root node to the node.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node.
+    ///
+    /// # Returns
+    ///
+    /// The depth of the node in the tree.  This method returns an error if the node is not found in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    ///
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None).unwrap();
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1)).unwrap();
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2)).unwrap();
+    /// let depth_result = tree.get_node_depth(&node_3);
+    /// assert!(depth_result.is_ok());
+    /// assert_eq!(depth_result.unwrap(), 2);
+    /// ```
+    pub fn get_node_depth<K>(&self, node_id: &K) -> crate::prelude::Result<i32>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+    {
+        let node = self
+            .get_node_by_id(node_id)
+            .ok_or_else(|| Error::node_not_found(node_id))?;
+        let mut depth = 0;
+        let mut parent = node.get_parent_id()?;
+        while let Some(parent_id) = parent {
+            depth += 1;
+            parent = self.find_node(&parent_id)?.get_parent_id()?;
+        }
+        Ok(depth)
+    }
+
+    /// Get the ancestors of a node in the tree.
+    ///
+    /// This method gets the ancestors of a node in the tree. The ancestors of a node are all the nodes
+    /// that are on the path from the root node to the node, not including the node itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node.
+    ///
+    /// # Returns
+    ///
+    /// The ancestors of the node from closest to furthest.  This method returns an error if the node is not found in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    ///
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None).unwrap();
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1)).unwrap();
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2)).unwrap();
+    /// let depth_result = tree.get_ancestor_ids(&node_3);
+    /// assert!(depth_result.is_ok());
+    /// assert_eq!(depth_result.unwrap(), vec![2, 1]);
+    /// ```
+    pub fn get_ancestor_ids<K>(&self, node_id: &K) -> crate::prelude::Result<Vec<Q>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+    {
+        let node = self
+            .get_node_by_id(node_id)
+            .ok_or_else(|| Error::node_not_found(node_id))?;
+        let mut ancestors = vec![];
+        let mut parent = node.get_parent_id()?;
+        while let Some(parent_id) = parent {
+            ancestors.push(parent_id.clone());
+            parent = self.find_node(&parent_id)?.get_parent_id()?;
+        }
+        Ok(ancestors)
+    }
+
+    /// Get the data of the edge between a node and its parent.
+    ///
+    /// Every node except the root node is linked to its parent by an edge that can carry data of
+    /// type `E`, such as a branch length or a relationship label. Edges without data are ignored
+    /// by the weighted methods, which count them as `E::default()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the child node of the edge.
+    ///
+    /// # Returns
+    ///
+    /// The data of the edge, or `None` if the edge has no data. This method returns an error if the
+    /// node is not found in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{DefaultStorage, Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32, DefaultStorage, f64> =
+    ///     Tree::new_with_edges(Some("Sample Tree"), DefaultStorage::default());
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    ///
+    /// assert_eq!(tree.get_edge(&node_2)?, None);
+    /// tree.set_edge(&node_2, 1.5)?;
+    /// assert_eq!(tree.get_edge(&node_2)?, Some(&1.5));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_edge<K>(&self, node_id: &K) -> crate::prelude::Result<Option<&E>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Ord + ?Sized,
+    {
+        self.get_node_ref(node_id)
+            .ok_or_else(|| Error::node_not_found(node_id))?;
+        Ok(self.edges.get(node_id))
+    }
+
+    /// Set the data of the edge between a node and its parent.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the child node of the edge.
+    /// * `edge` - The data of the edge.
+    ///
+    /// # Returns
+    ///
+    /// The previous data of the edge, if any. This method returns an error if the node is not found
+    /// in the tree or if the node has no parent.
+    pub fn set_edge<K>(&mut self, node_id: &K, edge: E) -> crate::prelude::Result<Option<E>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+    {
+        let node = self
+            .get_node_ref(node_id)
+            .ok_or_else(|| Error::node_not_found(node_id))?;
+        if node.get_parent_id()?.is_none() {
+            return Err(InvalidOperation(format!(
+                "Node {node_id:?} has no parent, so it has no edge"
+            )));
+        }
+        let node_id = node.get_node_id()?;
+        Ok(self.edges.insert(node_id, edge))
+    }
+
+    /// Remove the data of the edge between a node and its parent.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the child node of the edge.
+    ///
+    /// # Returns
+    ///
+    /// The removed data of the edge, if any. This method returns an error if the node is not found
+    /// in the tree.
+    pub fn remove_edge<K>(&mut self, node_id: &K) -> crate::prelude::Result<Option<E>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Ord + ?Sized,
+    {
+        self.get_node_ref(node_id)
+            .ok_or_else(|| Error::node_not_found(node_id))?;
+        Ok(self.edges.remove(node_id))
+    }
+
+    /// Get the weighted depth of a node in the tree.
+    ///
+    /// The weighted depth of a node is the sum of the data of the edges on the path from the root
+    /// node to the node. Edges without data count as `E::default()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node.
+    ///
+    /// # Returns
+    ///
+    /// The weighted depth of the node. This method returns an error if the node is not found in the
+    /// tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{DefaultStorage, Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32, DefaultStorage, u32> =
+    ///     Tree::new_with_edges(Some("Sample Tree"), DefaultStorage::default());
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+    /// tree.set_edge(&node_2, 4)?;
+    /// tree.set_edge(&node_3, 5)?;
+    ///
+    /// assert_eq!(tree.get_weighted_depth(&node_3)?, 9);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_weighted_depth<K>(&self, node_id: &K) -> crate::prelude::Result<E>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+        E: Clone + Default + Add<Output = E>,
+    {
+        let node = self
+            .get_node_ref(node_id)
+            .ok_or_else(|| Error::node_not_found(node_id))?;
+        Ok(self.get_weight(&self.get_path_to_root(node)?))
+    }
+
+    /// Get the weighted length of the path between two nodes in the tree.
+    ///
+    /// The path goes up from the first node to the closest common ancestor of both nodes and then
+    /// down to the second node. Its weighted length is the sum of the data of its edges. Edges
+    /// without data count as `E::default()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The id of the node the path starts from.
+    /// * `to` - The id of the node the path ends at.
+    ///
+    /// # Returns
+    ///
+    /// The weighted length of the path. This method returns an error if either node is not found in
+    /// the tree or if the nodes have no common ancestor.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{DefaultStorage, Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32, DefaultStorage, f64> =
+    ///     Tree::new_with_edges(Some("Sample Tree"), DefaultStorage::default());
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+    /// tree.set_edge(&node_2, 0.5)?;
+    /// tree.set_edge(&node_3, 1.25)?;
+    ///
+    /// assert_eq!(tree.get_weighted_path_length(&node_2, &node_3)?, 1.75);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_weighted_path_length<K>(&self, from: &K, to: &K) -> crate::prelude::Result<E>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+        E: Clone + Default + Add<Output = E>,
+    {
+        let from_node = self
+            .get_node_ref(from)
+            .ok_or_else(|| Error::node_not_found(from))?;
+        let to_node = self
+            .get_node_ref(to)
+            .ok_or_else(|| Error::node_not_found(to))?;
+        let from_path = self.get_path_to_root(from_node)?;
+        let to_path = self.get_path_to_root(to_node)?;
+        let common = from_path
+            .iter()
+            .position(|x| to_path.contains(x))
+            .ok_or(InvalidOperation(format!(
+                "Nodes {from:?} and {to:?} have no common ancestor"
+            )))?;
+        let to_common = to_path
+            .iter()
+            .position(|x| x == &from_path[common])
+            .unwrap_or(to_path.len());
+        Ok(self.get_weight(&from_path[..common]) + self.get_weight(&to_path[..to_common]))
+    }
+
+    /// Get the ids of a node and of all its ancestors, from the node up to the root node.
+    fn get_path_to_root(&self, node: &Node<Q, T, P>) -> crate::prelude::Result<Vec<Q>> {
+        let node_id = node.get_node_id()?;
+        let mut path = self.get_ancestor_ids(&node_id)?;
+        path.insert(0, node_id);
+        Ok(path)
+    }
+
+    /// Get the sum of the data of the edges between the given nodes and their parents.
+    fn get_weight(&self, node_ids: &[Q]) -> E
+    where
+        E: Clone + Default + Add<Output = E>,
+    {
+        node_ids.iter().fold(E::default(), |weight, node_id| {
+            weight + self.edges.get(node_id).cloned().unwrap_or_default()
+        })
+    }
+
+    /// Get the height of the tree.
+    ///
+    /// This method gets the height of the tree. The height of the tree is the length of the longest path
+    /// from the root node to a leaf node. The height of the tree is the number of edges on the longest
+    /// path from the root node to a leaf node.
+    ///
+    /// # Returns
+    ///
+    /// The height of the tree. This method returns an error if the tree has no root node.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree, Result};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    ///
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+    /// let tree_height = tree.get_height();
+    /// assert!(tree_height.is_ok());
+    /// assert_eq!(tree_height?, 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_height(&self) -> crate::prelude::Result<i32> {
+        let root = self
+            .get_root_node()
+            .ok_or(InvalidOperation(String::from("Tree has no root node")))?;
+        self.get_node_height(&root.get_node_id()?)
+    }
+
+    /// Get the degree of a node in the tree.
+    ///
+    /// This method gets the degree of a node in the tree. The degree of a node is the number of children
+    /// that the node has.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node.
+    ///
+    /// # Returns
+    ///
+    /// The degree of the node in the tree. This method returns an error if the node is not found in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Result, Node, Tree};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    ///
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+    ///
+    /// assert_eq!(tree.get_node_degree(&node_1)?, 2);
+    /// assert_eq!(tree.get_node_degree(&node_2)?, 0);
+    /// assert_eq!(tree.get_node_degree(&node_3)?, 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_node_degree<K>(&self, node_id: &K) -> crate::prelude::Result<i32>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+    {
+        let node = self
+            .get_node_by_id(node_id)
+            .ok_or_else(|| Error::node_not_found(node_id))?;
+        Ok(node.get_children_ids()?.len() as i32)
+    }
+
+    /// Get the nodes in the tree.
+    ///
+    /// This method gets the nodes in the tree.
+    ///
+    /// # Returns
+    ///
+    /// The nodes in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    ///
+    /// let node = Node::new(1, Some(2));
+    /// tree.add_node(node.clone(), None).unwrap();
+    ///
+    /// assert_eq!(tree.get_nodes().len(), 1);
+    /// ```
+    pub fn get_nodes(&self) -> &Nodes<Q, T, P> {
+        self.nodes.as_ref()
+    }
+
+    /// Get the number of nodes in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// assert!(tree.is_empty());
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// assert_eq!(tree.len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Check if the tree has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Check if the tree has a node with the given id.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node. Any borrowed form of the id can be used.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<String, i32> = Tree::new(Some("Sample Tree"));
+    /// tree.add_node(Node::new("root".to_string(), Some(2)), None)?;
+    /// assert!(tree.contains("root"));
+    /// assert!(!tree.contains("leaf"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn contains<K>(&self, node_id: &K) -> bool
+    where
+        Q: Borrow<K>,
+        K: Eq + ?Sized,
+    {
+        self.get_node_ref(node_id).is_some()
+    }
+
+    /// Iterate over the nodes of the tree in pre-order.
+    ///
+    /// The iterator starts at the root node and visits each node before its children. Iterating
+    /// over a reference to the tree does the same.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+    /// tree.add_node(Node::new(4, Some(5)), Some(&node_2))?;
+    ///
+    /// let ids = tree.iter().map(|x| x.get_node_id()).collect::<Result<Vec<_>, _>>()?;
+    /// assert_eq!(ids, vec![1, 2, 4, 3]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn iter(&self) -> TreeIter<'_, Q, T, P> {
+        // The nodes are indexed once, so that each step finds the children of a node without
+        // searching the whole tree.
+        let mut index = BTreeMap::new();
+        let mut root = None;
+        for node in self.nodes.iter() {
+            let is_root = node
+                .get_parent_id()
+                .expect("Error: Failed to get the node Id of the parent.")
+                .is_none();
+            if is_root && root.is_none() {
+                root = Some(node);
+            }
+            index
+                .entry(
+                    node.get_node_id()
+                        .expect("Error: Failed to get the node Id."),
+                )
+                .or_insert(node);
+        }
+        TreeIter {
+            index,
+            stack: root.into_iter().collect(),
+        }
+    }
+
+    /// Walk the subtree of a node with a visitor.
+    ///
+    /// The walk visits the nodes in pre-order. It calls `TreeVisitor::enter` when it reaches a
+    /// node and `TreeVisitor::leave` once the children of the node have been walked, so the visitor
+    /// knows where the children of each node start and end. The callbacks can skip the children of
+    /// a node or stop the walk, see `WalkControl`.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node to start the walk from.
+    /// * `visitor` - The visitor.
+    ///
+    /// # Returns
+    ///
+    /// This method returns an error if the node is not found in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree, TreeVisitor, WalkControl};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// struct Leaves(Vec<i32>);
+    ///
+    /// impl TreeVisitor<i32, i32> for Leaves {
+    ///     fn enter(&mut self, node: &Node<i32, i32>, depth: usize) -> WalkControl {
+    ///         if node.children_ref().is_empty() {
+    ///             self.0.push(node.get_node_id().unwrap());
+    ///         }
+    ///         if depth == 1 {
+    ///             WalkControl::SkipChildren
+    ///         } else {
+    ///             WalkControl::Continue
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+    /// tree.add_node(Node::new(4, Some(5)), Some(&node_2))?;
+    ///
+    /// let mut leaves = Leaves(vec![]);
+    /// tree.walk(&node_1, &mut leaves)?;
+    /// assert_eq!(leaves.0, vec![3]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn walk<K, V>(&self, node_id: &K, visitor: &mut V) -> crate::prelude::Result<()>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+        V: TreeVisitor<Q, T, P> + ?Sized,
+    {
+        let mut events = self.walk_events(node_id)?;
+        let mut depth = 0;
+        while let Some(event) = events.next() {
+            let control = match event {
+                WalkEvent::Enter(node) => {
+                    depth += 1;
+                    visitor.enter(node, depth - 1)
+                }
+                WalkEvent::Leave(node) => {
+                    depth -= 1;
+                    visitor.leave(node, depth)
+                }
+            };
+            match control {
+                WalkControl::Continue => {}
+                WalkControl::SkipChildren => events.skip_children(),
+                WalkControl::Stop => break,
+            }
+        }
+        Ok(())
+    }
+
+    /// Walk the subtree of a node as a sequence of events.
+    ///
+    /// This is the iterator form of `walk`: the iterator yields `WalkEvent::Enter` when the walk
+    /// reaches a node and `WalkEvent::Leave` once the children of the node have been walked. The
+    /// children of the node that was just entered can be skipped with `WalkEvents::skip_children`.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node to start the walk from.
+    ///
+    /// # Returns
+    ///
+    /// The events of the walk. This method returns an error if the node is not found in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree, WalkEvent};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    ///
+    /// let mut json = String::new();
+    /// for event in tree.walk_events(&node_1)? {
+    ///     match event {
+    ///         WalkEvent::Enter(node) => json += &format!("[{}", node.get_node_id()?),
+    ///         WalkEvent::Leave(_) => json += "]",
+    ///     }
+    /// }
+    /// assert_eq!(json, "[1[2]]");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn walk_events<K>(&self, node_id: &K) -> crate::prelude::Result<WalkEvents<'_, Q, T, P>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+    {
+        let node = self
+            .get_node_ref(node_id)
+            .ok_or_else(|| Error::node_not_found(node_id))?;
+        Ok(WalkEvents::new(&self.nodes, node))
+    }
+
+    /// Create a cursor that points at a node of the tree.
+    ///
+    /// The cursor moves between the parent, the children and the siblings of the node, see
+    /// `TreeCursor`.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node the cursor points at.
+    ///
+    /// # Returns
+    ///
+    /// The cursor. This method returns an error if the node is not found in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    ///
+    /// let mut cursor = tree.cursor(&node_1)?;
+    /// assert!(cursor.first_child());
+    /// assert_eq!(cursor.node().get_value()?, Some(3));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn cursor<K>(&self, node_id: &K) -> crate::prelude::Result<TreeCursor<'_, Q, T, P, E>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+    {
+        let node_id = self
+            .get_node_ref(node_id)
+            .ok_or_else(|| Error::node_not_found(node_id))?
+            .get_node_id()?;
+        TreeCursor::new(self, &node_id)
+    }
+
+    /// Create a cursor that points at a node of the tree and can edit the tree.
+    ///
+    /// The cursor moves like the one of `cursor`, and adds and removes nodes around the node it
+    /// points at, see `TreeCursorMut`.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node the cursor points at.
+    ///
+    /// # Returns
+    ///
+    /// The cursor. This method returns an error if the node is not found in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    ///
+    /// let mut cursor = tree.cursor_mut(&node_1)?;
+    /// cursor.insert_child(Node::new(2, Some(3)))?;
+    /// assert!(cursor.first_child());
+    /// assert_eq!(cursor.replace_value(Some(4))?, Some(3));
+    /// assert_eq!(tree.get_node_by_id(&2).unwrap().get_value()?, Some(4));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn cursor_mut<K>(
+        &mut self,
+        node_id: &K,
+    ) -> crate::prelude::Result<TreeCursorMut<'_, Q, T, P, E>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+    {
+        let node_id = self
+            .get_node_ref(node_id)
+            .ok_or_else(|| Error::node_not_found(node_id))?
+            .get_node_id()?;
+        TreeCursorMut::new(self, &node_id)
+    }
+
+    /// Remove a node from the tree.
+    ///
+    /// This method removes a node from the tree. The node is removed using the given removal strategy.
+    /// The removal strategy determines how the node and its children are removed from the tree. The
+    /// `RetainChildren` strategy retains the children of the node when the node is removed. The
+    /// `RemoveNodeAndChildren` strategy removes the node and its children when the node is removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node to remove.
+    /// * `strategy` - The strategy to use when removing the node.
+    ///
+    /// # Returns
+    /// An error if the node is not found in the tree or if the node is the root node and the removal
+    /// strategy is `RetainChildren`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree, NodeRemovalStrategy, Result};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    ///
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+    ///
+    /// tree.remove_node(&node_2, NodeRemovalStrategy::RetainChildren)?;
+    /// assert_eq!(tree.get_nodes().len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn remove_node<K>(
+        &mut self,
+        node_id: &K,
+        strategy: NodeRemovalStrategy,
+    ) -> crate::prelude::Result<()>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+    {
+        match strategy {
+            NodeRemovalStrategy::RetainChildren => {
+                let node = self
+                    .get_node_by_id(node_id)
+                    .ok_or_else(|| Error::node_not_found(node_id))?;
+                let parent_node_id = &node.get_parent_id()?.ok_or(InvalidOperation(
+                    String::from("Cannot remove root node with RetainChildren strategy"),
+                ))?;
+                let parent_node = self.find_node(parent_node_id)?;
+                parent_node.remove_child(node.clone())?;
+                let children = node.get_children_ids()?;
+                for child in children {
+                    if let Some(child) = self.get_node_by_id::<Q>(&child) {
+                        parent_node.add_child(child)?;
+                    }
+                }
+                self.nodes.retain(|n| !n.has_id(node_id));
+                let removed_id = node.get_node_id()?;
+                self.edges.remove::<Q>(&removed_id);
+                self.attributes.remove_node(&removed_id);
+                Ok(())
+            }
+            NodeRemovalStrategy::RemoveNodeAndChildren => {
+                let node = self
+                    .get_node_by_id(node_id)
+                    .ok_or_else(|| Error::node_not_found(node_id))?;
+                let children = node.get_children_ids()?;
+                if let Some(parent_id) = node.get_parent_id()? {
+                    let parent = self.find_node(&parent_id)?;
+                    parent.remove_child(node.clone())?;
+                }
+                self.nodes.retain(|n| !n.has_id(node_id));
+                let removed_id = node.get_node_id()?;
+                self.edges.remove::<Q>(&removed_id);
+                self.attributes.remove_node(&removed_id);
+                for child in children {
+                    let child = self.find_node(&child)?;
+                    node.remove_child(child.clone())?;
+                    self.remove_node::<Q>(&child.get_node_id()?, strategy)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Change the id of a node in the tree.
+    ///
+    /// This method gives a node a new id in place. The parent of the node lists the node under its
+    /// new id, the children of the node point to it by its new id, and the edge and attributes of
+    /// the node are kept. The root node can be renamed as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The current id of the node.
+    /// * `new_id` - The new id of the node.
+    ///
+    /// # Returns
+    ///
+    /// This method returns an error if the node is not found in the tree or if another node of the
+    /// tree already has the new id.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+    ///
+    /// tree.rename_node(&node_2, 20)?;
+    /// assert_eq!(tree.get_node_by_id(&node_1).unwrap().get_children_ids()?, vec![20]);
+    /// assert_eq!(tree.get_node_by_id(&3).unwrap().get_parent_id()?, Some(20));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn rename_node<K>(&mut self, node_id: &K, new_id: Q) -> crate::prelude::Result<()>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+    {
+        let old_id = self
+            .get_node_ref(node_id)
+            .ok_or_else(|| Error::node_not_found(node_id))?
+            .get_node_id()?;
+        if old_id == new_id {
+            return Ok(());
+        }
+        if self.get_node_ref::<Q>(&new_id).is_some() {
+            return Err(InvalidOperation(format!(
+                "Node {new_id:?} is already in the tree"
+            )));
+        }
+        self.rename_ids(&BTreeMap::from([(old_id, new_id)]));
+        Ok(())
+    }
+
+    /// Get a subsection of the tree.
+    ///
+    /// This method gets a subsection of the tree starting from the node with the given node id. The
+    /// subsection is a list of nodes that are descendants of the node with the given node id upto the
+    /// given number of descendants. If the number of descendants is `None`, all the descendants of the
+    /// node are included in the subsection.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node to get the subsection from.
+    /// * `generations` - The number of descendants to include in the subsection. If `None`, all the descendants of the node are included in the subsection.
+    ///
+    /// # Returns
+    ///
+    /// The subsection of the tree starting from the node with the given node id. The subsection is
+    /// named after the id of that node, written with its `Debug` implementation.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// # let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    ///
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+    ///
+    /// let subsection = tree.get_subtree(&node_2, None)?;
+    /// assert_eq!(subsection.get_nodes().len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_subtree<K>(
+        &self,
+        node_id: &K,
+        generations: Option<i32>,
+    ) -> crate::prelude::Result<SubTree<Q, T, P, E>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+        E: Clone,
+    {
+        let node = self
+            .get_node_ref(node_id)
+            .ok_or_else(|| Error::node_not_found(node_id))?;
+        let root_id = node.get_node_id()?;
+        let subsection = self.get_subtree_nodes(node, generations)?;
+        let subsection_ids = subsection
+            .iter()
+            .map(|x| x.get_node_id())
+            .collect::<crate::prelude::Result<BTreeSet<_>>>()?;
+        // The edge between the root of the subtree and its parent is not part of the subtree.
+        let edges = self
+            .edges
+            .iter()
+            .filter(|(id, _)| *id != &root_id && subsection_ids.contains::<Q>(id))
+            .map(|(id, edge)| (id.clone(), edge.clone()))
+            .collect();
+        Ok(SubTree {
+            name: Some(format!("{root_id:?}")),
+            nodes: subsection,
+            edges,
+            attributes: self
+                .attributes
+                .subset(&|node_id| subsection_ids.contains::<Q>(node_id)),
+            id_generator: self.id_generator.clone(),
+        })
+    }
+
+    /// Get the nodes of the subsection of the tree starting from a node.
+    fn get_subtree_nodes(
+        &self,
+        node: &Node<Q, T, P>,
+        generations: Option<i32>,
+    ) -> crate::prelude::Result<Nodes<Q, T, P>> {
+        let mut subsection = Nodes::default();
+        subsection.push(node.clone());
+        // Get the subsequent children of the node recursively for the number of generations and add them to the subsection.
+        let children = node.get_children_ids()?;
+        if let Some(generations) = generations {
+            for current_generation in 0..generations {
+                for child in children.iter() {
+                    subsection.append(
+                        &mut self
+                            .get_subtree_nodes(self.find_node(child)?, Some(current_generation))?,
+                    );
+                }
+            }
+        } else {
+            for child in children.iter() {
+                subsection.append(&mut self.get_subtree_nodes(self.find_node(child)?, None)?);
+            }
+        }
+        Ok(subsection)
+    }
+
+    /// Get a copy of a subsection of the tree.
+    ///
+    /// This method gets the same subsection as `get_subtree`, but its nodes are copies that share no
+    /// data with this tree. The root node of the copy has no parent, so the copy is a standalone
+    /// tree, and changes made to it do not affect this tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node to get the subsection from.
+    /// * `generations` - The number of descendants to include in the subsection. If `None`, all the descendants of the node are included in the subsection.
+    ///
+    /// # Returns
+    ///
+    /// A copy of the subsection of the tree starting from the node with the given node id.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// # let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    ///
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+    ///
+    /// let subsection = tree.get_subtree_owned(&node_2, None)?;
+    /// subsection.get_node_by_id(&3).unwrap().set_value(None)?;
+    /// assert_eq!(subsection.get_root_node(), subsection.get_node_by_id(&2));
+    /// assert_eq!(tree.get_node_by_id(&3).unwrap().get_value()?, Some(6));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_subtree_owned<K>(
+        &self,
+        node_id: &K,
+        generations: Option<i32>,
+    ) -> crate::prelude::Result<SubTree<Q, T, P, E>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+        T: Clone,
+        E: Clone,
+    {
+        self.get_subtree(node_id, generations)?.deep_clone()
+    }
+
+    /// Get the siblings of a node in the tree.
+    ///
+    /// This method gets the siblings of a node in the tree. The siblings of a node are the children
+    /// that share the same parent as the node.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node to get the siblings of.
+    /// * `inclusive` - A flag that indicates whether to include the node in the siblings list.
+    ///
+    /// # Returns
+    ///
+    /// The siblings of the node in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+    /// tree.add_node(Node::new(4, Some(7)), Some(&node_1))?;
+    ///
+    /// let siblings = tree.get_sibling_ids(&node_2, false)?;
+    /// assert_eq!(siblings.len(), 2);
+    ///
+    /// let siblings = tree.get_sibling_ids(&node_2, true)?;
+    /// assert_eq!(siblings.len(), 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_sibling_ids<K>(&self, node_id: &K, inclusive: bool) -> crate::prelude::Result<Vec<Q>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+    {
+        let node = self
+            .get_node_by_id(node_id)
+            .ok_or_else(|| Error::node_not_found(node_id))?;
+        if let Some(parent_id) = node.get_parent_id()? {
+            let parent = self.find_node(&parent_id)?;
+            if inclusive {
+                parent.get_children_ids()
+            } else {
+                Ok(parent
+                    .get_children_ids()?
+                    .iter()
+                    .filter(|x| (*x).borrow() != node_id)
+                    .cloned()
+                    .collect())
+            }
+        } else if inclusive {
+            // We need to clone this since Q does not implement Copy.
+            Ok(vec![node.get_node_id()?])
+        } else {
+            Ok(vec![])
+        }
+    }
+
+    /// Get the id of the sibling that follows a node.
+    ///
+    /// The siblings of a node are in the order of the children of its parent, which is the order
+    /// in which they were added unless the children were sorted with `Node::sort_children`.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node.
+    ///
+    /// # Returns
+    ///
+    /// The id of the next sibling, or `None` if the node is the last child of its parent or the
+    /// root node. This method returns an error if the node is not found in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+    /// let node_4 = tree.add_node(Node::new(4, Some(7)), Some(&node_1))?;
+    ///
+    /// assert_eq!(tree.get_next_sibling_id(&node_2)?, Some(node_3));
+    /// assert_eq!(tree.get_next_sibling_id(&node_4)?, None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_next_sibling_id<K>(&self, node_id: &K) -> crate::prelude::Result<Option<Q>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+    {
+        self.find_sibling_id(node_id, true)
+    }
+
+    /// Get the id of the sibling that precedes a node.
+    ///
+    /// See `get_next_sibling_id` for the order of the siblings.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node.
+    ///
+    /// # Returns
+    ///
+    /// The id of the previous sibling, or `None` if the node is the first child of its parent or
+    /// the root node. This method returns an error if the node is not found in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+    /// let node_4 = tree.add_node(Node::new(4, Some(7)), Some(&node_1))?;
+    ///
+    /// assert_eq!(tree.get_previous_sibling_id(&node_4)?, Some(node_3));
+    /// assert_eq!(tree.get_previous_sibling_id(&node_2)?, None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_previous_sibling_id<K>(&self, node_id: &K) -> crate::prelude::Result<Option<Q>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+    {
+        self.find_sibling_id(node_id, false)
+    }
+
+    /// Get the position of a node among the children of its parent.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node.
+    ///
+    /// # Returns
+    ///
+    /// The index of the node, starting at `0`, or `None` if the node is the root node. This method
+    /// returns an error if the node is not found in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+    /// let node_4 = tree.add_node(Node::new(4, Some(7)), Some(&node_1))?;
+    ///
+    /// assert_eq!(tree.get_child_index(&node_3)?, Some(1));
+    /// assert_eq!(tree.get_child_index(&node_1)?, None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_child_index<K>(&self, node_id: &K) -> crate::prelude::Result<Option<usize>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+    {
+        let node = self
+            .get_node_ref(node_id)
+            .ok_or_else(|| Error::node_not_found(node_id))?;
+        let Some(parent_id) = node.get_parent_id()? else {
+            return Ok(None);
+        };
+        let parent = self.find_node(&parent_id)?;
+        let index = parent
+            .children_ref()
+            .iter()
+            .position(|x| x.borrow() == node_id);
+        Ok(index)
+    }
+
+    /// Get the id of the first child of a node.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node.
+    ///
+    /// # Returns
+    ///
+    /// The id of the first child, or `None` if the node has no children. This method returns an
+    /// error if the node is not found in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+    /// let node_4 = tree.add_node(Node::new(4, Some(7)), Some(&node_1))?;
+    ///
+    /// assert_eq!(tree.get_first_child_id(&node_1)?, Some(node_2));
+    /// assert_eq!(tree.get_first_child_id(&node_3)?, None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_first_child_id<K>(&self, node_id: &K) -> crate::prelude::Result<Option<Q>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+    {
+        self.get_nth_child_id(node_id, 0)
+    }
+
+    /// Get the id of the last child of a node.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node.
+    ///
+    /// # Returns
+    ///
+    /// The id of the last child, or `None` if the node has no children. This method returns an
+    /// error if the node is not found in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+    /// let node_4 = tree.add_node(Node::new(4, Some(7)), Some(&node_1))?;
+    ///
+    /// assert_eq!(tree.get_last_child_id(&node_1)?, Some(node_4));
+    /// assert_eq!(tree.get_last_child_id(&node_2)?, None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_last_child_id<K>(&self, node_id: &K) -> crate::prelude::Result<Option<Q>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+    {
+        let node = self
+            .get_node_ref(node_id)
+            .ok_or_else(|| Error::node_not_found(node_id))?;
+        let child_id = node.children_ref().last().cloned();
+        Ok(child_id)
+    }
+
+    /// Get the id of the child of a node at the given position.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent_id` - The id of the node.
+    /// * `n` - The index of the child, starting at `0`.
+    ///
+    /// # Returns
+    ///
+    /// The id of the child, or `None` if the node has `n` children or less. This method returns an
+    /// error if the node is not found in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+    /// let node_4 = tree.add_node(Node::new(4, Some(7)), Some(&node_1))?;
+    ///
+    /// assert_eq!(tree.get_nth_child_id(&node_1, 2)?, Some(node_4));
+    /// assert_eq!(tree.get_nth_child_id(&node_1, 3)?, None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_nth_child_id<K>(&self, parent_id: &K, n: usize) -> crate::prelude::Result<Option<Q>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+    {
+        let parent = self
+            .get_node_ref(parent_id)
+            .ok_or_else(|| Error::node_not_found(parent_id))?;
+        let child_id = parent.children_ref().get(n).cloned();
+        Ok(child_id)
+    }
+
+    /// Get the id of the sibling that follows or precedes a node.
+    fn find_sibling_id<K>(&self, node_id: &K, next: bool) -> crate::prelude::Result<Option<Q>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+    {
+        let node = self
+            .get_node_ref(node_id)
+            .ok_or_else(|| Error::node_not_found(node_id))?;
+        let Some(parent_id) = node.get_parent_id()? else {
+            return Ok(None);
+        };
+        let parent = self.find_node(&parent_id)?;
+        let children = parent.children_ref();
+        let index = children
+            .iter()
+            .position(|x| x.borrow() == node_id)
+            .and_then(|x| {
+                if next {
+                    x.checked_add(1)
+                } else {
+                    x.checked_sub(1)
+                }
+            });
+        Ok(index.and_then(|x| children.get(x)).cloned())
+    }
+
+    /// Add a subsection to the tree.
+    ///
+    /// This method adds a subsection to the tree. The subsection is a list of nodes that are descendants
+    /// of the node with the given node id. The subsection is added as children of the node with the
+    /// given node id.
+    /// The nodes of the subsection are added as they are, so their ids must not already be in the
+    /// tree. Use `add_subtree_with` to add a subsection whose ids may collide with those of the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node to add the subsection to.
+    /// * `subtree` - The subsection to add to the tree.
+    ///
+    /// # Returns
+    /// This function return an error if:
+    /// - The node is not found in the tree.
+    /// - The subsection has no root node.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree, SubTree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_id = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let mut subtree = SubTree::new(Some("Sample Tree"));
+    /// let node_2 = subtree.add_node(Node::new(2, Some(3)), None)?;
+    /// subtree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+    /// tree.add_subtree(&node_id, subtree)?;
+    /// assert_eq!(tree.get_nodes().len(), 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_subtree<K>(
+        &mut self,
+        node_id: &K,
+        mut subtree: SubTree<Q, T, P, E>,
+    ) -> crate::prelude::Result<()>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+    {
+        let node = self
+            .get_node_by_id(node_id)
+            .ok_or_else(|| Error::node_not_found(node_id))?;
+        // Get the root node in the subsection and add it as a child of the node.
+        let subtree_nodes = subtree.get_nodes();
+        let root_node = subtree
+            .get_root_node()
+            .ok_or(InvalidOperation(String::from("Subtree has no root node.")))?;
+        node.add_child(root_node.clone())?;
+        self.nodes.append(&mut subtree_nodes.clone());
+        self.edges.append(&mut subtree.edges);
+        self.attributes.merge(mem::take(&mut subtree.attributes));
+        Ok(())
+    }
+
+    /// Add a subsection to the tree, resolving the ids of the subsection that are already in the
+    /// tree.
+    ///
+    /// This method adds a subsection to the tree in the same way as `add_subtree`, but first deals
+    /// with every node of the subsection whose id is already used by a node of the tree as the given
+    /// policy says. See `CollisionPolicy`. A copy of the subsection is added, so a subsection taken
+    /// from another tree with `get_subtree` leaves that tree unchanged. The root of the subsection
+    /// is the node whose parent is not in the subsection.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node to add the subsection to.
+    /// * `subtree` - The subsection to add to the tree.
+    /// * `policy` - What to do with the nodes of the subsection whose ids are already in the tree.
+    ///
+    /// # Returns
+    ///
+    /// The ids of the nodes of the subsection that were added to the tree, mapped to their ids in
+    /// the tree. Nodes left out by `CollisionPolicy::Skip` are not in the map. This function returns
+    /// an error without changing the tree if:
+    /// - The node is not found in the tree.
+    /// - The subsection has no root node.
+    /// - An id collides and the policy is `CollisionPolicy::Fail`.
+    /// - The id of the root of the subsection collides and the policy is `CollisionPolicy::Skip`,
+    ///   since nothing would be left to add.
+    /// - The policy is `CollisionPolicy::Overwrite` and the node to overwrite is the node the
+    ///   subsection is added to or one of its ancestors.
+    /// - The policy is `CollisionPolicy::Remap`, an id collides and the tree has no id generator,
+    ///   or its generator does not produce an unused id. See `add_value`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{CollisionPolicy, Node, SequentialIdGenerator, SubTree, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// tree.set_id_generator(SequentialIdGenerator::new(100, 1));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    ///
+    /// let mut subtree = SubTree::new(None);
+    /// let node_2 = subtree.add_node(Node::new(2, Some(4)), None)?;
+    /// subtree.add_node(Node::new(3, Some(5)), Some(&node_2))?;
+    ///
+    /// let ids = tree.add_subtree_with(&node_1, subtree, CollisionPolicy::Remap)?;
+    /// assert_eq!(ids.get(&2), Some(&100));
+    /// assert_eq!(ids.get(&3), Some(&3));
+    /// assert_eq!(tree.get_node_by_id(&node_1).unwrap().get_children_ids()?, vec![2, 100]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_subtree_with<K>(
+        &mut self,
+        node_id: &K,
+        subtree: SubTree<Q, T, P, E>,
+        policy: CollisionPolicy,
+    ) -> crate::prelude::Result<BTreeMap<Q, Q>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+        T: Clone,
+        E: Clone,
+    {
+        let target_id = self
+            .get_node_ref(node_id)
+            .ok_or_else(|| Error::node_not_found(node_id))?
+            .get_node_id()?;
+        // The subtree is copied first: its nodes may be shared with the tree it was taken from, and
+        // the copy of its root has no parent even when the original root had one.
+        let mut subtree = subtree.deep_clone()?;
+        let root_id = subtree
+            .get_root_node()
+            .ok_or(InvalidOperation(String::from("Subtree has no root node.")))?
+            .get_node_id()?;
+        let mut ids = subtree
+            .nodes
+            .iter()
+            .map(|x| x.get_node_id().map(|id| (id.clone(), id)))
+            .collect::<crate::prelude::Result<BTreeMap<_, _>>>()?;
+        let collisions = ids
+            .keys()
+            .filter(|id| self.get_node_ref::<Q>(id).is_some())
+            .cloned()
+            .collect::<Vec<_>>();
+        match policy {
+            CollisionPolicy::Fail => {
+                if let Some(id) = collisions.first() {
+                    return Err(InvalidOperation(format!(
+                        "Node {id:?} of the subtree is already in the tree"
+                    )));
+                }
+            }
+            CollisionPolicy::Skip => {
+                if collisions.contains(&root_id) {
+                    return Err(InvalidOperation(format!(
+                        "Node {root_id:?}, the root of the subtree, is already in the tree"
+                    )));
+                }
+                for id in collisions.iter() {
+                    if subtree.get_node_ref::<Q>(id).is_some() {
+                        subtree.remove_node::<Q>(id, NodeRemovalStrategy::RemoveNodeAndChildren)?;
+                    }
+                }
+                ids.retain(|id, _| subtree.get_node_ref::<Q>(id).is_some());
+            }
+            CollisionPolicy::Overwrite => {
+                let mut ancestor_ids = self.get_ancestor_ids::<Q>(&target_id)?;
+                ancestor_ids.push(target_id.clone());
+                if let Some(id) = collisions.iter().find(|x| ancestor_ids.contains(x)) {
+                    return Err(InvalidOperation(format!(
+                        "Node {id:?} cannot be overwritten because the subtree is added below it"
+                    )));
+                }
+                for id in collisions.iter() {
+                    // The node may already be gone with the descendants of another collision.
+                    if self.get_node_ref::<Q>(id).is_some() {
+                        self.remove_node::<Q>(id, NodeRemovalStrategy::RemoveNodeAndChildren)?;
+                    }
+                }
+            }
+            CollisionPolicy::Remap => {
+                let mut renames = BTreeMap::new();
+                for id in collisions {
+                    let used = self.nodes.len() + ids.len() + renames.len();
+                    let new_id = self.generate_id(used, |x| {
+                        self.get_node_ref::<Q>(x).is_some()
+                            || ids.contains_key::<Q>(x)
+                            || renames.values().any(|y| y == x)
+                    })?;
+                    ids.insert(id.clone(), new_id.clone());
+                    renames.insert(id, new_id);
+                }
+                subtree.rename_ids(&renames);
+            }
+        }
+        self.add_subtree::<Q>(&target_id, subtree)?;
+        Ok(ids)
+    }
+
+    /// Replace the ids of the nodes that are keys of `ids` with the matching values, along with
+    /// the references to them and their edges and attributes.
+    fn rename_ids(&mut self, ids: &BTreeMap<Q, Q>) {
+        self.nodes.iter().for_each(|x| x.rename_ids(ids));
+        let edges = ids
+            .iter()
+            .filter_map(|(old_id, new_id)| Some((new_id.clone(), self.edges.remove(old_id)?)))
+            .collect::<Vec<_>>();
+        self.edges.extend(edges);
+        self.attributes.rename_nodes(ids);
+    }
+
+    /// Traverse the subtree from the given node.
+    ///
+    /// This method traverses the subtree from the given node in the given order.
+    ///
+    /// # Arguments
+    ///
+    /// * `order` - The order to traverse the tree.
+    /// * `node_id` - The id of the node to start the traversal from.
+    ///
+    /// # Returns
+    ///
+    /// The nodes in the tree in the given order. This method returns an error if the node is not found
+    /// in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree, TraversalStrategy};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+    ///
+    /// let ordered_nodes = tree.traverse(&node_1, TraversalStrategy::PreOrder)?;
+    /// # let expected = vec![1, 2, 3];
+    /// # assert_eq!(ordered_nodes, expected);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn traverse<K>(
+        &self,
+        node_id: &K,
+        order: TraversalStrategy,
+    ) -> crate::prelude::Result<Vec<Q>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Eq + ?Sized,
+    {
+        let node = self
+            .get_node_ref(node_id)
+            .ok_or_else(|| Error::node_not_found(node_id))?;
+        traverse_subtree(&node.get_node_id()?, order, &|node_id| {
+            self.find_node(node_id)?.get_children_ids()
+        })
+    }
+
+    /// Get the attributes of a given type attached to the nodes of the tree.
+    ///
+    /// Attributes are annotations kept next to the nodes instead of inside their values, such as
+    /// whether a node is collapsed or a cached aggregate. The tree holds one table per attribute
+    /// type and keeps it in sync with its nodes: the attributes of removed nodes are dropped,
+    /// `get_subtree` copies the attributes of the nodes of the subtree, `add_subtree` adds the
+    /// attributes of the subtree and cloning the tree copies them. Attributes do not take part in
+    /// comparing or hashing trees, and they are not serialized with the tree unless a type is opted
+    /// in with `with_attributes`. A table can also be serialized on its own and attached again with
+    /// `insert_attributes`.
+    ///
+    /// # Returns
+    ///
+    /// The attributes of type `A`, or `None` if no attribute of this type was set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, NodeRemovalStrategy, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Collapsed(bool);
+    ///
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// tree.set_attribute(&node_2, Collapsed(true))?;
+    ///
+    /// let attributes = tree.attributes::<Collapsed>().unwrap();
+    /// assert_eq!(attributes.get(&node_2), Some(&Collapsed(true)));
+    ///
+    /// tree.remove_node(&node_2, NodeRemovalStrategy::RemoveNodeAndChildren)?;
+    /// assert!(tree.attributes::<Collapsed>().unwrap().is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn attributes<A>(&self) -> Option<&NodeAttributes<Q, A>>
+    where
+        Q: 'static,
+        A: 'static,
+    {
+        self.attributes.get()
+    }
+
+    /// Set an attribute of a node.
+    ///
+    /// This method attaches the attribute to the node in the table of its type, creating the table
+    /// if needed. See `attributes`.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node.
+    /// * `attribute` - The attribute to set.
+    ///
+    /// # Returns
+    ///
+    /// The previous attribute of this type of the node, or an error if the node is not in the tree.
+    pub fn set_attribute<K, A>(
+        &mut self,
+        node_id: &K,
+        attribute: A,
+    ) -> crate::prelude::Result<Option<A>>
+    where
+        Q: Borrow<K> + Send + Sync + 'static,
+        K: Debug + Eq + ?Sized,
+        A: Clone + Send + Sync + 'static,
+    {
+        let node_id = self
+            .get_node_ref(node_id)
+            .ok_or_else(|| Error::node_not_found(node_id))?
+            .get_node_id()?;
+        Ok(self.attributes.get_or_insert().insert(node_id, attribute))
+    }
+
+    /// Remove an attribute of a node.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node.
+    ///
+    /// # Returns
+    ///
+    /// The removed attribute, or `None` if the node had no attribute of this type.
+    pub fn remove_attribute<A, K>(&mut self, node_id: &K) -> Option<A>
+    where
+        Q: Borrow<K> + 'static,
+        K: Ord + ?Sized,
+        A: 'static,
+    {
+        self.attributes.get_mut::<A>()?.remove(node_id)
+    }
+
+    /// Remove the attributes of a given type from the tree.
+    ///
+    /// # Returns
+    ///
+    /// The removed attributes, or `None` if no attribute of this type was set.
+    pub fn take_attributes<A>(&mut self) -> Option<NodeAttributes<Q, A>>
+    where
+        Q: 'static,
+        A: 'static,
+    {
+        self.attributes.take()
+    }
+
+    /// Attach a table of attributes to the tree.
+    ///
+    /// This method replaces the attributes of the same type, for instance to restore attributes that
+    /// were serialized separately from the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `attributes` - The attributes to attach.
+    ///
+    /// # Returns
+    ///
+    /// The attributes of the same type that were replaced, or an error if an attribute belongs to a
+    /// node that is not in the tree. The tree is left unchanged on error.
+    pub fn insert_attributes<A>(
+        &mut self,
+        attributes: NodeAttributes<Q, A>,
+    ) -> crate::prelude::Result<Option<NodeAttributes<Q, A>>>
+    where
+        Q: Send + Sync + 'static,
+        A: Clone + Send + Sync + 'static,
+    {
+        for (node_id, _) in attributes.iter() {
+            self.find_node(node_id)?;
+        }
+        Ok(self.attributes.insert(attributes))
+    }
+
+    /// Copy the tree into new nodes that share no data with this tree.
+    ///
+    /// Cloning a tree clones the handles to its nodes, so changes made to the nodes of a clone are
+    /// visible in the original. This method instead copies the id and the value of every node into
+    /// new nodes and links them the same way, so the copy can be changed without affecting this
+    /// tree. Links to nodes that are not part of this tree, such as the parent of the root node of a
+    /// subtree, are not copied. The attributes of the nodes are copied as well. The copy takes
+    /// `O(n log n)` time for a tree of `n` nodes.
+    ///
+    /// # Returns
+    ///
+    /// A copy of the tree that shares no data with this tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    ///
+    /// let copy = tree.deep_clone()?;
+    /// copy.get_node_by_id(&2).unwrap().set_value(Some(4))?;
+    /// assert_eq!(tree.get_node_by_id(&2).unwrap().get_value()?, Some(3));
+    /// assert_eq!(copy.get_node_by_id(&2).unwrap().get_value()?, Some(4));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn deep_clone(&self) -> crate::prelude::Result<Self>
+    where
+        T: Clone,
+        E: Clone,
+    {
+        // The copies are indexed by id so that the tree is copied in a single pass over its links.
+        let mut indices: BTreeMap<Q, usize> = BTreeMap::new();
+        let mut copies = vec![];
+        for node in self.nodes.iter() {
+            let node_id = node.get_node_id()?;
+            if !indices.contains_key(&node_id) {
+                indices.insert(node_id.clone(), copies.len());
+                copies.push((Node::new_in(node_id, node.get_value()?, P::default()), node));
+            }
+        }
+        for (copy, node) in copies.iter() {
+            for child_id in node.children_ref().iter() {
+                if let Some(child) = indices.get(child_id) {
+                    copy.add_child(copies[*child].0.clone())?;
+                }
+            }
+        }
+        let nodes = Nodes::new(copies.into_iter().map(|(copy, _)| copy).collect());
+        Ok(Tree {
+            name: self.name.clone(),
+            nodes,
+            edges: self.edges.clone(),
+            attributes: self.attributes.clone(),
+            id_generator: self.id_generator.clone(),
+        })
+    }
+
+    /// Render the tree as text.
+    ///
+    /// This method renders the tree with the options of the given renderer. The glyphs, the node
+    /// labels, the maximum depth, the maximum number of children per node and the header are all
+    /// configured on the renderer. Displaying a tree is the same as rendering it with
+    /// `TreeRenderer::new()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `renderer` - The renderer holding the rendering options.
+    ///
+    /// # Returns
+    ///
+    /// The rendered tree. An empty tree renders as its header only.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree, TreeRenderer};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// tree.add_node(Node::new(3, Some(4)), Some(&node_1))?;
+    ///
+    /// let renderer = TreeRenderer::with_formatter(|id: &i32, _: Option<&i32>| id.to_string())
+    ///     .max_children(1)
+    ///     .header(false);
+    /// assert_eq!(tree.render(&renderer)?, "1\n├── 2\n└── … 1 more\n");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn render(&self, renderer: &TreeRenderer<Q, T>) -> crate::prelude::Result<String> {
+        let root_id = self.get_root_node().map(|x| x.get_node_id()).transpose()?;
+        renderer.render_tree(self.get_name(), root_id, |node_id| {
+            self.get_node_label(renderer, node_id)
+        })
+    }
+
+    /// Label a node with a renderer and get the ids of its children.
+    fn get_node_label(
+        &self,
+        renderer: &TreeRenderer<Q, T>,
+        node_id: &Q,
+    ) -> crate::prelude::Result<(String, Vec<Q>)> {
+        let node = self.find_node(node_id)?;
+        let label = node.with_value(|value| renderer.label(node_id, value));
+        Ok((label, node.get_children_ids()?))
+    }
+
+    /// Render the tree as text with the data of its edges.
+    ///
+    /// This method renders the tree in the same way as `render`, and adds the data of the edge
+    /// between each node and its parent after the label of the node, in square brackets. Nodes whose
+    /// edge has no data are rendered as in `render`.
+    ///
+    /// # Arguments
+    ///
+    /// * `renderer` - The renderer holding the rendering options.
+    ///
+    /// # Returns
+    ///
+    /// The rendered tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{DefaultStorage, Node, Tree, TreeRenderer};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32, DefaultStorage, &str> =
+    ///     Tree::new_with_edges(None, DefaultStorage::default());
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// tree.set_edge(&node_2, "manages")?;
+    ///
+    /// let renderer = TreeRenderer::with_formatter(|id: &i32, _: Option<&i32>| id.to_string())
+    ///     .header(false);
+    /// assert_eq!(tree.render_with_edges(&renderer)?, "1\n└── 2 [manages]\n");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn render_with_edges(&self, renderer: &TreeRenderer<Q, T>) -> crate::prelude::Result<String>
+    where
+        E: Display,
+    {
+        let root_id = self.get_root_node().map(|x| x.get_node_id()).transpose()?;
+        renderer.render_tree(self.get_name(), root_id, |node_id| {
+            let node = self.find_node(node_id)?;
+            let mut label = node.with_value(|value| renderer.label(node_id, value));
+            if let Some(edge) = self.edges.get(node_id) {
+                label = format!("{label} [{edge}]");
+            }
+            Ok((label, node.get_children_ids()?))
+        })
+    }
+
+    /// Get a value that displays the tree with the data of its edges.
+    ///
+    /// The tree is displayed as `render_with_edges` renders it with the default renderer. The tree
+    /// itself implements `Display` without its edges.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{DefaultStorage, Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32, DefaultStorage, u32> =
+    ///     Tree::new_with_edges(Some("Costs"), DefaultStorage::default());
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// tree.set_edge(&node_2, 7)?;
+    ///
+    /// # #[cfg(not(feature = "print_node_id"))]
+    /// assert_eq!(tree.display_edges().to_string(), "Costs\n*****\n2\n└── 3 [7]\n");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn display_edges(&self) -> DisplayEdges<'_, Q, T, P, E>
+    where
+        Q: Display,
+        T: Display,
+        E: Display,
+    {
+        DisplayEdges { tree: self }
+    }
+
+    /// Export the tree as a Mermaid diagram.
+    ///
+    /// This method exports the tree as a Mermaid flowchart or mindmap. The tree is laid out by the
+    /// default `TreeRenderer`, so the nodes are in the same order and have the same labels as when
+    /// rendering the tree, and a node without a value gets an empty label. Labels are escaped so
+    /// that values containing quotes, brackets or newlines do not break the diagram. If the tree has
+    /// a name, it is used as the title of the diagram.
+    ///
+    /// # Arguments
+    ///
+    /// * `style` - The kind of Mermaid diagram to produce.
+    ///
+    /// # Returns
+    ///
+    /// The Mermaid source of the diagram. An empty tree produces a diagram with no nodes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{MermaidStyle, Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(None);
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    ///
+    /// let diagram = tree.to_mermaid(MermaidStyle::Flowchart)?;
+    /// assert!(diagram.starts_with("flowchart TD\n"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_mermaid(&self, style: MermaidStyle) -> crate::prelude::Result<String>
+    where
+        Q: Display,
+        T: Display,
+    {
+        let mut diagram = String::new();
+        if let Some(name) = &self.name {
+            diagram.push_str(&format!(
+                "---\ntitle: {}\n---\n",
+                escape_mermaid_label(name)
+            ));
+        }
+        match style {
+            MermaidStyle::Flowchart => diagram.push_str("flowchart TD\n"),
+            MermaidStyle::Mindmap => diagram.push_str("mindmap\n"),
+        }
+        for (index, line) in self.get_diagram_lines()?.into_iter().enumerate() {
+            let label = escape_mermaid_label(&line.label);
+            match (style, line.parent) {
+                (MermaidStyle::Flowchart, None) => {
+                    diagram.push_str(&format!("    n{index}[\"{label}\"]\n"));
+                }
+                (MermaidStyle::Flowchart, Some(parent_index)) => {
+                    diagram.push_str(&format!("    n{parent_index} --> n{index}[\"{label}\"]\n"));
+                }
+                (MermaidStyle::Mindmap, _) => {
+                    let indent = "    ".repeat(line.depth + 1);
+                    diagram.push_str(&format!("{indent}n{index}[\"{label}\"]\n"));
+                }
+            }
+        }
+        Ok(diagram)
+    }
+
+    /// Export the tree as a PlantUML diagram.
+    ///
+    /// This method exports the tree as a PlantUML work breakdown structure or mindmap. The tree is
+    /// laid out by the default `TreeRenderer`, so the nodes are in the same order and have the same
+    /// labels as when rendering the tree, and a node without a value gets an empty label. Labels are
+    /// escaped so that values containing brackets or newlines do not break the diagram. If the tree
+    /// has a name, it is used as the title of the diagram.
+    ///
+    /// # Arguments
+    ///
+    /// * `style` - The kind of PlantUML diagram to produce.
+    ///
+    /// # Returns
+    ///
+    /// The PlantUML source of the diagram. An empty tree produces a diagram with no nodes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, PlantUmlStyle, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(None);
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    ///
+    /// let diagram = tree.to_plantuml(PlantUmlStyle::Wbs)?;
+    /// assert!(diagram.starts_with("@startwbs\n"));
+    /// assert!(diagram.ends_with("@endwbs\n"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_plantuml(&self, style: PlantUmlStyle) -> crate::prelude::Result<String>
+    where
+        Q: Display,
+        T: Display,
+    {
+        let (start, end) = match style {
+            PlantUmlStyle::Wbs => ("@startwbs", "@endwbs"),
+            PlantUmlStyle::Mindmap => ("@startmindmap", "@endmindmap"),
+        };
+        let mut diagram = format!("{start}\n");
+        if let Some(name) = &self.name {
+            diagram.push_str(&format!("title {}\n", escape_plantuml_label(name)));
+        }
+        for line in self.get_diagram_lines()? {
+            let label = escape_plantuml_label(&line.label);
+            diagram.push_str(&format!("{} {label}\n", "*".repeat(line.depth + 1)));
+        }
+        diagram.push_str(&format!("{end}\n"));
+        Ok(diagram)
+    }
+
+    /// Export the tree as materialized paths.
+    ///
+    /// This method returns a row for each node holding the id of the node and the path from the
+    /// root node to it. The path is made of the ids of the nodes on the way, each one preceded by
+    /// the separator, so the root node `1` has the path `/1` and its child `2` has the path `/1/2`.
+    /// The rows are in pre-order, which is also the order needed to rebuild the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `separator` - The separator placed before each id in a path.
+    ///
+    /// # Returns
+    ///
+    /// The `(id, path)` rows of the tree. An empty tree has no rows. This method returns an error if
+    /// the separator is empty or if the text of a node id contains the separator, since the path
+    /// could not be split back into the same ids.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<&str, i32> = Tree::new(None);
+    /// let root = tree.add_node(Node::new("root", Some(1)), None)?;
+    /// let a = tree.add_node(Node::new("a", Some(2)), Some(&root))?;
+    /// tree.add_node(Node::new("b", Some(3)), Some(&a))?;
+    ///
+    /// let rows = tree.to_materialized_paths("/")?;
+    /// assert_eq!(rows[2], ("b", "/root/a/b".to_string()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_materialized_paths(&self, separator: &str) -> crate::prelude::Result<Vec<(Q, String)>>
+    where
+        Q: Display,
+    {
+        if separator.is_empty() {
+            return Err(InvalidOperation(String::from(
+                "The separator must not be empty.",
+            )));
+        }
+        let mut rows: Vec<(Q, String)> = vec![];
+        for (_, node, _, parent_index) in self.get_diagram_nodes()? {
+            let node_id = node.get_node_id()?;
+            Self::check_path_segment(&node_id, separator)?;
+            let parent_path = parent_index.map_or("", |x| rows[x].1.as_str());
+            let path = format!("{parent_path}{separator}{node_id}");
+            rows.push((node_id, path));
+        }
+        Ok(rows)
+    }
+
+    /// Export the tree as nested sets.
+    ///
+    /// This method returns a row for each node holding the id of the node, its left and right
+    /// bounds and its depth. The bounds are numbered from `1` in a depth-first walk of the tree, so
+    /// the bounds of a node enclose the bounds of all of its descendants. The root node has a depth
+    /// of `0`. The rows are in pre-order, which is also the order of their left bounds.
+    ///
+    /// # Returns
+    ///
+    /// The `(id, left, right, depth)` rows of the tree. An empty tree has no rows.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(None);
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// tree.add_node(Node::new(3, Some(4)), Some(&node_2))?;
+    /// tree.add_node(Node::new(4, Some(5)), Some(&node_1))?;
+    ///
+    /// assert_eq!(
+    ///     tree.to_nested_sets()?,
+    ///     vec![(1, 1, 8, 0), (2, 2, 5, 1), (3, 3, 4, 2), (4, 6, 7, 1)]
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_nested_sets(&self) -> crate::prelude::Result<Vec<(Q, usize, usize, usize)>> {
+        let nodes = self.get_diagram_nodes()?;
+        let mut sizes = vec![1; nodes.len()];
+        for (index, _, _, parent_index) in nodes.iter().rev() {
+            if let Some(parent_index) = parent_index {
+                sizes[*parent_index] += sizes[*index];
+            }
+        }
+        nodes
+            .into_iter()
+            .map(|(index, node, depth, _)| {
+                // Every node visited before this one has been closed, except for its ancestors.
+                let left = 2 * index - depth + 1;
+                Ok((
+                    node.get_node_id()?,
+                    left,
+                    left + 2 * sizes[index] - 1,
+                    depth,
+                ))
+            })
+            .collect()
+    }
+
+    /// Export the tree as a closure table.
+    ///
+    /// This method returns a row for each pair of a node and one of its ancestors, holding the id of
+    /// the ancestor, the id of the node and the number of edges between them. Every node is also
+    /// paired with itself at a depth of `0`. The rows are grouped by node in pre-order and each
+    /// group is ordered from the node itself up to the root node.
+    ///
+    /// # Returns
+    ///
+    /// The `(ancestor, descendant, depth)` rows of the tree. An empty tree has no rows.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(None);
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    ///
+    /// assert_eq!(tree.to_closure_table()?, vec![(1, 1, 0), (2, 2, 0), (1, 2, 1)]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_closure_table(&self) -> crate::prelude::Result<Vec<(Q, Q, usize)>> {
+        let nodes = self.get_diagram_nodes()?;
+        let mut rows = vec![];
+        for (index, node, _, _) in nodes.iter() {
+            let node_id = node.get_node_id()?;
+            let mut ancestor_index = Some(*index);
+            let mut depth = 0;
+            while let Some(index) = ancestor_index {
+                let (_, ancestor, _, parent_index) = &nodes[index];
+                rows.push((ancestor.get_node_id()?, node_id.clone(), depth));
+                ancestor_index = *parent_index;
+                depth += 1;
+            }
+        }
+        Ok(rows)
+    }
+
+    /// Lay out the tree for the diagram exporters.
+    ///
+    /// The tree is laid out by the default renderer, so the nodes of a diagram are in the same
+    /// order and have the same labels as when rendering the tree.
+    fn get_diagram_lines(&self) -> crate::prelude::Result<Vec<TreeLine>>
+    where
+        Q: Display,
+        T: Display,
+    {
+        let renderer = TreeRenderer::new();
+        let root_id = self.get_root_node().map(|x| x.get_node_id()).transpose()?;
+        renderer.layout(root_id, |node_id| self.get_node_label(&renderer, node_id))
+    }
+
+    /// Get the nodes of the tree in the order that they are displayed.
+    ///
+    /// Each entry holds the position of the node in the display order, the node, its depth and the
+    /// position of its parent.
+    #[doc(hidden)]
+    #[allow(clippy::type_complexity)]
+    fn get_diagram_nodes(
+        &self,
+    ) -> crate::prelude::Result<Vec<(usize, Node<Q, T, P>, usize, Option<usize>)>> {
+        let mut diagram_nodes = vec![];
+        let Some(root) = self.get_root_node() else {
+            return Ok(diagram_nodes);
+        };
+        let mut stack = vec![(root, 0, None)];
+        while let Some((node, depth, parent_index)) = stack.pop() {
+            let index = diagram_nodes.len();
+            for child_id in node.get_children_ids()?.iter().rev() {
+                let child = self
+                    .get_node_by_id(child_id)
+                    .ok_or_else(|| Error::node_not_found(child_id))?;
+                stack.push((child, depth + 1, Some(index)));
+            }
+            diagram_nodes.push((index, node, depth, parent_index));
+        }
+        Ok(diagram_nodes)
+    }
+
+    /// Check that a node id can be a segment of a materialized path.
+    ///
+    /// The separator must not occur in the text of the id, otherwise the path would not split back
+    /// into the ids it was made of.
+    fn check_path_segment(node_id: &Q, separator: &str) -> crate::prelude::Result<()>
+    where
+        Q: Display,
+    {
+        if node_id.to_string().contains(separator) {
+            return Err(InvalidOperation(format!(
+                "Node {node_id:?} contains the separator '{separator}'."
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl<Q, T, P> Tree<Q, T, P>
+where
+    Q: PartialEq + Eq + Clone + Debug + Hash + Ord,
+    P: NodeStorage,
+{
+    /// Create a new tree with the given storage.
+    ///
+    /// This method creates a new tree with no nodes in the same way as `Tree::new` but lets the
+    /// storage of the nodes be chosen. The nodes added to the tree must use the same storage.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree_name` - The name of the tree.
+    /// * `storage` - The storage used to share the data of the nodes.
+    ///
+    /// # Returns
+    ///
+    /// A new tree with no nodes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{ArcStorage, Node, ThreadSafeTree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: ThreadSafeTree<i32, i32> = ThreadSafeTree::new_in(None, ArcStorage);
+    /// tree.add_node(Node::new_in(1, Some(2), ArcStorage), None)?;
+    /// let handle = std::thread::spawn(move || tree.get_height());
+    /// assert_eq!(handle.join().unwrap()?, 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_in(tree_name: Option<&str>, _storage: P) -> Self {
+        Self {
+            name: tree_name.map(|x| x.to_string()),
+            nodes: Nodes::default(),
+            edges: BTreeMap::new(),
+            attributes: AttributeTables::default(),
+            id_generator: SharedIdGenerator::default(),
+        }
+    }
+
+    /// Create a tree from an indented text outline.
+    ///
+    /// This method parses an outline where every non-blank line holds a node and the depth of the
+    /// node is given by its indentation. The outline written when displaying a tree can be parsed
+    /// with the `IndentStyle::BoxDrawing` style, including the name of the tree if it has one. The
+    /// node for each line is created by the given closure from the text that follows the indentation.
+    ///
+    /// # Arguments
+    ///
+    /// * `outline` - The text outline of the tree.
+    /// * `style` - The indentation used by the outline.
+    /// * `create_node` - The closure used to create the node for each line.
+    ///
+    /// # Returns
+    ///
+    /// The parsed tree. This method returns a `ParseError` holding the line and column of the problem
+    /// if the indentation is malformed or a node id is used more than once. Errors returned by the
+    /// closure are passed through.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{IndentStyle, Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let outline = "Risk\n  Fixed Income\n    Debt\n  Equity\n";
+    /// let tree: Tree<String, ()> = Tree::from_indented_with(outline, IndentStyle::Spaces(2), |label| {
+    ///     Ok(Node::new(label.to_string(), None))
+    /// })?;
+    /// assert_eq!(tree.get_ancestor_ids(&"Debt".to_string())?, vec!["Fixed Income", "Risk"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_indented_with(
+        outline: &str,
+        style: IndentStyle,
+        mut create_node: impl FnMut(&str) -> crate::prelude::Result<Node<Q, T, P>>,
+    ) -> crate::prelude::Result<Self> {
+        Tree::build_from_outline(outline, style, |entry| create_node(entry.label))
+    }
+
+    /// Create a tree from an indented text outline with auto-generated node ids.
+    ///
+    /// This method parses an outline in the same way as `Tree::from_indented_with`. The value of each
+    /// node is parsed from the text that follows the indentation and the node is given an
+    /// auto-generated id.
+    ///
+    /// # Arguments
+    ///
+    /// * `outline` - The text outline of the tree.
+    /// * `style` - The indentation used by the outline.
+    ///
+    /// # Returns
+    ///
+    /// The parsed tree. This method returns a `ParseError` holding the line and column of the problem
+    /// if the indentation is malformed or a value can not be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::*;
+    ///
+    /// # #[cfg(feature = "auto_id")]
+    /// # fn main() -> Result<()> {
+    /// let tree: Tree<AutomatedId, i32> = Tree::from_indented("1\n\t2\n\t\t3\n\t4\n", IndentStyle::Tabs)?;
+    /// assert_eq!(tree.get_height()?, 2);
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "auto_id"))]
+    /// # fn main() {}
+    /// ```
+    ///
+    /// This is available only when the `auto_id` feature is enabled.
+    #[cfg(feature = "auto_id")]
+    pub fn from_indented(outline: &str, style: IndentStyle) -> crate::prelude::Result<Self>
+    where
+        Q: From<u128>,
+        T: FromStr,
+    {
+        Tree::build_from_outline(outline, style, |entry| {
+            let value = entry.label.parse().map_err(|_| ParseError {
+                line: entry.line,
+                column: entry.column,
+                message: format!("Invalid value '{}'", entry.label),
+            })?;
+            Ok(Node::new_with_auto_id_in(Some(value), P::default()))
+        })
+    }
+
+    /// Create a tree from materialized paths.
+    ///
+    /// This method rebuilds a tree from `(id, path)` rows such as the ones returned by
+    /// `Tree::to_materialized_paths`. The parent of a node is the node whose path is the path of the
+    /// node without its last segment and the node whose path has a single segment is the root node.
+    /// The rows can be given in any order. Children keep the relative order of their rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - The `(id, path)` rows of the tree.
+    /// * `separator` - The separator placed before each id in a path.
+    /// * `value_of` - The closure used to get the value of the node with the given id.
+    ///
+    /// # Returns
+    ///
+    /// The rebuilt tree. This method returns an error if the separator is empty, if the text of a
+    /// node id contains the separator, if a node id or a path is used more than once, if the parent
+    /// of a node is missing or if there is more than one root node.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::Tree;
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let rows = vec![(3, "/1/2/3".to_string()), (1, "/1".to_string()), (2, "/1/2".to_string())];
+    /// let tree: Tree<i32, i32> = Tree::from_materialized_paths(rows, "/", |id| Some(id * 10))?;
+    /// assert_eq!(tree.get_ancestor_ids(&3)?, vec![2, 1]);
+    /// assert_eq!(tree.get_node_by_id(&3).unwrap().get_value()?, Some(30));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_materialized_paths(
+        rows: impl IntoIterator<Item = (Q, String)>,
+        separator: &str,
+        mut value_of: impl FnMut(&Q) -> Option<T>,
+    ) -> crate::prelude::Result<Self>
+    where
+        Q: Display,
+    {
+        if separator.is_empty() {
+            return Err(InvalidOperation(String::from(
+                "The separator must not be empty.",
+            )));
+        }
+        let mut rows = rows.into_iter().collect::<Vec<_>>();
+        rows.sort_by_key(|(_, path)| path.matches(separator).count());
+        let mut tree = Tree::new_in(None, P::default());
+        let mut path_ids: BTreeMap<&str, Q> = BTreeMap::new();
+        for (node_id, path) in rows.iter() {
+            Self::check_path_segment(node_id, separator)?;
+            if tree.get_node_by_id(node_id).is_some() {
+                return Err(InvalidOperation(format!("Duplicate node id {node_id:?}.")));
+            }
+            let parent_path = path.rsplit_once(separator).map_or("", |x| x.0);
+            let parent_id = match parent_path.is_empty() {
+                true => None,
+                false => Some(path_ids.get(parent_path).ok_or(InvalidOperation(format!(
+                    "The parent of node {node_id:?} at '{path}' is missing."
+                )))?),
+            };
+            tree.add_node(
+                Node::new_in(node_id.clone(), value_of(node_id), P::default()),
+                parent_id,
+            )?;
+            if path_ids.insert(path, node_id.clone()).is_some() {
+                return Err(InvalidOperation(format!("Duplicate path '{path}'.")));
+            }
+        }
+        Ok(tree)
+    }
+
+    /// Create a tree from nested sets.
+    ///
+    /// This method rebuilds a tree from `(id, left, right, depth)` rows such as the ones returned by
+    /// `Tree::to_nested_sets`. The parent of a node is the node with the closest bounds that enclose
+    /// its bounds. The rows can be given in any order. Children are ordered by their left bound.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - The `(id, left, right, depth)` rows of the tree.
+    /// * `value_of` - The closure used to get the value of the node with the given id.
+    ///
+    /// # Returns
+    ///
+    /// The rebuilt tree. This method returns an error if a node id is used more than once, if the
+    /// bounds of a node are not strictly increasing or overlap with the bounds of another node
+    /// without being nested, if the depth of a node does not match its bounds or if there is more
+    /// than one root node.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::Tree;
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let rows = vec![(1, 1, 6, 0), (2, 2, 3, 1), (3, 4, 5, 1)];
+    /// let tree: Tree<i32, i32> = Tree::from_nested_sets(rows, |_| None)?;
+    /// assert_eq!(tree.get_sibling_ids(&2, false)?, vec![3]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_nested_sets(
+        rows: impl IntoIterator<Item = (Q, usize, usize, usize)>,
+        mut value_of: impl FnMut(&Q) -> Option<T>,
+    ) -> crate::prelude::Result<Self> {
+        let mut rows = rows.into_iter().collect::<Vec<_>>();
+        rows.sort_by_key(|(_, left, _, _)| *left);
+        let mut tree = Tree::new_in(None, P::default());
+        // The ids and bounds of the ancestors of the next node.
+        let mut ancestors: Vec<(Q, usize, usize)> = vec![];
+        for (node_id, left, right, depth) in rows {
+            if tree.get_node_by_id(&node_id).is_some() {
+                return Err(InvalidOperation(format!("Duplicate node id {node_id:?}.")));
+            }
+            if left >= right {
+                return Err(InvalidOperation(format!(
+                    "The bounds of node {node_id:?} are invalid."
+                )));
+            }
+            while ancestors.last().is_some_and(|x| x.2 < left) {
+                ancestors.pop();
+            }
+            if let Some((_, parent_left, parent_right)) = ancestors.last() {
+                if left <= *parent_left || right >= *parent_right {
+                    return Err(InvalidOperation(format!(
+                        "The bounds of node {node_id:?} overlap with the bounds of another node."
+                    )));
+                }
+            }
+            if depth != ancestors.len() {
+                return Err(InvalidOperation(format!(
+                    "The depth of node {node_id:?} does not match its bounds."
+                )));
+            }
+            let value = value_of(&node_id);
+            tree.add_node(
+                Node::new_in(node_id.clone(), value, P::default()),
+                ancestors.last().map(|x| &x.0),
+            )?;
+            ancestors.push((node_id, left, right));
+        }
+        Ok(tree)
+    }
+
+    /// Create a tree from a closure table.
+    ///
+    /// This method rebuilds a tree from `(ancestor, descendant, depth)` rows such as the ones
+    /// returned by `Tree::to_closure_table`. The parent of a node is its ancestor at a depth of `1`
+    /// and the depth of a node is the largest depth among its rows. The rows pairing a node with
+    /// itself are optional. The rows can be given in any order. Children keep the order in which
+    /// they first appear in the rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - The `(ancestor, descendant, depth)` rows of the tree.
+    /// * `value_of` - The closure used to get the value of the node with the given id.
+    ///
+    /// # Returns
+    ///
+    /// The rebuilt tree. This method returns an error if a node has more than one parent, if the
+    /// parent of a node is missing or if there is more than one root node.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::Tree;
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let rows = vec![(1, 2, 1), (1, 3, 2), (2, 3, 1), (1, 4, 1)];
+    /// let tree: Tree<i32, i32> = Tree::from_closure_table(rows, |_| None)?;
+    /// assert_eq!(tree.get_ancestor_ids(&3)?, vec![2, 1]);
+    /// assert_eq!(tree.get_root_node().unwrap().get_children_ids()?, vec![2, 4]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_closure_table(
+        rows: impl IntoIterator<Item = (Q, Q, usize)>,
+        mut value_of: impl FnMut(&Q) -> Option<T>,
+    ) -> crate::prelude::Result<Self> {
+        let mut node_ids: Vec<Q> = vec![];
+        let mut depths: BTreeMap<Q, usize> = BTreeMap::new();
+        let mut parent_ids: BTreeMap<Q, Q> = BTreeMap::new();
+        for (ancestor_id, descendant_id, depth) in rows {
+            for node_id in [&ancestor_id, &descendant_id] {
+                if !depths.contains_key(node_id) {
+                    depths.insert(node_id.clone(), 0);
+                    node_ids.push(node_id.clone());
+                }
+            }
+            let node_depth = depths.get_mut(&descendant_id).expect("Node depth missing");
+            *node_depth = depth.max(*node_depth);
+            if depth == 1 {
+                if let Some(parent_id) = parent_ids.get(&descendant_id) {
+                    if parent_id != &ancestor_id {
+                        return Err(InvalidOperation(format!(
+                            "Node {descendant_id:?} has more than one parent."
+                        )));
+                    }
+                }
+                parent_ids.insert(descendant_id, ancestor_id);
+            }
+        }
+        node_ids.sort_by_key(|x| depths[x]);
+        let mut tree = Tree::new_in(None, P::default());
+        for node_id in node_ids {
+            let parent_id = parent_ids.get(&node_id);
+            if parent_id.is_none() && depths[&node_id] > 0 {
+                return Err(InvalidOperation(format!(
+                    "The parent of node {node_id:?} is missing."
+                )));
+            }
+            let value = value_of(&node_id);
+            tree.add_node(Node::new_in(node_id, value, P::default()), parent_id)?;
+        }
+        Ok(tree)
+    }
+
+    /// Create a tree from its edges.
+    ///
+    /// This method builds a tree from `(parent, child)` pairs given in any order. The root node is
+    /// the only node that is never a child. The nodes have no values. Children keep the order of
+    /// their edges.
+    ///
+    /// # Arguments
+    ///
+    /// * `edges` - The `(parent, child)` pairs of the tree.
+    ///
+    /// # Returns
+    ///
+    /// The tree. This method returns an error if a node has more than one parent, if more than one
+    /// node is never a child or if some nodes form a cycle. An empty list of edges gives an empty
+    /// tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::Tree;
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let tree: Tree<i32, i32> = Tree::from_edges(vec![(2, 3), (1, 2), (1, 4)])?;
+    /// assert_eq!(tree.get_root_node().unwrap().get_children_ids()?, vec![2, 4]);
+    /// assert_eq!(tree.get_ancestor_ids(&3)?, vec![2, 1]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_edges(edges: impl IntoIterator<Item = (Q, Q)>) -> crate::prelude::Result<Self> {
+        let mut parent_ids: BTreeMap<Q, Q> = BTreeMap::new();
+        let mut node_ids: Vec<Q> = vec![];
+        let mut entries = vec![];
+        for (parent_id, child_id) in edges {
+            if parent_ids
+                .insert(child_id.clone(), parent_id.clone())
+                .is_some()
+            {
+                return Err(InvalidOperation(format!(
+                    "Node {child_id:?} has more than one parent."
+                )));
+            }
+            node_ids.push(parent_id.clone());
+            entries.push((child_id, Some(parent_id), None));
+        }
+        let mut root_ids = node_ids
+            .into_iter()
+            .filter(|x| !parent_ids.contains_key(x))
+            .collect::<BTreeSet<_>>()
+            .into_iter();
+        entries.extend(root_ids.next().map(|x| (x, None, None)));
+        if root_ids.next().is_some() {
+            return Err(RootNodeAlreadyPresent);
+        }
+        Tree::from_parent_map(entries)
+    }
+
+    /// Create a tree from the parent of each node.
+    ///
+    /// This method builds a tree from `(node, parent, value)` entries given in any order. The root
+    /// node is the node without a parent. Children keep the order of their entries.
+    ///
+    /// # Arguments
+    ///
+    /// * `entries` - The id, the id of the parent and the value of each node.
+    ///
+    /// # Returns
+    ///
+    /// The tree. This method returns an error if:
+    /// - A node appears more than once.
+    /// - More than one node has no parent.
+    /// - The parent of a node is not in the entries, which makes the node an orphan.
+    /// - Some nodes form a cycle, so they can not be reached from the root node.
+    ///
+    /// An empty list of entries gives an empty tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::Tree;
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let entries = vec![(3, Some(2), Some("c")), (1, None, Some("a")), (2, Some(1), Some("b"))];
+    /// let tree: Tree<i32, &str> = Tree::from_parent_map(entries)?;
+    /// assert_eq!(tree.get_ancestor_ids(&3)?, vec![2, 1]);
+    /// assert_eq!(tree.get_node_by_id(&2).unwrap().get_value()?, Some("b"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_parent_map(
+        entries: impl IntoIterator<Item = (Q, Option<Q>, Option<T>)>,
+    ) -> crate::prelude::Result<Self> {
+        let mut nodes: BTreeMap<Q, Node<Q, T, P>> = BTreeMap::new();
+        let mut children_ids: BTreeMap<Q, Vec<Q>> = BTreeMap::new();
+        let mut root_id = None;
+        for (node_id, parent_id, value) in entries {
+            if nodes.contains_key(&node_id) {
+                return Err(InvalidOperation(format!(
+                    "Node {node_id:?} appears more than once."
+                )));
+            }
+            match parent_id {
+                Some(parent_id) => children_ids
+                    .entry(parent_id)
+                    .or_default()
+                    .push(node_id.clone()),
+                None => {
+                    if root_id.replace(node_id.clone()).is_some() {
+                        return Err(RootNodeAlreadyPresent);
+                    }
+                }
+            }
+            nodes.insert(node_id.clone(), Node::new_in(node_id, value, P::default()));
+        }
+        if let Some((parent_id, orphan_ids)) =
+            children_ids.iter().find(|(x, _)| !nodes.contains_key(*x))
+        {
+            return Err(InvalidOperation(format!(
+                "Node {:?} is an orphan: its parent {parent_id:?} is not in the tree.",
+                orphan_ids[0]
+            )));
+        }
+        let mut tree = Tree::new_in(None, P::default());
+        let mut stack = root_id.into_iter().collect::<Vec<_>>();
+        while let Some(node_id) = stack.pop() {
+            let node = nodes.remove(&node_id).expect("Error: Node visited twice.");
+            if let Some(child_ids) = children_ids.remove(&node_id) {
+                for child_id in child_ids.iter() {
+                    node.add_child(nodes[child_id].clone())?;
+                }
+                stack.extend(child_ids.into_iter().rev());
+            }
+            tree.nodes.push(node);
+        }
+        if let Some(node_id) = nodes.keys().next() {
+            return Err(InvalidOperation(format!(
+                "Node {node_id:?} is part of a cycle."
+            )));
+        }
+        Ok(tree)
+    }
+
+    fn build_from_outline(
+        outline: &str,
+        style: IndentStyle,
+        mut create_node: impl FnMut(&OutlineEntry) -> crate::prelude::Result<Node<Q, T, P>>,
+    ) -> crate::prelude::Result<Self> {
+        let (name, entries) = parse_outline(outline, style)?;
+        let mut tree = Tree::new_in(name.as_deref(), P::default());
+        let mut ancestor_ids: Vec<Q> = vec![];
+        for entry in entries {
+            let node = create_node(&entry)?;
+            let node_id = node.get_node_id()?;
+            if tree.get_node_by_id(&node_id).is_some() {
+                return Err(ParseError {
+                    line: entry.line,
+                    column: entry.column,
+                    message: format!("Duplicate node id {node_id:?}"),
+                });
+            }
+            ancestor_ids.truncate(entry.depth);
+            tree.add_node(node, ancestor_ids.last())?;
+            ancestor_ids.push(node_id);
+        }
+        Ok(tree)
+    }
+}
+
+impl<Q, P> Tree<Q, NewickValue, P>
+where
+    Q: PartialEq + Eq + Clone + Debug + Hash + Ord,
+    P: NodeStorage,
+{
+    /// Create a tree from a string in the Newick format.
+    ///
+    /// This method parses a phylogenetic tree written in the Newick format. Node labels may be quoted
+    /// with single quotes, unquoted underscores are read as spaces and comments in square brackets are
+    /// ignored. The label and branch length of each node are stored in its value. The nodes are given
+    /// sequential ids in pre-order starting from `0` for the root node.
+    ///
+    /// # Arguments
+    ///
+    /// * `newick` - The tree in the Newick format. It must be terminated by a `;`.
+    ///
+    /// # Returns
+    ///
+    /// The parsed tree. This method returns a `ParseError` holding the line and column of the problem
+    /// if the input is malformed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{NewickValue, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let tree: Tree<u32, NewickValue> = Tree::from_newick("(A:0.1,B:0.2)'Root node';")?;
+    /// let root = tree.get_root_node().unwrap();
+    /// assert_eq!(root.get_value()?.unwrap().get_label(), Some("Root node"));
+    /// assert_eq!(tree.get_nodes().len(), 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_newick(newick: &str) -> crate::prelude::Result<Self>
+    where
+        Q: TryFrom<usize>,
+    {
+        let mut tree = Tree::new_in(None, P::default());
+        let mut node_ids: Vec<Q> = vec![];
+        for (index, (value, parent)) in parse_newick(newick)?.into_iter().enumerate() {
+            let node_id = Q::try_from(index)
+                .map_err(|_| InvalidOperation(format!("The node id {index} is out of range.")))?;
+            tree.add_node(
+                Node::new_in(node_id.clone(), Some(value), P::default()),
+                parent.map(|x| &node_ids[x]),
+            )?;
+            node_ids.push(node_id);
+        }
+        Ok(tree)
+    }
+
+    /// Export the tree in the Newick format.
+    ///
+    /// This method writes the labels and branch lengths of the nodes in the Newick format. Labels that
+    /// contain whitespace, underscores or Newick punctuation are quoted.
+    ///
+    /// # Returns
+    ///
+    /// The tree in the Newick format terminated by a `;`. This method returns an error if the tree
+    /// has no root node.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{NewickValue, Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<u32, NewickValue> = Tree::new(None);
+    /// let root = tree.add_node(Node::new(1, Some(NewickValue::new(Some("Root"), None))), None)?;
+    /// tree.add_node(Node::new(2, Some(NewickValue::new(Some("A"), Some(0.5)))), Some(&root))?;
+    /// tree.add_node(Node::new(3, Some(NewickValue::new(Some("B c"), Some(1.0)))), Some(&root))?;
+    ///
+    /// assert_eq!(tree.to_newick()?, "(A:0.5,'B c':1)Root;");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_newick(&self) -> crate::prelude::Result<String> {
+        let root = self
+            .get_root_node()
+            .ok_or(InvalidOperation(String::from("Tree has no root node")))?;
+        let mut newick = String::new();
+        self.write_newick_sub_tree(&root, &mut newick)?;
+        newick.push(';');
+        Ok(newick)
+    }
+
+    fn write_newick_sub_tree(
+        &self,
+        node: &Node<Q, NewickValue, P>,
+        newick: &mut String,
+    ) -> crate::prelude::Result<()> {
+        let children = node.get_children_ids()?;
+        if !children.is_empty() {
+            newick.push('(');
+            for (index, child_id) in children.iter().enumerate() {
+                if index > 0 {
+                    newick.push(',');
+                }
+                let child = self
+                    .get_node_by_id(child_id)
+                    .ok_or_else(|| Error::node_not_found(child_id))?;
+                self.write_newick_sub_tree(&child, newick)?;
+            }
+            newick.push(')');
+        }
+        if let Some(value) = node.get_value()? {
+            value.write_newick(newick);
+        }
+        Ok(())
+    }
+}
+
+impl<Q, T, P, E> Default for Tree<Q, T, P, E>
+where
+    Q: PartialEq + Eq + Clone,
+    P: NodeStorage,
+{
+    /// Create a new tree with no nodes.
+    fn default() -> Self {
+        Tree {
+            name: None,
+            nodes: Nodes::default(),
+            edges: BTreeMap::new(),
+            attributes: AttributeTables::default(),
+            id_generator: SharedIdGenerator::default(),
+        }
+    }
+}
+
+impl<Q, T, P, E> Display for Tree<Q, T, P, E>
+where
+    Q: PartialEq + Eq + Clone + Debug + Display + Hash + Ord,
+    T: Display,
+    P: NodeStorage,
+{
+    /// Print the tree.
+    ///
+    /// The tree is printed as `render` renders it with `TreeRenderer::new()`. A node without a value
+    /// is printed with an empty label, so the value does not need to implement `Default`. Earlier
+    /// versions printed `T::default()` instead; use `render` with a formatter to keep that output.
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let rendered = self.render(&TreeRenderer::new()).map_err(|_| FmtError)?;
+        f.write_str(&rendered)
+    }
+}
+
+/// A value that displays a tree with the data of its edges.
+///
+/// This struct is returned by `Tree::display_edges`.
+pub struct DisplayEdges<'a, Q, T, P, E>
+where
+    Q: PartialEq + Eq + Clone,
+    P: NodeStorage,
+{
+    tree: &'a Tree<Q, T, P, E>,
+}
+
+impl<Q, T, P, E> Display for DisplayEdges<'_, Q, T, P, E>
+where
+    Q: PartialEq + Eq + Clone + Debug + Display + Hash + Ord,
+    T: Display,
+    P: NodeStorage,
+    E: Display,
+{
+    /// Print the tree with the data of its edges.
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let rendered = self
+            .tree
+            .render_with_edges(&TreeRenderer::new())
+            .map_err(|_| FmtError)?;
+        f.write_str(&rendered)
+    }
+}
+
+/// An iterator over the nodes of a tree in pre-order.
+///
+/// This struct is returned by `Tree::iter`.
+pub struct TreeIter<'a, Q, T, P>
+where
+    Q: PartialEq + Eq + Clone,
+    P: NodeStorage,
+{
+    index: BTreeMap<Q, &'a Node<Q, T, P>>,
+    stack: Vec<&'a Node<Q, T, P>>,
+}
+
+impl<'a, Q, T, P> Iterator for TreeIter<'a, Q, T, P>
+where
+    Q: PartialEq + Eq + Clone + Ord,
+    P: NodeStorage,
+{
+    type Item = &'a Node<Q, T, P>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        let children = node.children_ref();
+        self.stack.extend(
+            children
+                .iter()
+                .rev()
+                .filter_map(|x| self.index.get(x).copied()),
+        );
+        Some(node)
+    }
+}
+
+impl<'a, Q, T, P, E> IntoIterator for &'a Tree<Q, T, P, E>
+where
+    Q: PartialEq + Eq + Clone + Debug + Hash + Ord,
+    P: NodeStorage,
+{
+    type Item = &'a Node<Q, T, P>;
+    type IntoIter = TreeIter<'a, Q, T, P>;
+
+    /// Iterate over the nodes of the tree in pre-order. See `Tree::iter`.
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<Q, T, P, E, K> Index<&K> for Tree<Q, T, P, E>
+where
+    Q: PartialEq + Eq + Clone + Borrow<K>,
+    P: NodeStorage,
+    K: Debug + Eq + ?Sized,
+{
+    type Output = Node<Q, T, P>;
+
+    /// Get the node with the given id.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is not found in the tree. Use `Tree::get_node_ref` to look up a node
+    /// that may be missing.
+    fn index(&self, node_id: &K) -> &Self::Output {
+        self.nodes
+            .get_by_node_id(node_id)
+            .unwrap_or_else(|| panic!("Error: Node {node_id:?} not found in the tree."))
+    }
+}
+
+impl<Q, T, P, E> Extend<(Option<Q>, Node<Q, T, P>)> for Tree<Q, T, P, E>
+where
+    Q: PartialEq + Eq + Clone + Debug + Hash + Ord,
+    P: NodeStorage,
+{
+    /// Add nodes to the tree, each as a child of the node with the given id, or as the root node if
+    /// the id is `None`. See `Tree::add_node`.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the message of the error of `Tree::add_node` if a parent node is not found in
+    /// the tree or if a second root node is added. Use `Tree::add_node` to get an error instead.
+    fn extend<I: IntoIterator<Item = (Option<Q>, Node<Q, T, P>)>>(&mut self, iter: I) {
+        for (parent_id, node) in iter {
+            if let Err(error) = self.add_node(node, parent_id.as_ref()) {
+                panic!("{error}");
+            }
+        }
+    }
+}
+
+impl<Q, T, P> FromIterator<(Q, Option<Q>, Option<T>)> for Tree<Q, T, P>
+where
+    Q: PartialEq + Eq + Clone + Debug + Hash + Ord,
+    P: NodeStorage,
+{
+    /// Create a tree from `(node, parent, value)` entries. See `Tree::from_parent_map`.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the message of the error of `Tree::from_parent_map` if the entries do not
+    /// describe a tree. Use `Tree::from_parent_map` to get an error instead.
+    fn from_iter<I: IntoIterator<Item = (Q, Option<Q>, Option<T>)>>(iter: I) -> Self {
+        Tree::from_parent_map(iter).unwrap_or_else(|error| panic!("{error}"))
+    }
+}
+
+impl<Q, T, P, E> Clone for Tree<Q, T, P, E>
+where
+    Q: PartialEq + Eq + Clone,
+    P: NodeStorage,
+    E: Clone,
+{
+    /// Clone the tree.
+    ///
+    /// The clone shares its nodes with this tree. See the `Cloning` section of `Tree`.
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            nodes: self.nodes.clone(),
+            edges: self.edges.clone(),
+            attributes: self.attributes.clone(),
+            id_generator: self.id_generator.clone(),
+        }
+    }
+}
+
+impl<Q, T, P, E> Drop for Tree<Q, T, P, E>
+where
+    Q: PartialEq + Eq + Clone,
+    P: NodeStorage,
+{
+    /// Drop the tree.
+    #[doc(hidden)]
+    fn drop(&mut self) {
+        self.nodes.clear();
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Q, T, P, E> Serialize for Tree<Q, T, P, E>
+where
+    Q: PartialEq + Eq + Clone + Serialize,
+    T: Serialize,
+    E: Serialize,
+    P: NodeStorage,
+{
+    /// Serialize the tree.
+    ///
+    /// The data of the edges is serialized as a list of `(node id, edge)` pairs, and only if at
+    /// least one edge has data. The attributes of the nodes are not serialized, see
+    /// `with_attributes`.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.serialize_with_table::<S, ()>(serializer, None)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Q, T, P, E> Tree<Q, T, P, E>
+where
+    Q: PartialEq + Eq + Clone + Serialize,
+    T: Serialize,
+    E: Serialize,
+    P: NodeStorage,
+{
+    /// Serialize the tree, and the attributes of one type if `attributes` is given.
+    fn serialize_with_table<S, A>(
+        &self,
+        serializer: S,
+        attributes: Option<Option<&NodeAttributes<Q, A>>>,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+        A: Serialize,
+    {
+        let len = 1
+            + usize::from(self.name.is_some())
+            + usize::from(!self.edges.is_empty())
+            + usize::from(attributes.is_some());
+        let mut state = serializer.serialize_struct("Tree", len)?;
+        if self.name.is_some() {
+            state.serialize_field("name", &self.name)?;
+        }
+        state.serialize_field("nodes", &self.nodes)?;
+        if !self.edges.is_empty() {
+            state.serialize_field("edges", &self.edges.iter().collect::<Vec<_>>())?;
+        }
+        match attributes {
+            Some(Some(attributes)) => state.serialize_field("attributes", attributes)?,
+            Some(None) => state.serialize_field("attributes", &[] as &[(Q, A)])?,
+            None => {}
+        }
+        state.end()
+    }
+
+    /// Serialize the tree with the attributes of a given type.
+    ///
+    /// Serializing a tree leaves its attributes out. This method opts in to saving the attributes
+    /// of type `A`: the returned value serializes as the tree with an extra `attributes` field that
+    /// holds the table of `A` as a list of `(node id, attribute)` pairs. The tree is read back with
+    /// `deserialize_with_attributes`. Deserializing it as a plain tree ignores the attributes.
+    ///
+    /// # Returns
+    ///
+    /// A value that serializes the tree and its attributes of type `A`. If no attribute of this
+    /// type was set, the list of attributes is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    /// struct Collapsed(bool);
+    ///
+    /// let mut tree: Tree<i32, i32> = Tree::new(None);
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// tree.set_attribute(&node_1, Collapsed(true))?;
+    ///
+    /// let serialized = serde_json::to_string(&tree.with_attributes::<Collapsed>()).unwrap();
+    /// let mut deserializer = serde_json::Deserializer::from_str(&serialized);
+    /// let deserialized: Tree<i32, i32> =
+    ///     Tree::deserialize_with_attributes::<Collapsed, _>(&mut deserializer).unwrap();
+    /// assert_eq!(
+    ///     deserialized.attributes::<Collapsed>().unwrap().get(&node_1),
+    ///     Some(&Collapsed(true))
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_attributes<A>(&self) -> WithAttributes<'_, Q, T, P, E, A>
+    where
+        Q: 'static,
+        A: Serialize + 'static,
+    {
+        WithAttributes {
+            tree: self,
+            attributes: self.attributes.get(),
+        }
+    }
+}
+
+/// A value that serializes a tree with the attributes of one type.
+///
+/// This struct is returned by `Tree::with_attributes`.
+#[cfg(feature = "serde")]
+pub struct WithAttributes<'a, Q, T, P, E, A>
+where
+    Q: PartialEq + Eq + Clone,
+    P: NodeStorage,
+{
+    tree: &'a Tree<Q, T, P, E>,
+    attributes: Option<&'a NodeAttributes<Q, A>>,
+}
+
+#[cfg(feature = "serde")]
+impl<Q, T, P, E, A> Serialize for WithAttributes<'_, Q, T, P, E, A>
+where
+    Q: PartialEq + Eq + Clone + Serialize,
+    T: Serialize,
+    E: Serialize,
+    P: NodeStorage,
+    A: Serialize,
+{
+    /// Serialize the tree with an `attributes` field.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.tree
+            .serialize_with_table(serializer, Some(self.attributes))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Q, T, P, E> Deserialize<'de> for Tree<Q, T, P, E>
+where
+    Q: PartialEq + Eq + Clone + Ord + Deserialize<'de>,
+    T: Deserialize<'de>,
+    E: Deserialize<'de>,
+    P: NodeStorage,
+{
+    /// Deserialize the tree.
+    ///
+    /// An `attributes` field written by `Tree::with_attributes` is ignored, see
+    /// `deserialize_with_attributes`.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let serialized_tree: SerializedTree<Q, T, P, E, IgnoredAny> =
+            Deserialize::deserialize(deserializer)?;
+        Ok(Tree {
+            name: serialized_tree.name,
+            nodes: serialized_tree.nodes,
+            edges: serialized_tree.edges.into_iter().collect(),
+            attributes: AttributeTables::default(),
+            id_generator: SharedIdGenerator::default(),
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Q, T, P, E> Tree<Q, T, P, E>
+where
+    Q: PartialEq + Eq + Clone + Debug + Hash + Ord + Send + Sync + 'static,
+    P: NodeStorage,
+{
+    /// Deserialize a tree with the attributes of a given type.
+    ///
+    /// This method reads a tree written by `with_attributes` and attaches its attributes of type
+    /// `A`. A tree serialized without attributes is read with no attributes of this type.
+    ///
+    /// # Arguments
+    ///
+    /// * `deserializer` - The deserializer to read the tree from.
+    ///
+    /// # Returns
+    ///
+    /// The deserialized tree, or an error if the data is invalid or an attribute belongs to a node
+    /// that is not in the tree.
+    pub fn deserialize_with_attributes<'de, A, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        Q: Deserialize<'de>,
+        T: Deserialize<'de>,
+        E: Deserialize<'de>,
+        A: Clone + Send + Sync + Deserialize<'de> + 'static,
+        D: serde::Deserializer<'de>,
+    {
+        let serialized_tree: SerializedTree<Q, T, P, E, NodeAttributes<Q, A>> =
+            Deserialize::deserialize(deserializer)?;
+        let mut tree = Tree {
+            name: serialized_tree.name,
+            nodes: serialized_tree.nodes,
+            edges: serialized_tree.edges.into_iter().collect(),
+            attributes: AttributeTables::default(),
+            id_generator: SharedIdGenerator::default(),
+        };
+        if let Some(attributes) = serialized_tree.attributes {
+            tree.insert_attributes(attributes)
+                .map_err(serde::de::Error::custom)?;
+        }
+        Ok(tree)
+    }
+}
+
+/// The fields of a serialized tree, with its attributes if they were saved.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(bound(
+    deserialize = "Nodes<Q, T, P>: Deserialize<'de>, Q: Deserialize<'de>, E: Deserialize<'de>, A: Deserialize<'de>"
+))]
+struct SerializedTree<Q, T, P, E, A>
+where
+    Q: PartialEq + Eq + Clone,
+    P: NodeStorage,
+{
+    name: Option<String>,
+    nodes: Nodes<Q, T, P>,
+    #[serde(default = "Vec::new")]
+    edges: Vec<(Q, E)>,
+    #[serde(default = "Option::default")]
+    attributes: Option<A>,
+}
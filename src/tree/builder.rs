@@ -0,0 +1,127 @@
+use crate::error::Error::InvalidOperation;
+use crate::lib::*;
+use crate::node::{DefaultStorage, NodeStorage};
+use crate::tree::Tree;
+
+/// A builder that creates a tree by describing its nodes.
+///
+/// The root node is set with `node` and its descendants are added with `child`, whose closure
+/// describes the children of the new node. The nodes are checked when the tree is built, see
+/// `Tree::from_parent_map`.
+///
+/// # Type Parameters
+///
+/// * `Q` - The type of the node id.
+/// * `T` - The type of the node value.
+/// * `P` - The storage used to share the data of the nodes. See `NodeStorage`.
+///
+/// # Example
+///
+/// ```rust
+/// # use tree_ds::prelude::{TraversalStrategy, Tree, TreeBuilder};
+///
+/// # fn main() -> tree_ds::prelude::Result<()> {
+/// let tree: Tree<i32, &str> = TreeBuilder::new(Some("Sample Tree"))
+///     .node(1, "root")
+///     .child(2, "a", |b| b.child(4, "x", |b| b))
+///     .child(3, "b", |b| b)
+///     .build()?;
+///
+/// assert_eq!(tree.get_name(), Some("Sample Tree"));
+/// assert_eq!(tree.traverse(&1, TraversalStrategy::PreOrder)?, vec![1, 2, 4, 3]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TreeBuilder<Q, T, P = DefaultStorage> {
+    name: Option<String>,
+    entries: Vec<(Q, Option<Q>, Option<T>)>,
+    parent_id: Option<Q>,
+    orphan_id: Option<Q>,
+    _storage: P,
+}
+
+impl<Q, T> TreeBuilder<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Debug + Hash + Ord,
+{
+    /// Create a new builder for a tree with the given name.
+    pub fn new(tree_name: Option<&str>) -> Self {
+        TreeBuilder::new_in(tree_name, DefaultStorage::default())
+    }
+}
+
+impl<Q, T, P> TreeBuilder<Q, T, P>
+where
+    Q: PartialEq + Eq + Clone + Debug + Hash + Ord,
+    P: NodeStorage,
+{
+    /// Create a new builder for a tree with the given name and storage.
+    pub fn new_in(tree_name: Option<&str>, storage: P) -> Self {
+        Self {
+            name: tree_name.map(|x| x.to_string()),
+            entries: vec![],
+            parent_id: None,
+            orphan_id: None,
+            _storage: storage,
+        }
+    }
+
+    /// Set the root node of the tree.
+    ///
+    /// The children added after this call at the top level of the builder are children of the
+    /// root node.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the root node.
+    /// * `value` - The value of the root node.
+    pub fn node(mut self, node_id: Q, value: T) -> Self {
+        self.entries.push((node_id.clone(), None, Some(value)));
+        self.parent_id = Some(node_id);
+        self
+    }
+
+    /// Add a child to the current node.
+    ///
+    /// The current node is the root node, or the node whose closure is running. The closure
+    /// describes the children of the new node and returns the builder, so a node without children
+    /// is added with `|b| b`.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the new node.
+    /// * `value` - The value of the new node.
+    /// * `children` - The closure that adds the children of the new node.
+    pub fn child<F>(mut self, node_id: Q, value: T, children: F) -> Self
+    where
+        F: FnOnce(Self) -> Self,
+    {
+        if self.parent_id.is_none() && self.orphan_id.is_none() {
+            self.orphan_id = Some(node_id.clone());
+        }
+        self.entries
+            .push((node_id.clone(), self.parent_id.clone(), Some(value)));
+        let parent_id = self.parent_id.replace(node_id);
+        let mut builder = children(self);
+        builder.parent_id = parent_id;
+        builder
+    }
+
+    /// Build the tree.
+    ///
+    /// # Returns
+    ///
+    /// The tree. This method returns an error if a child was added before the root node, if the
+    /// root node was set more than once or if a node id was used more than once.
+    pub fn build(self) -> crate::prelude::Result<Tree<Q, T, P>> {
+        if let Some(node_id) = self.orphan_id {
+            return Err(InvalidOperation(format!(
+                "Node {node_id:?} was added as a child before the root node."
+            )));
+        }
+        let mut tree = Tree::from_parent_map(self.entries)?;
+        tree.rename(self.name.as_deref());
+        Ok(tree)
+    }
+}
@@ -0,0 +1,422 @@
+use crate::error::Error;
+use crate::error::Error::InvalidOperation;
+use crate::lib::*;
+use crate::node::{DefaultStorage, Node, NodeStorage};
+use crate::tree::{NodeRemovalStrategy, Tree};
+
+/// A cursor that points at a node of a tree and moves between related nodes.
+///
+/// A cursor is created with `Tree::cursor`. Each move returns `true` when the cursor moved, and
+/// returns `false` and leaves the cursor in place when the node has no such relative. The siblings
+/// of a node are in the order of the children of its parent. The cursor indexes the nodes of the
+/// tree when it is created, which takes `O(n log n)` time for a tree of `n` nodes, and each move
+/// then takes constant time. The clones of a cursor share its index.
+///
+/// # Type Parameters
+///
+/// * `Q` - The type of the node id.
+/// * `T` - The type of the node value.
+/// * `P` - The storage used to share the data of the nodes. See `NodeStorage`.
+/// * `E` - The type of the edge data.
+///
+/// # Example
+///
+/// ```rust
+/// # use tree_ds::prelude::{Node, Tree};
+///
+/// # fn main() -> tree_ds::prelude::Result<()> {
+/// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+/// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+/// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+/// tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+/// tree.add_node(Node::new(4, Some(5)), Some(&node_2))?;
+///
+/// let mut cursor = tree.cursor(&node_1)?;
+/// assert!(cursor.first_child());
+/// assert!(cursor.next_sibling());
+/// assert_eq!(cursor.node().get_node_id()?, 3);
+/// assert!(!cursor.next_sibling());
+/// assert!(cursor.prev_sibling());
+/// assert!(cursor.last_child());
+/// assert_eq!(cursor.node().get_node_id()?, 4);
+/// assert!(cursor.parent() && cursor.parent());
+/// assert!(!cursor.parent());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TreeCursor<'a, Q, T, P = DefaultStorage, E = ()>
+where
+    Q: PartialEq + Eq + Clone,
+    P: NodeStorage,
+{
+    index: Rc<CursorIndex<&'a Node<Q, T, P>>>,
+    current: usize,
+    tree: PhantomData<&'a Tree<Q, T, P, E>>,
+}
+
+impl<'a, Q, T, P, E> TreeCursor<'a, Q, T, P, E>
+where
+    Q: PartialEq + Eq + Clone + Debug + Hash + Ord,
+    P: NodeStorage,
+{
+    /// Create a cursor that points at the node of the tree with the given id.
+    pub(crate) fn new(tree: &'a Tree<Q, T, P, E>, node_id: &Q) -> crate::prelude::Result<Self> {
+        let (index, current) = CursorIndex::new(tree, node_id, |x| x)?;
+        Ok(Self {
+            index: Rc::new(index),
+            current,
+            tree: PhantomData,
+        })
+    }
+
+    /// Get the node the cursor points at.
+    pub fn node(&self) -> &'a Node<Q, T, P> {
+        self.index.nodes[self.current]
+    }
+
+    /// Move the cursor to the parent of the node.
+    pub fn parent(&mut self) -> bool {
+        self.move_to(self.index.parent_of(self.current))
+    }
+
+    /// Move the cursor to the first child of the node.
+    pub fn first_child(&mut self) -> bool {
+        self.move_to(self.index.child_of(self.current, false))
+    }
+
+    /// Move the cursor to the last child of the node.
+    pub fn last_child(&mut self) -> bool {
+        self.move_to(self.index.child_of(self.current, true))
+    }
+
+    /// Move the cursor to the sibling that follows the node.
+    pub fn next_sibling(&mut self) -> bool {
+        self.move_to(self.index.sibling_of(self.current, true))
+    }
+
+    /// Move the cursor to the sibling that precedes the node.
+    pub fn prev_sibling(&mut self) -> bool {
+        self.move_to(self.index.sibling_of(self.current, false))
+    }
+
+    fn move_to(&mut self, node: Option<usize>) -> bool {
+        node.map(|x| self.current = x).is_some()
+    }
+}
+
+impl<Q, T, P, E> Clone for TreeCursor<'_, Q, T, P, E>
+where
+    Q: PartialEq + Eq + Clone,
+    P: NodeStorage,
+{
+    fn clone(&self) -> Self {
+        Self {
+            index: self.index.clone(),
+            current: self.current,
+            tree: PhantomData,
+        }
+    }
+}
+
+/// A cursor that points at a node of a tree and edits the tree around it.
+///
+/// A mutable cursor is created with `Tree::cursor_mut`. It moves like a `TreeCursor`, and can also
+/// add nodes next to the node, remove the node and replace its value. The cursor borrows the tree
+/// mutably, so the tree can not be used in any other way while the cursor exists, and its index
+/// stays in sync with the tree: adding or removing nodes updates the index in place.
+///
+/// # Type Parameters
+///
+/// * `Q` - The type of the node id.
+/// * `T` - The type of the node value.
+/// * `P` - The storage used to share the data of the nodes. See `NodeStorage`.
+/// * `E` - The type of the edge data.
+///
+/// # Example
+///
+/// ```rust
+/// # use tree_ds::prelude::{Node, TraversalStrategy, Tree};
+///
+/// # fn main() -> tree_ds::prelude::Result<()> {
+/// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+/// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+/// tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+///
+/// let mut cursor = tree.cursor_mut(&node_1)?;
+/// cursor.insert_child(Node::new(4, Some(5)))?;
+/// cursor.first_child();
+/// cursor.insert_sibling(Node::new(3, Some(4)))?;
+/// assert_eq!(cursor.replace_value(Some(30))?, Some(3));
+/// cursor.remove()?;
+/// assert_eq!(cursor.node().get_node_id()?, 3);
+///
+/// assert_eq!(tree.traverse(&node_1, TraversalStrategy::PreOrder)?, vec![1, 3, 4]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TreeCursorMut<'a, Q, T, P = DefaultStorage, E = ()>
+where
+    Q: PartialEq + Eq + Clone,
+    P: NodeStorage,
+{
+    tree: &'a mut Tree<Q, T, P, E>,
+    index: CursorIndex<Node<Q, T, P>>,
+    current: usize,
+}
+
+impl<'a, Q, T, P, E> TreeCursorMut<'a, Q, T, P, E>
+where
+    Q: PartialEq + Eq + Clone + Debug + Hash + Ord,
+    P: NodeStorage,
+{
+    /// Create a cursor that points at the node of the tree with the given id.
+    pub(crate) fn new(tree: &'a mut Tree<Q, T, P, E>, node_id: &Q) -> crate::prelude::Result<Self> {
+        let (index, current) = CursorIndex::new(tree, node_id, Node::clone)?;
+        Ok(Self {
+            tree,
+            index,
+            current,
+        })
+    }
+
+    /// Get the node the cursor points at.
+    pub fn node(&self) -> &Node<Q, T, P> {
+        &self.index.nodes[self.current]
+    }
+
+    /// Move the cursor to the parent of the node.
+    pub fn parent(&mut self) -> bool {
+        self.move_to(self.index.parent_of(self.current))
+    }
+
+    /// Move the cursor to the first child of the node.
+    pub fn first_child(&mut self) -> bool {
+        self.move_to(self.index.child_of(self.current, false))
+    }
+
+    /// Move the cursor to the last child of the node.
+    pub fn last_child(&mut self) -> bool {
+        self.move_to(self.index.child_of(self.current, true))
+    }
+
+    /// Move the cursor to the sibling that follows the node.
+    pub fn next_sibling(&mut self) -> bool {
+        self.move_to(self.index.sibling_of(self.current, true))
+    }
+
+    /// Move the cursor to the sibling that precedes the node.
+    pub fn prev_sibling(&mut self) -> bool {
+        self.move_to(self.index.sibling_of(self.current, false))
+    }
+
+    /// Add a node as the last child of the node.
+    ///
+    /// The cursor does not move.
+    ///
+    /// # Arguments
+    ///
+    /// * `node` - The node to add.
+    ///
+    /// # Returns
+    ///
+    /// The id of the added node.
+    pub fn insert_child(&mut self, node: Node<Q, T, P>) -> crate::prelude::Result<Q> {
+        let node_id = self.node().get_node_id()?;
+        let position = self.index.children[self.current].len();
+        let added_id = self.tree.add_node(node.clone(), Some(&node_id))?;
+        self.index.insert(node, self.current, position);
+        Ok(added_id)
+    }
+
+    /// Add a node as the sibling that follows the node.
+    ///
+    /// The cursor does not move.
+    ///
+    /// # Arguments
+    ///
+    /// * `node` - The node to add.
+    ///
+    /// # Returns
+    ///
+    /// The id of the added node. This method returns an error if the cursor points at the root
+    /// node, which has no siblings.
+    pub fn insert_sibling(&mut self, node: Node<Q, T, P>) -> crate::prelude::Result<Q> {
+        let parent = self
+            .index
+            .parent_of(self.current)
+            .ok_or(InvalidOperation(String::from(
+                "Cannot add a sibling to the root node",
+            )))?;
+        let parent_id = self.index.nodes[parent].get_node_id()?;
+        let position = self.index.positions[self.current] + 1;
+        let added_id = self.tree.insert_node(node.clone(), &parent_id, position)?;
+        self.index.insert(node, parent, position);
+        Ok(added_id)
+    }
+
+    /// Remove the node and its descendants from the tree.
+    ///
+    /// The cursor moves to the sibling that followed the node, or to the sibling that preceded it,
+    /// or to its parent if the node had no siblings. The node is unlinked from the index of the
+    /// cursor, and the numbers of the node and its descendants are left unused.
+    ///
+    /// # Returns
+    ///
+    /// The removed node, which no longer has a parent or children. This method returns an error
+    /// if the cursor points at the root node, since the cursor would be left without a node.
+    pub fn remove(&mut self) -> crate::prelude::Result<Node<Q, T, P>> {
+        let next = self
+            .index
+            .sibling_of(self.current, true)
+            .or_else(|| self.index.sibling_of(self.current, false))
+            .or_else(|| self.index.parent_of(self.current))
+            .ok_or(InvalidOperation(String::from(
+                "Cannot remove the root node with a cursor",
+            )))?;
+        let node = self.node().clone();
+        self.tree.remove_node::<Q>(
+            &node.get_node_id()?,
+            NodeRemovalStrategy::RemoveNodeAndChildren,
+        )?;
+        self.index.remove(self.current);
+        self.current = next;
+        Ok(node)
+    }
+
+    /// Replace the value of the node.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The new value of the node.
+    ///
+    /// # Returns
+    ///
+    /// The previous value of the node.
+    pub fn replace_value(&mut self, value: Option<T>) -> crate::prelude::Result<Option<T>> {
+        let mut value = value;
+        self.node().update_value(|x| mem::swap(x, &mut value))?;
+        Ok(value)
+    }
+
+    fn move_to(&mut self, node: Option<usize>) -> bool {
+        node.map(|x| self.current = x).is_some()
+    }
+}
+
+/// The nodes of a tree, numbered so that a cursor moves between them in constant time.
+///
+/// The nodes are numbered in the order of the tree, and each number maps to the numbers of the
+/// parent and of the children of the node and to the position of the node among its siblings.
+#[derive(Debug)]
+struct CursorIndex<N> {
+    nodes: Vec<N>,
+    parents: Vec<Option<usize>>,
+    children: Vec<Vec<usize>>,
+    positions: Vec<usize>,
+}
+
+impl<N> CursorIndex<N> {
+    /// Index the nodes of a tree, keeping each node as the handle given by `handle`.
+    ///
+    /// # Returns
+    ///
+    /// The index and the number of the node with the given id, or an error if the node is not
+    /// found in the tree.
+    fn new<'a, Q, T, P, E>(
+        tree: &'a Tree<Q, T, P, E>,
+        node_id: &Q,
+        handle: impl Fn(&'a Node<Q, T, P>) -> N,
+    ) -> crate::prelude::Result<(Self, usize)>
+    where
+        Q: PartialEq + Eq + Clone + Debug + Hash + Ord,
+        P: NodeStorage,
+    {
+        let mut numbers = BTreeMap::new();
+        for (number, node) in tree.get_nodes().iter().enumerate() {
+            numbers.entry(node.get_node_id()?).or_insert(number);
+        }
+        let current = *numbers
+            .get(node_id)
+            .ok_or_else(|| Error::node_not_found(node_id))?;
+        let children = tree
+            .get_nodes()
+            .iter()
+            .map(|x| {
+                x.children_ref()
+                    .iter()
+                    .filter_map(|x| numbers.get(x).copied())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let mut parents = vec![None; children.len()];
+        let mut positions = vec![0; children.len()];
+        for (parent, children) in children.iter().enumerate() {
+            for (position, child) in children.iter().enumerate() {
+                parents[*child] = Some(parent);
+                positions[*child] = position;
+            }
+        }
+        let index = Self {
+            nodes: tree.get_nodes().iter().map(handle).collect(),
+            parents,
+            children,
+            positions,
+        };
+        Ok((index, current))
+    }
+
+    /// Get the parent of a node.
+    fn parent_of(&self, node: usize) -> Option<usize> {
+        self.parents[node]
+    }
+
+    /// Get the first or the last child of a node.
+    fn child_of(&self, node: usize, last: bool) -> Option<usize> {
+        let children = &self.children[node];
+        if last {
+            children.last().copied()
+        } else {
+            children.first().copied()
+        }
+    }
+
+    /// Get the sibling that follows or precedes a node.
+    fn sibling_of(&self, node: usize, next: bool) -> Option<usize> {
+        let position = self.positions[node];
+        let position = if next {
+            position + 1
+        } else {
+            position.checked_sub(1)?
+        };
+        self.children[self.parent_of(node)?].get(position).copied()
+    }
+
+    /// Add a node at the given position among the children of a node.
+    fn insert(&mut self, node: N, parent: usize, position: usize) {
+        let number = self.nodes.len();
+        self.nodes.push(node);
+        self.parents.push(Some(parent));
+        self.children.push(vec![]);
+        self.positions.push(position);
+        self.children[parent].insert(position, number);
+        for sibling in self.children[parent][position + 1..].iter() {
+            self.positions[*sibling] += 1;
+        }
+    }
+
+    /// Remove a node from the children of its parent.
+    ///
+    /// The node and its descendants keep their numbers, which are no longer reachable from the
+    /// other nodes.
+    fn remove(&mut self, node: usize) {
+        if let Some(parent) = self.parents[node].take() {
+            let position = self.positions[node];
+            self.children[parent].remove(position);
+            for sibling in self.children[parent][position..].iter() {
+                self.positions[*sibling] -= 1;
+            }
+        }
+    }
+}
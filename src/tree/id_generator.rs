@@ -0,0 +1,265 @@
+use crate::lib::*;
+#[cfg(not(feature = "no_std"))]
+use std::collections::hash_map::RandomState;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::time::SystemTime;
+
+/// A source of node ids for a tree.
+///
+/// A tree that owns an id generator can create nodes without being given their ids, see
+/// `Tree::set_id_generator` and `Tree::add_value`. Each tree owns its generator, so the ids of one
+/// tree do not depend on the nodes added to other trees.
+///
+/// # Type Parameters
+///
+/// * `Q` - The type of the generated ids.
+///
+/// # Example
+///
+/// ```rust
+/// # use tree_ds::prelude::{IdGenerator, Tree};
+///
+/// # fn main() -> tree_ds::prelude::Result<()> {
+/// struct Labels(u32);
+///
+/// impl IdGenerator<String> for Labels {
+///     fn next_id(&mut self) -> String {
+///         self.0 += 1;
+///         format!("node-{}", self.0)
+///     }
+/// }
+///
+/// let mut tree: Tree<String, i32> = Tree::new(None);
+/// tree.set_id_generator(Labels(0));
+/// let root = tree.add_value(Some(1), None)?;
+/// assert_eq!(root, "node-1");
+/// # Ok(())
+/// # }
+/// ```
+pub trait IdGenerator<Q>: Send + Sync {
+    /// Generate the next id.
+    fn next_id(&mut self) -> Q;
+}
+
+/// An id generator that counts up from a start value by a fixed step.
+///
+/// The default generator starts at `1` and counts up by `1`.
+///
+/// # Example
+///
+/// ```rust
+/// # use tree_ds::prelude::{IdGenerator, SequentialIdGenerator};
+///
+/// let mut generator = SequentialIdGenerator::new(10u32, 5);
+/// assert_eq!(generator.next_id(), 10);
+/// assert_eq!(generator.next_id(), 15);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SequentialIdGenerator<Q> {
+    next: Q,
+    step: Q,
+}
+
+impl<Q> SequentialIdGenerator<Q> {
+    /// Create a generator whose first id is `start` and whose ids are `step` apart.
+    pub fn new(start: Q, step: Q) -> Self {
+        Self { next: start, step }
+    }
+}
+
+impl<Q> Default for SequentialIdGenerator<Q>
+where
+    Q: From<u8>,
+{
+    fn default() -> Self {
+        Self::new(Q::from(1), Q::from(1))
+    }
+}
+
+impl<Q> IdGenerator<Q> for SequentialIdGenerator<Q>
+where
+    Q: Clone + Add<Output = Q> + Send + Sync,
+{
+    fn next_id(&mut self) -> Q {
+        let id = self.next.clone();
+        self.next = id.clone() + self.step.clone();
+        id
+    }
+}
+
+/// An id generator that produces a reproducible sequence of pseudo-random ids from a seed.
+///
+/// Two generators created with the same seed produce the same ids, which makes the ids of a tree
+/// predictable in tests. The ids are not suitable for cryptographic use.
+///
+/// # Example
+///
+/// ```rust
+/// # use tree_ds::prelude::{IdGenerator, SeededIdGenerator};
+///
+/// let mut generator = SeededIdGenerator::new(42);
+/// let mut other = SeededIdGenerator::new(42);
+/// let id: u64 = generator.next_id();
+/// assert_eq!(id, other.next_id());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SeededIdGenerator {
+    state: u64,
+}
+
+impl SeededIdGenerator {
+    /// Create a generator from a seed.
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Get the next value of the sequence.
+    fn next_value(&mut self) -> u64 {
+        // The SplitMix64 generator.
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut value = self.state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        value ^ (value >> 31)
+    }
+}
+
+impl<Q> IdGenerator<Q> for SeededIdGenerator
+where
+    Q: From<u64>,
+{
+    fn next_id(&mut self) -> Q {
+        Q::from(self.next_value())
+    }
+}
+
+/// An id generator that produces pseudo-random ids from a seed chosen when it is created.
+///
+/// This is a `SeededIdGenerator` whose seed is different for every generator.
+///
+/// This is not available when the `no_std` feature is enabled.
+#[cfg(not(feature = "no_std"))]
+#[derive(Clone, Debug)]
+pub struct RandomIdGenerator(SeededIdGenerator);
+
+#[cfg(not(feature = "no_std"))]
+impl RandomIdGenerator {
+    /// Create a generator with a random seed.
+    pub fn new() -> Self {
+        Self(SeededIdGenerator::new(
+            RandomState::new().build_hasher().finish(),
+        ))
+    }
+}
+
+#[cfg(not(feature = "no_std"))]
+impl Default for RandomIdGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(not(feature = "no_std"))]
+impl<Q> IdGenerator<Q> for RandomIdGenerator
+where
+    Q: From<u64>,
+{
+    fn next_id(&mut self) -> Q {
+        Q::from(self.0.next_value())
+    }
+}
+
+/// An id generator that uses the number of nanoseconds since the Unix epoch as ids.
+///
+/// The ids are strictly increasing: an id that would not be greater than the previous one is
+/// replaced by the previous id plus one.
+///
+/// This is not available when the `no_std` feature is enabled.
+#[cfg(not(feature = "no_std"))]
+#[derive(Clone, Debug, Default)]
+pub struct EpochIdGenerator {
+    last: u64,
+}
+
+#[cfg(not(feature = "no_std"))]
+impl EpochIdGenerator {
+    /// Create a generator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(not(feature = "no_std"))]
+impl<Q> IdGenerator<Q> for EpochIdGenerator
+where
+    Q: From<u64>,
+{
+    fn next_id(&mut self) -> Q {
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|x| x.as_nanos() as u64)
+            .unwrap_or_default();
+        self.last = now.max(self.last + 1);
+        Q::from(self.last)
+    }
+}
+
+/// The id generator of a tree.
+///
+/// The generator is shared by the clones and the subtrees of a tree, so the ids they generate do
+/// not collide when their nodes are added back to the tree. Like attributes, the generator is not
+/// part of the identity of a tree.
+pub(crate) struct SharedIdGenerator<Q> {
+    generator: Option<Arc<Mutex<Box<dyn IdGenerator<Q>>>>>,
+}
+
+impl<Q> SharedIdGenerator<Q> {
+    /// Replace the generator.
+    pub(crate) fn set<G>(&mut self, generator: G)
+    where
+        G: IdGenerator<Q> + 'static,
+    {
+        self.generator = Some(Arc::new(Mutex::new(Box::new(generator))));
+    }
+
+    /// Generate the next id, or `None` if there is no generator.
+    pub(crate) fn next_id(&self) -> Option<Q> {
+        self.generator.as_ref().map(|x| x.lock().next_id())
+    }
+}
+
+impl<Q> Clone for SharedIdGenerator<Q> {
+    fn clone(&self) -> Self {
+        Self {
+            generator: self.generator.clone(),
+        }
+    }
+}
+
+impl<Q> Default for SharedIdGenerator<Q> {
+    fn default() -> Self {
+        Self { generator: None }
+    }
+}
+
+impl<Q> Debug for SharedIdGenerator<Q> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("SharedIdGenerator")
+            .field("is_set", &self.generator.is_some())
+            .finish()
+    }
+}
+
+impl<Q> PartialEq for SharedIdGenerator<Q> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<Q> Eq for SharedIdGenerator<Q> {}
+
+impl<Q> Hash for SharedIdGenerator<Q> {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
+}
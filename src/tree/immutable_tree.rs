@@ -0,0 +1,601 @@
+use crate::error::Error;
+use crate::error::Error::{InvalidOperation, RootNodeAlreadyPresent};
+use crate::lib::*;
+use crate::node::{Node, NodeStorage};
+use crate::tree::persistent_map::PersistentMap;
+use crate::tree::{traverse_subtree, NodeRemovalStrategy, TraversalStrategy, Tree, TreeRenderer};
+
+/// A persistent tree whose updates return a new tree.
+///
+/// This struct is an immutable tree. Inserting, removing, moving or changing the value of a node
+/// returns a new tree and leaves the original untouched, so every version of a tree can be kept
+/// around. The new tree shares all the nodes that did not change with the original, which makes
+/// each update O(log n) in the number of nodes plus the number of children of the nodes involved.
+///
+/// Cloning an immutable tree is O(1) and the clone is fully independent: no update made through one
+/// tree is ever visible through another. The tree is `Send` and `Sync` as long as the node ids and
+/// values are.
+///
+/// The nodes are kept ordered by id, so ids must implement `Ord` and must be unique in the tree.
+///
+/// # Type Parameters
+///
+/// * `Q` - The type of the node id.
+/// * `T` - The type of the node value.
+///
+/// # Example
+///
+/// ```rust
+/// # use tree_ds::prelude::ImmutableTree;
+///
+/// # fn main() -> tree_ds::prelude::Result<()> {
+/// let version_1: ImmutableTree<i32, i32> = ImmutableTree::new(Some("Document"))
+///     .insert(1, Some(2), None)?
+///     .insert(2, Some(3), Some(&1))?;
+/// let version_2 = version_1.set_value(&2, Some(4))?;
+///
+/// assert_eq!(version_1.get_value(&2)?, Some(&3));
+/// assert_eq!(version_2.get_value(&2)?, Some(&4));
+/// # Ok(())
+/// # }
+/// ```
+pub struct ImmutableTree<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+{
+    name: Option<Arc<str>>,
+    root: Option<Q>,
+    nodes: PersistentMap<Q, Arc<NodeData<Q, T>>>,
+}
+
+/// The data of a node of an `ImmutableTree`.
+///
+/// The value is shared between the versions of the tree, so it does not need to implement `Clone`.
+struct NodeData<Q, T> {
+    value: Option<Arc<T>>,
+    parent: Option<Q>,
+    children: Vec<Q>,
+}
+
+impl<Q: Clone, T> Clone for NodeData<Q, T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            parent: self.parent.clone(),
+            children: self.children.clone(),
+        }
+    }
+}
+
+impl<Q, T> ImmutableTree<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Debug + Hash + Ord,
+{
+    /// Create a new empty immutable tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree_name` - The name of the tree.
+    ///
+    /// # Returns
+    ///
+    /// A new empty tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::ImmutableTree;
+    ///
+    /// let tree: ImmutableTree<i32, i32> = ImmutableTree::new(Some("Sample Tree"));
+    /// ```
+    pub fn new(tree_name: Option<&str>) -> Self {
+        Self {
+            name: tree_name.map(Arc::from),
+            root: None,
+            nodes: PersistentMap::default(),
+        }
+    }
+
+    /// Get the name of the tree.
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Get the id of the root node of the tree.
+    ///
+    /// # Returns
+    ///
+    /// The id of the root node, or `None` if the tree is empty.
+    pub fn get_root_id(&self) -> Option<&Q> {
+        self.root.as_ref()
+    }
+
+    /// Get the number of nodes in the tree.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Check if the tree has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.len() == 0
+    }
+
+    /// Check if the tree has a node with the given id.
+    pub fn contains<K>(&self, node_id: &K) -> bool
+    where
+        Q: Borrow<K>,
+        K: Ord + ?Sized,
+    {
+        self.nodes.get(node_id).is_some()
+    }
+
+    /// Get the value of a node.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node.
+    ///
+    /// # Returns
+    ///
+    /// The value of the node, or an error if the node is not in the tree.
+    pub fn get_value<K>(&self, node_id: &K) -> crate::prelude::Result<Option<&T>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Ord + ?Sized,
+    {
+        Ok(self.get_data(node_id)?.value.as_deref())
+    }
+
+    /// Get the id of the parent of a node.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node.
+    ///
+    /// # Returns
+    ///
+    /// The id of the parent, `None` for the root node, or an error if the node is not in the tree.
+    pub fn get_parent_id<K>(&self, node_id: &K) -> crate::prelude::Result<Option<&Q>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Ord + ?Sized,
+    {
+        Ok(self.get_data(node_id)?.parent.as_ref())
+    }
+
+    /// Get the ids of the children of a node.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node.
+    ///
+    /// # Returns
+    ///
+    /// The ids of the children in order, or an error if the node is not in the tree.
+    pub fn get_children_ids<K>(&self, node_id: &K) -> crate::prelude::Result<&[Q]>
+    where
+        Q: Borrow<K>,
+        K: Debug + Ord + ?Sized,
+    {
+        Ok(&self.get_data(node_id)?.children)
+    }
+
+    /// Insert a node into a new version of the tree.
+    ///
+    /// This method returns a tree with a new node added as the last child of `parent_id`, or as the
+    /// root node if `parent_id` is `None`.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the new node.
+    /// * `value` - The value of the new node.
+    /// * `parent_id` - The id of the parent of the new node.
+    ///
+    /// # Returns
+    ///
+    /// The new tree. An error is returned if the id is already in the tree, if the parent is not
+    /// in the tree, or if `parent_id` is `None` and the tree already has a root node.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::ImmutableTree;
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let tree: ImmutableTree<i32, i32> = ImmutableTree::new(None).insert(1, Some(2), None)?;
+    /// let new_tree = tree.insert(2, Some(3), Some(&1))?;
+    ///
+    /// assert_eq!(tree.len(), 1);
+    /// assert_eq!(new_tree.get_children_ids(&1)?, &[2]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn insert(
+        &self,
+        node_id: Q,
+        value: Option<T>,
+        parent_id: Option<&Q>,
+    ) -> crate::prelude::Result<Self> {
+        if self.contains(&node_id) {
+            return Err(InvalidOperation(format!(
+                "Node {node_id:?} is already in the tree"
+            )));
+        }
+        let mut tree = self.clone();
+        match parent_id {
+            Some(parent_id) => tree.update_data(parent_id, |x| x.children.push(node_id.clone()))?,
+            None if tree.root.is_some() => return Err(RootNodeAlreadyPresent),
+            None => tree.root = Some(node_id.clone()),
+        }
+        let data = NodeData {
+            value: value.map(Arc::new),
+            parent: parent_id.cloned(),
+            children: vec![],
+        };
+        tree.nodes = tree.nodes.insert(node_id, Arc::new(data));
+        Ok(tree)
+    }
+
+    /// Remove a node from a new version of the tree.
+    ///
+    /// This method returns a tree without the given node. The strategy decides what happens to the
+    /// children of the node, as in `Tree::remove_node`: with `RetainChildren` they become the last
+    /// children of the parent of the removed node, and with `RemoveNodeAndChildren` all the
+    /// descendants are removed too.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node to remove.
+    /// * `strategy` - The strategy to use when removing the node.
+    ///
+    /// # Returns
+    ///
+    /// The new tree. An error is returned if the node is not in the tree, or if the root node is
+    /// removed with the `RetainChildren` strategy.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{ImmutableTree, NodeRemovalStrategy};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let tree: ImmutableTree<i32, i32> = ImmutableTree::new(None)
+    ///     .insert(1, Some(2), None)?
+    ///     .insert(2, Some(3), Some(&1))?
+    ///     .insert(3, Some(4), Some(&2))?;
+    /// let new_tree = tree.remove(&2, NodeRemovalStrategy::RetainChildren)?;
+    ///
+    /// assert_eq!(new_tree.get_children_ids(&1)?, &[3]);
+    /// assert_eq!(tree.get_children_ids(&1)?, &[2]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn remove(
+        &self,
+        node_id: &Q,
+        strategy: NodeRemovalStrategy,
+    ) -> crate::prelude::Result<Self> {
+        let data = self.get_data(node_id)?.clone();
+        let mut tree = self.clone();
+        match strategy {
+            NodeRemovalStrategy::RetainChildren => {
+                let parent_id = data.parent.as_ref().ok_or(InvalidOperation(String::from(
+                    "Cannot remove root node with RetainChildren strategy",
+                )))?;
+                tree.update_data(parent_id, |x| {
+                    x.children.retain(|x| x != node_id);
+                    x.children.extend(data.children.iter().cloned());
+                })?;
+                for child_id in data.children.iter() {
+                    tree.update_data(child_id, |x| x.parent = Some(parent_id.clone()))?;
+                }
+                tree.nodes = tree.nodes.remove(node_id);
+            }
+            NodeRemovalStrategy::RemoveNodeAndChildren => {
+                match &data.parent {
+                    Some(parent_id) => {
+                        tree.update_data(parent_id, |x| x.children.retain(|x| x != node_id))?
+                    }
+                    None => tree.root = None,
+                }
+                let mut stack = vec![node_id.clone()];
+                while let Some(node_id) = stack.pop() {
+                    stack.extend(tree.get_data(&node_id)?.children.iter().cloned());
+                    tree.nodes = tree.nodes.remove(&node_id);
+                }
+            }
+        }
+        Ok(tree)
+    }
+
+    /// Set the value of a node in a new version of the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node.
+    /// * `value` - The new value of the node.
+    ///
+    /// # Returns
+    ///
+    /// The new tree, or an error if the node is not in the tree.
+    pub fn set_value(&self, node_id: &Q, value: Option<T>) -> crate::prelude::Result<Self> {
+        let mut tree = self.clone();
+        tree.update_data(node_id, |x| x.value = value.map(Arc::new))?;
+        Ok(tree)
+    }
+
+    /// Move a node and its descendants under a new parent in a new version of the tree.
+    ///
+    /// The node becomes the last child of its new parent.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node to move.
+    /// * `parent_id` - The id of the new parent.
+    ///
+    /// # Returns
+    ///
+    /// The new tree. An error is returned if either node is not in the tree, or if the new parent
+    /// is the node itself or one of its descendants.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::ImmutableTree;
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let tree: ImmutableTree<i32, i32> = ImmutableTree::new(None)
+    ///     .insert(1, Some(2), None)?
+    ///     .insert(2, Some(3), Some(&1))?
+    ///     .insert(3, Some(4), Some(&1))?;
+    /// let new_tree = tree.move_node(&3, &2)?;
+    ///
+    /// assert_eq!(new_tree.get_parent_id(&3)?, Some(&2));
+    /// assert_eq!(tree.get_parent_id(&3)?, Some(&1));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn move_node(&self, node_id: &Q, parent_id: &Q) -> crate::prelude::Result<Self> {
+        let data = self.get_data(node_id)?;
+        let mut ancestor_id = Some(parent_id);
+        while let Some(id) = ancestor_id {
+            if id == node_id {
+                return Err(InvalidOperation(format!(
+                    "Cannot move node {node_id:?} under itself or one of its descendants"
+                )));
+            }
+            ancestor_id = self.get_data(id)?.parent.as_ref();
+        }
+        let mut tree = self.clone();
+        if let Some(old_parent_id) = &data.parent {
+            tree.update_data(old_parent_id, |x| x.children.retain(|x| x != node_id))?;
+        }
+        tree.update_data(parent_id, |x| x.children.push(node_id.clone()))?;
+        tree.update_data(node_id, |x| x.parent = Some(parent_id.clone()))?;
+        Ok(tree)
+    }
+
+    /// Traverse the subtree of a node.
+    ///
+    /// This method returns the ids of the nodes in the same order as `Tree::traverse`.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node to start the traversal from.
+    /// * `order` - The order in which to visit the nodes.
+    ///
+    /// # Returns
+    ///
+    /// The ids of the nodes, or an error if the node is not in the tree.
+    pub fn traverse<K>(
+        &self,
+        node_id: &K,
+        order: TraversalStrategy,
+    ) -> crate::prelude::Result<Vec<Q>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Ord + ?Sized,
+    {
+        let (node_id, _) = self
+            .nodes
+            .get_key_value(node_id)
+            .ok_or_else(|| Error::node_not_found(node_id))?;
+        traverse_subtree(node_id, order, &|node_id| {
+            let data = self
+                .nodes
+                .get::<Q>(node_id)
+                .ok_or_else(|| Error::node_not_found(node_id))?;
+            Ok(data.children.to_vec())
+        })
+    }
+
+    /// Create an immutable tree from a tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The tree to copy.
+    ///
+    /// # Returns
+    ///
+    /// An immutable tree with the same name, nodes and structure as the tree, or an error if a node
+    /// id appears more than once in the tree.
+    pub fn from_tree<P: NodeStorage, E>(tree: &Tree<Q, T, P, E>) -> crate::prelude::Result<Self>
+    where
+        T: Clone,
+    {
+        let mut immutable = Self::new(tree.get_name());
+        let Some(root) = tree.get_root_node() else {
+            return Ok(immutable);
+        };
+        for node_id in tree.traverse(&root.get_node_id()?, TraversalStrategy::PreOrder)? {
+            let node = tree
+                .get_node_ref(&node_id)
+                .ok_or_else(|| Error::node_not_found(&node_id))?;
+            immutable =
+                immutable.insert(node_id, node.get_value()?, node.get_parent_id()?.as_ref())?;
+        }
+        Ok(immutable)
+    }
+
+    /// Create a tree with the given storage from this immutable tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - The storage of the nodes of the new tree.
+    ///
+    /// # Returns
+    ///
+    /// A tree with the same name, nodes and structure as this immutable tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{DefaultStorage, ImmutableTree, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let immutable: ImmutableTree<i32, i32> = ImmutableTree::new(None)
+    ///     .insert(1, Some(2), None)?
+    ///     .insert(2, Some(3), Some(&1))?;
+    /// let tree: Tree<i32, i32> = immutable.to_tree_in(DefaultStorage::default())?;
+    ///
+    /// assert_eq!(tree.get_node_height(&1)?, 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_tree_in<P: NodeStorage>(&self, storage: P) -> crate::prelude::Result<Tree<Q, T, P>>
+    where
+        T: Clone,
+    {
+        let mut tree = Tree::new_in(self.get_name(), storage);
+        let Some(root) = &self.root else {
+            return Ok(tree);
+        };
+        for node_id in self.traverse(root, TraversalStrategy::PreOrder)? {
+            let data = self.get_data(&node_id)?;
+            tree.add_node(
+                Node::new_in(node_id, data.value.as_deref().cloned(), storage),
+                data.parent.as_ref(),
+            )?;
+        }
+        Ok(tree)
+    }
+
+    /// Render the tree as text.
+    ///
+    /// This method renders the tree with the options of the given renderer, as `Tree::render` does.
+    pub fn render(&self, renderer: &TreeRenderer<Q, T>) -> crate::prelude::Result<String>
+    where
+        Q: Display,
+    {
+        renderer.render_tree(self.get_name(), self.root.clone(), |node_id| {
+            let data = self.get_data(node_id)?;
+            let label = renderer.label(node_id, data.value.as_deref());
+            Ok((label, data.children.clone()))
+        })
+    }
+
+    /// Get the data of a node.
+    fn get_data<K>(&self, node_id: &K) -> crate::prelude::Result<&Arc<NodeData<Q, T>>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Ord + ?Sized,
+    {
+        self.nodes
+            .get(node_id)
+            .ok_or_else(|| Error::node_not_found(node_id))
+    }
+
+    /// Replace the data of a node with an updated copy.
+    fn update_data<F>(&mut self, node_id: &Q, update: F) -> crate::prelude::Result<()>
+    where
+        F: FnOnce(&mut NodeData<Q, T>),
+    {
+        let mut data = NodeData::clone(self.get_data(node_id)?);
+        update(&mut data);
+        self.nodes = self.nodes.insert(node_id.clone(), Arc::new(data));
+        Ok(())
+    }
+}
+
+impl<Q, T> Clone for ImmutableTree<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+{
+    /// Clone the tree without copying its nodes.
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            root: self.root.clone(),
+            nodes: self.nodes.clone(),
+        }
+    }
+}
+
+impl<Q, T> PartialEq for ImmutableTree<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq,
+{
+    /// Compare the names, the nodes and the structure of two trees.
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.root == other.root
+            && self.nodes.len() == other.nodes.len()
+            && self.nodes.iter().zip(other.nodes.iter()).all(|(x, y)| {
+                x.0 == y.0
+                    && x.1.value == y.1.value
+                    && x.1.parent == y.1.parent
+                    && x.1.children == y.1.children
+            })
+    }
+}
+
+impl<Q, T> Eq for ImmutableTree<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: Eq,
+{
+}
+
+impl<Q, T> Debug for ImmutableTree<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Debug,
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ImmutableTree")
+            .field("name", &self.name)
+            .field("root", &self.root)
+            .field(
+                "nodes",
+                &self
+                    .nodes
+                    .iter()
+                    .map(|(id, data)| (id, &data.value, &data.children))
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl<Q, T> Default for ImmutableTree<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Debug + Hash + Ord,
+{
+    /// Create an empty tree without a name.
+    fn default() -> Self {
+        ImmutableTree::new(None)
+    }
+}
+
+impl<Q, T> Display for ImmutableTree<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Debug + Display + Hash + Ord,
+    T: Display,
+{
+    /// Print the tree as a `Tree` with the same nodes would be printed.
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let rendered = self.render(&TreeRenderer::new()).map_err(|_| FmtError)?;
+        f.write_str(&rendered)
+    }
+}
@@ -1,15 +1,47 @@
 use crate::lib::*;
+use crate::node::{ArcStorage, DefaultStorage, RcStorage};
 
 #[cfg(feature = "async")]
-pub use async_tree::Tree;
-#[cfg(not(feature = "async"))]
-pub use sync_tree::Tree;
+pub use async_tree::{AsyncTree, AsyncTreeReadGuard, AsyncTreeWriteGuard};
+pub(crate) use attributes::AttributeTables;
+pub use attributes::NodeAttributes;
+#[cfg(feature = "serde")]
+pub use base::WithAttributes;
+pub use base::{DisplayEdges, Tree, TreeIter};
+pub use builder::TreeBuilder;
+pub use cursor::{TreeCursor, TreeCursorMut};
+pub(crate) use id_generator::SharedIdGenerator;
+#[cfg(not(feature = "no_std"))]
+pub use id_generator::{EpochIdGenerator, RandomIdGenerator};
+pub use id_generator::{IdGenerator, SeededIdGenerator, SequentialIdGenerator};
+pub use immutable_tree::ImmutableTree;
+pub use shared_tree::{SharedTree, TreeSnapshot};
+pub use walk::{TreeVisitor, WalkControl, WalkEvent, WalkEvents};
 
 #[cfg(feature = "async")]
 mod async_tree;
+mod attributes;
+mod base;
+mod builder;
+mod cursor;
+mod id_generator;
+mod immutable_tree;
+#[cfg(not(feature = "no_std"))]
+mod parallel;
+mod persistent_map;
+mod shared_tree;
+mod walk;
 
-#[cfg(not(feature = "async"))]
-mod sync_tree;
+pub(crate) use newick::parse_newick;
+pub use newick::NewickValue;
+pub use outline::IndentStyle;
+pub(crate) use outline::{parse_outline, OutlineEntry};
+pub(crate) use renderer::TreeLine;
+pub use renderer::{TreeGlyphs, TreeRenderer};
+
+mod newick;
+mod outline;
+mod renderer;
 
 /// The strategy to use when removing a node from the tree.
 ///
@@ -28,6 +60,27 @@ pub enum NodeRemovalStrategy {
     RemoveNodeAndChildren,
 }
 
+/// The policy to use when a subtree added to a tree has node ids that are already in the tree.
+///
+/// This enum represents what `Tree::add_subtree_with` does with a node of the subtree whose id is
+/// already used by a node of the tree.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum CollisionPolicy {
+    /// Return an error without changing the tree.
+    Fail,
+    /// Leave out the node of the subtree along with its descendants. The node of the tree is kept.
+    /// If the root of the subtree collides, nothing is added and an error is returned.
+    Skip,
+    /// Replace the node of the tree by the node of the subtree. The node of the tree is removed as
+    /// with `NodeRemovalStrategy::RemoveNodeAndChildren`, so its descendants, their edges and their
+    /// attributes are removed as well, and the node of the subtree takes its id with its own
+    /// children.
+    Overwrite,
+    /// Give the node of the subtree a new id from the id generator of the tree. See
+    /// `Tree::set_id_generator`.
+    Remap,
+}
+
 /// The strategy to use when traversing the tree.
 ///
 /// This enum represents the strategy to use when traversing the tree.
@@ -45,14 +98,149 @@ pub enum TraversalStrategy {
     InOrder,
 }
 
+/// The diagram syntax to use when exporting the tree to Mermaid.
+///
+/// This enum represents the kind of Mermaid diagram produced by `Tree::to_mermaid`.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum MermaidStyle {
+    /// Export the tree as a top-down flowchart. Each node is declared once and connected to its
+    /// parent with an arrow.
+    Flowchart,
+    /// Export the tree as a mindmap. The hierarchy is expressed through indentation.
+    Mindmap,
+}
+
+/// The diagram syntax to use when exporting the tree to PlantUML.
+///
+/// This enum represents the kind of PlantUML diagram produced by `Tree::to_plantuml`.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum PlantUmlStyle {
+    /// Export the tree as a work breakdown structure (`@startwbs`).
+    Wbs,
+    /// Export the tree as a mindmap (`@startmindmap`).
+    Mindmap,
+}
+
 /// A subtree of a tree.
 ///
 /// This struct represents a subtree of a tree. A subtree is a tree that is a part of a larger tree.
-pub type SubTree<Q, T> = Tree<Q, T>;
+pub type SubTree<Q, T, P = DefaultStorage, E = ()> = Tree<Q, T, P, E>;
+
+/// A tree whose nodes keep their data in an `Rc<RefCell<_>>`.
+///
+/// This is the cheapest tree to use from a single thread. It can not be sent to other threads and is
+/// available whether or not the `async` feature is enabled.
+pub type LocalTree<Q, T> = Tree<Q, T, RcStorage>;
+
+/// A tree whose nodes keep their data in an `Arc<RwLock<_>>`.
+///
+/// This tree is `Send` and `Sync` as long as the node ids and values are, so it can be shared
+/// between threads. It is available whether or not the `async` feature is enabled and can be used
+/// in the same program as a `LocalTree`.
+pub type ThreadSafeTree<Q, T> = Tree<Q, T, ArcStorage>;
+
+/// Escape a label so that it can be placed inside a quoted Mermaid node label.
+///
+/// Mermaid does not support backslash escapes, so the characters that would terminate the label or
+/// be interpreted as markup are replaced with their entity codes and newlines become line breaks.
+pub(crate) fn escape_mermaid_label(label: &str) -> String {
+    let mut escaped = String::with_capacity(label.len());
+    for character in label.chars() {
+        match character {
+            '#' => escaped.push_str("#35;"),
+            '"' => escaped.push_str("#quot;"),
+            '[' => escaped.push_str("#91;"),
+            ']' => escaped.push_str("#93;"),
+            '(' => escaped.push_str("#40;"),
+            ')' => escaped.push_str("#41;"),
+            '{' => escaped.push_str("#123;"),
+            '}' => escaped.push_str("#125;"),
+            '<' => escaped.push_str("#lt;"),
+            '>' => escaped.push_str("#gt;"),
+            '\r' => {}
+            '\n' => escaped.push_str("<br/>"),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+/// Escape a label so that it can be placed on a PlantUML WBS or mindmap line.
+///
+/// PlantUML uses `~` as its escape character. Brackets are escaped so that a label can not be
+/// mistaken for a colour or stereotype and newlines are written as the `\n` sequence.
+pub(crate) fn escape_plantuml_label(label: &str) -> String {
+    let mut escaped = String::with_capacity(label.len());
+    for character in label.chars() {
+        match character {
+            '~' | '[' | ']' | '"' | '\\' | '<' | '>' => {
+                escaped.push('~');
+                escaped.push(character);
+            }
+            '\r' => {}
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+/// Traverse the subtree of a node.
+///
+/// This is the traversal shared by the trees of the crate. The `children_of` closure returns the
+/// ids of the children of the node with the given id, or an error if the node is not found.
+pub(crate) fn traverse_subtree<Q, F>(
+    node_id: &Q,
+    order: TraversalStrategy,
+    children_of: &F,
+) -> crate::prelude::Result<Vec<Q>>
+where
+    Q: Clone + Eq + Hash + Ord,
+    F: Fn(&Q) -> crate::prelude::Result<Vec<Q>>,
+{
+    let mut nodes = vec![];
+    let children = children_of(node_id)?;
+    match &order {
+        TraversalStrategy::PreOrder => {
+            nodes.push(node_id.clone());
+            for child_id in children.iter() {
+                nodes.append(&mut traverse_subtree(child_id, order, children_of)?);
+            }
+        }
+        TraversalStrategy::PostOrder => {
+            for child_id in children.iter() {
+                nodes.append(&mut traverse_subtree(child_id, order, children_of)?);
+            }
+            nodes.push(node_id.clone());
+        }
+        TraversalStrategy::InOrder => {
+            for (index, child_id) in children.iter().enumerate() {
+                if index == 0 {
+                    nodes.append(&mut traverse_subtree(child_id, order, children_of)?);
+                    if !nodes.contains(child_id) {
+                        nodes.push(child_id.clone());
+                    }
+                    if !nodes.contains(node_id) {
+                        nodes.push(node_id.clone());
+                    }
+                } else {
+                    nodes.push(child_id.clone());
+                    nodes.append(&mut traverse_subtree(child_id, order, children_of)?);
+                }
+            }
+        }
+    }
+    #[cfg(not(feature = "no_std"))]
+    let mut seen = HashSet::new();
+    #[cfg(feature = "no_std")]
+    let mut seen = BTreeSet::new();
+    nodes.retain(|x| seen.insert(x.clone()));
+    Ok(nodes)
+}
 
 #[cfg(test)]
 mod tests {
-    use crate::error::Error::{InvalidOperation, NodeNotFound, RootNodeAlreadyPresent};
+    use crate::error::Error::{InvalidOperation, NodeNotFound, ParseError, RootNodeAlreadyPresent};
     use crate::lib::*;
     #[allow(deprecated)]
     #[cfg(feature = "no_std")]
@@ -61,7 +249,7 @@ mod tests {
     use std::hash::DefaultHasher;
 
     use super::*;
-    use crate::prelude::{Node, Result};
+    use crate::prelude::{Node, NodeStorage, Result};
 
     #[test]
     fn test_tree_new() {
@@ -382,132 +570,2540 @@ mod tests {
     }
 
     #[test]
-    fn compare_tree() {
+    fn test_tree_to_mermaid() -> Result<()> {
         let mut tree = Tree::<u32, u32>::new(Some("Sample Tree"));
-        let node_1 = tree.add_node(Node::new(1, Some(2)), None).unwrap();
-        let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1)).unwrap();
-        let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2)).unwrap();
-        tree.add_node(Node::new(4, Some(5)), Some(&node_2)).unwrap();
-        tree.add_node(Node::new(5, Some(6)), Some(&node_3)).unwrap();
-        let mut tree_2 = Tree::<u32, u32>::new(Some("Sample Tree"));
-        let node_1 = tree_2.add_node(Node::new(1, Some(2)), None).unwrap();
-        let node_2 = tree_2
-            .add_node(Node::new(2, Some(3)), Some(&node_1))
-            .unwrap();
-        let node_3 = tree_2
-            .add_node(Node::new(3, Some(6)), Some(&node_2))
-            .unwrap();
-        tree_2
-            .add_node(Node::new(4, Some(5)), Some(&node_2))
-            .unwrap();
-        tree_2
-            .add_node(Node::new(5, Some(6)), Some(&node_3))
-            .unwrap();
-        assert_eq!(tree, tree_2);
-        let tree_3 = Tree::<u32, u32>::new(Some("Sample Tree"));
-        assert_ne!(tree, tree_3);
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+        let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+        tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+        tree.add_node(Node::new(4, Some(5)), Some(&node_1))?;
+        #[cfg(feature = "print_node_id")]
+        let expected_str = "---\ntitle: Sample Tree\n---\nflowchart TD\n    n0[\"1: 2\"]\n    n0 --> n1[\"2: 3\"]\n    n1 --> n2[\"3: 6\"]\n    n0 --> n3[\"4: 5\"]\n";
+        #[cfg(not(feature = "print_node_id"))]
+        let expected_str = "---\ntitle: Sample Tree\n---\nflowchart TD\n    n0[\"2\"]\n    n0 --> n1[\"3\"]\n    n1 --> n2[\"6\"]\n    n0 --> n3[\"5\"]\n";
+        assert_eq!(tree.to_mermaid(MermaidStyle::Flowchart)?, expected_str);
+
+        tree.rename(None);
+        #[cfg(feature = "print_node_id")]
+        let expected_str = "mindmap\n    n0[\"1: 2\"]\n        n1[\"2: 3\"]\n            n2[\"3: 6\"]\n        n3[\"4: 5\"]\n";
+        #[cfg(not(feature = "print_node_id"))]
+        let expected_str =
+            "mindmap\n    n0[\"2\"]\n        n1[\"3\"]\n            n2[\"6\"]\n        n3[\"5\"]\n";
+        assert_eq!(tree.to_mermaid(MermaidStyle::Mindmap)?, expected_str);
+        Ok(())
     }
 
     #[test]
-    fn test_tree_traverse() {
+    fn test_tree_to_mermaid_escapes_labels() -> Result<()> {
+        let mut tree = Tree::<u32, &str>::new(None);
+        tree.add_node(Node::new(1, Some("say \"hi\" [now]\n#1")), None)?;
+        let diagram = tree.to_mermaid(MermaidStyle::Flowchart)?;
+        assert!(diagram.contains("say #quot;hi#quot; #91;now#93;<br/>#35;1"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_to_plantuml() -> Result<()> {
         let mut tree = Tree::<u32, u32>::new(Some("Sample Tree"));
-        let node_1 = tree.add_node(Node::new(1, Some(2)), None).unwrap();
-        let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1)).unwrap();
-        let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_1)).unwrap();
-        let node_4 = tree.add_node(Node::new(4, Some(5)), Some(&node_2)).unwrap();
-        let node_5 = tree.add_node(Node::new(5, Some(6)), Some(&node_2)).unwrap();
-        let node_6 = tree.add_node(Node::new(6, Some(7)), Some(&node_3)).unwrap();
-        let preorder_nodes = tree.traverse(&node_1, TraversalStrategy::PreOrder).unwrap();
-        let expected_preorder = vec![node_1, node_2, node_4, node_5, node_3, node_6];
-        assert_eq!(preorder_nodes, expected_preorder);
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+        let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+        tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+        tree.add_node(Node::new(4, Some(5)), Some(&node_1))?;
+        #[cfg(feature = "print_node_id")]
+        let expected_str =
+            "@startwbs\ntitle Sample Tree\n* 1: 2\n** 2: 3\n*** 3: 6\n** 4: 5\n@endwbs\n";
+        #[cfg(not(feature = "print_node_id"))]
+        let expected_str = "@startwbs\ntitle Sample Tree\n* 2\n** 3\n*** 6\n** 5\n@endwbs\n";
+        assert_eq!(tree.to_plantuml(PlantUmlStyle::Wbs)?, expected_str);
 
-        let in_order_nodes = tree.traverse(&node_1, TraversalStrategy::InOrder).unwrap();
-        let expected_in_order = vec![node_4, node_2, node_5, node_1, node_3, node_6];
-        assert_eq!(in_order_nodes, expected_in_order);
+        let diagram = tree.to_plantuml(PlantUmlStyle::Mindmap)?;
+        assert!(diagram.starts_with("@startmindmap\n"));
+        assert!(diagram.ends_with("@endmindmap\n"));
+        Ok(())
+    }
 
-        let post_order_nodes = tree
-            .traverse(&node_1, TraversalStrategy::PostOrder)
-            .unwrap();
-        let expected_post_order = vec![node_4, node_5, node_2, node_6, node_3, node_1];
-        assert_eq!(post_order_nodes, expected_post_order);
+    #[test]
+    fn test_tree_to_plantuml_escapes_labels() -> Result<()> {
+        let mut tree = Tree::<u32, &str>::new(None);
+        tree.add_node(Node::new(1, Some("[#red] \"a\"\nb")), None)?;
+        let diagram = tree.to_plantuml(PlantUmlStyle::Wbs)?;
+        assert!(diagram.contains("~[#red~] ~\"a~\"\\nb\n"));
+        Ok(())
     }
 
-    #[allow(deprecated)] // This is solely for testing hashing in no_std.
     #[test]
-    fn test_hashing() {
-        let mut tree = Tree::<u32, u32>::new(Some("Sample Tree"));
-        let node_1 = tree.add_node(Node::new(1, Some(2)), None).unwrap();
-        let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1)).unwrap();
-        let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2)).unwrap();
-        tree.add_node(Node::new(4, Some(5)), Some(&node_2)).unwrap();
-        tree.add_node(Node::new(5, Some(6)), Some(&node_3)).unwrap();
-        let mut tree_2 = Tree::<u32, u32>::new(Some("Sample Tree"));
-        let node_1 = tree_2.add_node(Node::new(1, Some(2)), None).unwrap();
-        let node_2 = tree_2
-            .add_node(Node::new(2, Some(3)), Some(&node_1))
-            .unwrap();
-        let node_3 = tree_2
-            .add_node(Node::new(3, Some(6)), Some(&node_2))
-            .unwrap();
-        tree_2
-            .add_node(Node::new(4, Some(5)), Some(&node_2))
-            .unwrap();
-        tree_2
-            .add_node(Node::new(5, Some(6)), Some(&node_3))
-            .unwrap();
-        assert_eq!(tree, tree_2);
-        let mut hasher = DefaultHasher::new();
-        tree.hash(&mut hasher);
-        let tree_hash = hasher.finish();
-        let mut hasher = DefaultHasher::new();
-        tree_2.hash(&mut hasher);
-        let tree_2_hash = hasher.finish();
-        assert_eq!(tree_hash, tree_2_hash);
+    fn test_tree_to_diagram_follows_the_renderer() -> Result<()> {
+        // The value has no `Default`, and a missing value gets an empty label as when rendering.
+        struct Label(&'static str);
+
+        impl Display for Label {
+            fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+                f.write_str(self.0)
+            }
+        }
+
+        let mut tree = Tree::<u32, Label>::new(None);
+        let node_1 = tree.add_node(Node::new(1, Some(Label("a"))), None)?;
+        tree.add_node(Node::new(2, None), Some(&node_1))?;
+        tree.add_node(Node::new(3, Some(Label("c"))), Some(&node_1))?;
+        #[cfg(not(feature = "print_node_id"))]
+        {
+            assert_eq!(
+                tree.to_mermaid(MermaidStyle::Flowchart)?,
+                "flowchart TD\n    n0[\"a\"]\n    n0 --> n1[\"\"]\n    n0 --> n2[\"c\"]\n"
+            );
+            assert_eq!(
+                tree.to_plantuml(PlantUmlStyle::Wbs)?,
+                "@startwbs\n* a\n** \n** c\n@endwbs\n"
+            );
+        }
+        let rendered = tree.render(&TreeRenderer::new())?;
+        let labels = rendered
+            .lines()
+            .map(|x| x.trim_start_matches(['├', '└', '─', '│', ' ']))
+            .collect::<Vec<_>>();
+        let diagram = tree.to_plantuml(PlantUmlStyle::Wbs)?;
+        let diagram_labels = diagram
+            .lines()
+            .filter_map(|x| x.trim_start_matches('*').strip_prefix(' '))
+            .collect::<Vec<_>>();
+        assert_eq!(labels, diagram_labels);
+        Ok(())
     }
 
     #[test]
-    fn test_sort_children_by_height() -> Result<()> {
+    fn test_tree_display_missing_value() -> Result<()> {
         let mut tree = Tree::<u32, u32>::new(Some("Sample Tree"));
-        let node_1 = tree.add_node(Node::new(1, Some(1)), None)?;
-        let _node_2 = tree.add_node(Node::new(2, Some(2)), Some(&node_1))?;
-        let node_3 = tree.add_node(Node::new(3, Some(3)), Some(&node_1))?;
-        let node_4 = tree.add_node(Node::new(4, Some(4)), Some(&node_3))?;
-        let _node_5 = tree.add_node(Node::new(5, Some(5)), Some(&node_4))?;
+        let node_1 = tree.add_node(Node::new(1, Some(0)), None)?;
+        tree.add_node(Node::new(2, None), Some(&node_1))?;
+        #[cfg(feature = "print_node_id")]
+        let expected_str = "Sample Tree\n***********\n1: 0\n└── 2: \n";
+        #[cfg(not(feature = "print_node_id"))]
+        let expected_str = "Sample Tree\n***********\n0\n└── \n";
+        assert_eq!(tree.to_string(), expected_str);
 
-        let root = tree.get_node_by_id(&node_1).unwrap();
-        root.sort_children(|a, b| {
-            let a_height = tree.get_node_height(a).unwrap();
-            let b_height = tree.get_node_height(b).unwrap();
-            a_height.cmp(&b_height).reverse()
+        let renderer = TreeRenderer::with_formatter(|_: &u32, value: Option<&u32>| {
+            value.copied().unwrap_or_default().to_string()
+        });
+        assert_eq!(
+            tree.render(&renderer)?,
+            "Sample Tree\n***********\n0\n└── 0\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_to_diagram_empty_tree() -> Result<()> {
+        let tree = Tree::<u32, u32>::new(None);
+        assert_eq!(tree.to_mermaid(MermaidStyle::Flowchart)?, "flowchart TD\n");
+        assert_eq!(
+            tree.to_plantuml(PlantUmlStyle::Wbs)?,
+            "@startwbs\n@endwbs\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_from_newick() -> Result<()> {
+        let tree = Tree::<u32, NewickValue>::from_newick(
+            "((Homo_sapiens:0.1,'Pan troglodytes':0.2)Hominini:0.3,[outgroup] Gorilla:0.4)Root;",
+        )?;
+        assert_eq!(tree.get_nodes().len(), 5);
+        assert_eq!(
+            tree.traverse(&0, TraversalStrategy::PreOrder)?,
+            vec![0, 1, 2, 3, 4]
+        );
+        let hominini = tree.get_node_by_id(&1).unwrap().get_value()?.unwrap();
+        assert_eq!(hominini.get_label(), Some("Hominini"));
+        assert_eq!(hominini.get_branch_length(), Some(0.3));
+        let human = tree.get_node_by_id(&2).unwrap().get_value()?.unwrap();
+        assert_eq!(human.get_label(), Some("Homo sapiens"));
+        let chimp = tree.get_node_by_id(&3).unwrap().get_value()?.unwrap();
+        assert_eq!(chimp.get_label(), Some("Pan troglodytes"));
+        assert_eq!(chimp.get_branch_length(), Some(0.2));
+        let root = tree.get_root_node().unwrap().get_value()?.unwrap();
+        assert_eq!(root.get_label(), Some("Root"));
+        assert_eq!(root.get_branch_length(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_from_newick_unlabelled_nodes() -> Result<()> {
+        let tree = Tree::<u64, NewickValue>::from_newick("(,(,));")?;
+        assert_eq!(tree.get_nodes().len(), 5);
+        assert_eq!(tree.get_node_degree(&2)?, 2);
+        assert_eq!(
+            tree.get_root_node()
+                .unwrap()
+                .get_value()?
+                .unwrap()
+                .get_label(),
+            None
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_newick_round_trip() -> Result<()> {
+        let newick = "((A:0.1,'B c':0.20,'it''s':1e-3)'d_e':0.5,F)G:0;";
+        let tree = Tree::<u32, NewickValue>::from_newick(newick)?;
+        assert_eq!(tree.to_newick()?, newick);
+        let reparsed = Tree::<u32, NewickValue>::from_newick(&tree.to_newick()?)?;
+        assert_eq!(reparsed, tree);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_to_newick_no_root_node() {
+        let tree = Tree::<u32, NewickValue>::new(None);
+        assert_eq!(
+            tree.to_newick().unwrap_err(),
+            InvalidOperation("Tree has no root node".to_string())
+        );
+    }
+
+    #[test]
+    fn test_tree_from_newick_errors() {
+        let parse_error = |newick: &str| Tree::<u32, NewickValue>::from_newick(newick).unwrap_err();
+        assert_eq!(
+            parse_error("(A,B"),
+            ParseError {
+                line: 1,
+                column: 5,
+                message: "Unexpected end of input, expected ';'".to_string()
+            }
+        );
+        assert_eq!(
+            parse_error("(A,\n  B:x);"),
+            ParseError {
+                line: 2,
+                column: 5,
+                message: "Invalid branch length 'x'".to_string()
+            }
+        );
+        assert_eq!(
+            parse_error("(A,B));"),
+            ParseError {
+                line: 1,
+                column: 6,
+                message: "Unexpected ')' without a matching '('".to_string()
+            }
+        );
+        assert_eq!(
+            parse_error("(A,'B);"),
+            ParseError {
+                line: 1,
+                column: 4,
+                message: "Unterminated quoted label".to_string()
+            }
+        );
+        assert_eq!(
+            parse_error("A;\n[comment"),
+            ParseError {
+                line: 2,
+                column: 1,
+                message: "Unterminated comment".to_string()
+            }
+        );
+        assert_eq!(
+            parse_error("A; B;"),
+            ParseError {
+                line: 1,
+                column: 4,
+                message: "Unexpected 'B' after the end of the tree".to_string()
+            }
+        );
+        assert_eq!(
+            parse_error("A,B;"),
+            ParseError {
+                line: 1,
+                column: 2,
+                message: "Unexpected ','".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_tree_from_newick_id_out_of_range() {
+        let result = Tree::<u8, NewickValue>::from_newick(&format!("({});", ",".repeat(256)));
+        assert_eq!(
+            result.unwrap_err(),
+            InvalidOperation("The node id 256 is out of range.".to_string())
+        );
+    }
+
+    fn create_sequential_node(next_id: &mut u32, label: &str) -> Result<Node<u32, String>> {
+        *next_id += 1;
+        Ok(Node::new(*next_id, Some(label.to_string())))
+    }
+
+    #[test]
+    fn test_tree_from_indented_spaces() -> Result<()> {
+        let mut next_id = 0;
+        let outline = "root\n  a\n    b\n\n  c\n";
+        let tree = Tree::from_indented_with(outline, IndentStyle::Spaces(2), |label| {
+            create_sequential_node(&mut next_id, label)
         })?;
+        assert_eq!(tree.get_name(), None);
+        assert_eq!(
+            tree.traverse(&1, TraversalStrategy::PreOrder)?,
+            vec![1, 2, 3, 4]
+        );
+        assert_eq!(tree.get_ancestor_ids(&3)?, vec![2, 1]);
+        assert_eq!(tree.get_node_by_id(&4).unwrap().get_parent_id()?, Some(1));
+        assert_eq!(
+            tree.get_node_by_id(&3).unwrap().get_value()?,
+            Some("b".to_string())
+        );
+        Ok(())
+    }
 
+    #[test]
+    fn test_tree_from_indented_tabs() -> Result<()> {
+        let mut next_id = 0;
+        let outline = "root\r\n\ta\r\n\t\tb c\r\n\td\r\n";
+        let tree = Tree::from_indented_with(outline, IndentStyle::Tabs, |label| {
+            create_sequential_node(&mut next_id, label)
+        })?;
+        assert_eq!(tree.get_nodes().len(), 4);
+        assert_eq!(tree.get_node_depth(&3)?, 2);
         assert_eq!(
-            tree.get_node_by_id(&node_1).unwrap().get_children_ids()?,
-            vec![3, 2]
+            tree.get_node_by_id(&3).unwrap().get_value()?,
+            Some("b c".to_string())
         );
         Ok(())
     }
-}
 
-#[cfg(all(test, feature = "serde"))]
-mod serde_tests {
-    use crate::prelude::Node;
+    #[test]
+    #[cfg_attr(feature = "print_node_id", ignore)]
+    fn test_tree_from_indented_display_round_trip() -> Result<()> {
+        let mut tree = Tree::<u32, String>::new(Some("Sample Tree"));
+        let node_1 = tree.add_node(Node::new(1, Some("a".to_string())), None)?;
+        let node_2 = tree.add_node(Node::new(2, Some("b".to_string())), Some(&node_1))?;
+        let node_3 = tree.add_node(Node::new(3, Some("c".to_string())), Some(&node_2))?;
+        tree.add_node(Node::new(4, Some("d".to_string())), Some(&node_3))?;
+        tree.add_node(Node::new(5, Some("e".to_string())), Some(&node_2))?;
+        tree.add_node(Node::new(6, Some("f".to_string())), Some(&node_1))?;
+        let mut next_id = 0;
+        let parsed =
+            Tree::from_indented_with(&tree.to_string(), IndentStyle::BoxDrawing, |label| {
+                create_sequential_node(&mut next_id, label)
+            })?;
+        assert_eq!(parsed.get_name(), Some("Sample Tree"));
+        assert_eq!(parsed, tree);
+        assert_eq!(parsed.to_string(), tree.to_string());
+        Ok(())
+    }
 
-    use super::*;
+    #[test]
+    fn test_tree_from_indented_errors() {
+        let parse_error = |outline: &str, style: IndentStyle| {
+            let mut next_id = 0;
+            Tree::from_indented_with(outline, style, |label| {
+                create_sequential_node(&mut next_id, label)
+            })
+            .unwrap_err()
+        };
+        assert_eq!(
+            parse_error("  root\n", IndentStyle::Spaces(2)),
+            ParseError {
+                line: 1,
+                column: 3,
+                message: "The root node must not be indented".to_string()
+            }
+        );
+        assert_eq!(
+            parse_error("root\n   a\n", IndentStyle::Spaces(2)),
+            ParseError {
+                line: 2,
+                column: 4,
+                message: "The indentation is not a multiple of 2 spaces".to_string()
+            }
+        );
+        assert_eq!(
+            parse_error("root\n\ta\n\t\t\tb\n", IndentStyle::Tabs),
+            ParseError {
+                line: 3,
+                column: 4,
+                message: "The node is indented more than one level deeper than its parent"
+                    .to_string()
+            }
+        );
+        assert_eq!(
+            parse_error("root\n\t a\n", IndentStyle::Tabs),
+            ParseError {
+                line: 2,
+                column: 2,
+                message: "Expected tabs for indentation".to_string()
+            }
+        );
+        assert_eq!(
+            parse_error("root\nother\n", IndentStyle::Tabs),
+            ParseError {
+                line: 2,
+                column: 1,
+                message: "Only one root node is allowed".to_string()
+            }
+        );
+        assert_eq!(
+            parse_error("root\n│   a\n", IndentStyle::BoxDrawing),
+            ParseError {
+                line: 2,
+                column: 5,
+                message: "Expected '├── ' or '└── ' before the node".to_string()
+            }
+        );
+    }
 
     #[test]
-    fn test_tree_serialize_and_deserialize() {
-        let mut tree = Tree::new(None);
-        let node_1 = tree.add_node(Node::new(1, Some(2)), None).unwrap();
-        let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1)).unwrap();
-        let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2)).unwrap();
-        tree.add_node(Node::new(4, Some(5)), Some(&node_2)).unwrap();
-        tree.add_node(Node::new(5, Some(6)), Some(&node_3)).unwrap();
-        let serialized = serde_json::to_string(&tree).unwrap();
-        let expected = r#"{"nodes":[{"node_id":1,"value":2,"parent":null,"children":[2]},{"node_id":2,"value":3,"parent":1,"children":[3,4]},{"node_id":3,"value":6,"parent":2,"children":[5]},{"node_id":4,"value":5,"parent":2,"children":[]},{"node_id":5,"value":6,"parent":3,"children":[]}]}"#;
-        let deserialized: Tree<u32, u32> = serde_json::from_str(&serialized).unwrap();
-        let expected_tree: Tree<u32, u32> = serde_json::from_str(expected).unwrap();
-        assert_eq!(deserialized, expected_tree);
+    fn test_tree_from_indented_duplicate_id() {
+        let result = Tree::from_indented_with("a\n b\n", IndentStyle::Spaces(1), |label| {
+            Ok(Node::new(1, Some(label.to_string())))
+        });
+        assert_eq!(
+            result.unwrap_err(),
+            ParseError {
+                line: 2,
+                column: 2,
+                message: "Duplicate node id 1".to_string()
+            }
+        );
+    }
+
+    #[cfg(feature = "auto_id")]
+    #[test]
+    fn test_tree_from_indented_with_auto_id() -> Result<()> {
+        let tree = Tree::<crate::prelude::AutomatedId, i32>::from_indented(
+            "1\n    2\n        3\n    4\n",
+            IndentStyle::Spaces(4),
+        )?;
+        assert_eq!(tree.get_nodes().len(), 4);
+        assert_eq!(tree.get_height()?, 2);
+        assert_eq!(tree.get_root_node().unwrap().get_value()?, Some(1));
+        let result = Tree::<crate::prelude::AutomatedId, i32>::from_indented(
+            "1\n    two\n",
+            IndentStyle::Spaces(4),
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            ParseError {
+                line: 2,
+                column: 5,
+                message: "Invalid value 'two'".to_string()
+            }
+        );
+        Ok(())
+    }
+
+    fn create_sample_tree() -> Tree<u32, u32> {
+        create_sample_tree_in(DefaultStorage::default())
+    }
+
+    fn create_sample_tree_in<P: NodeStorage, E>(storage: P) -> Tree<u32, u32, P, E> {
+        let mut tree = Tree::new_with_edges(Some("Sample Tree"), storage);
+        let node_1 = tree
+            .add_node(Node::new_in(1, Some(10), P::default()), None)
+            .unwrap();
+        let node_2 = tree
+            .add_node(Node::new_in(2, Some(20), P::default()), Some(&node_1))
+            .unwrap();
+        tree.add_node(Node::new_in(3, Some(30), P::default()), Some(&node_2))
+            .unwrap();
+        tree.add_node(Node::new_in(4, Some(40), P::default()), Some(&node_2))
+            .unwrap();
+        tree.add_node(Node::new_in(5, Some(50), P::default()), Some(&node_1))
+            .unwrap();
+        tree
+    }
+
+    #[test]
+    fn test_tree_render_default_matches_display() -> Result<()> {
+        let tree = create_sample_tree();
+        assert_eq!(tree.render(&TreeRenderer::new())?, tree.to_string());
+        assert_eq!(tree.render(&TreeRenderer::default())?, tree.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_render_with_formatter() -> Result<()> {
+        let tree = create_sample_tree();
+        tree.get_node_by_id(&4).unwrap().set_value(None)?;
+        let renderer = TreeRenderer::with_formatter(|id: &u32, value: Option<&u32>| match value {
+            Some(value) => format!("{id} ({value})"),
+            None => format!("{id}"),
+        });
+        assert_eq!(
+            tree.render(&renderer)?,
+            "Sample Tree\n***********\n1 (10)\n├── 2 (20)\n│   ├── 3 (30)\n│   └── 4\n└── 5 (50)\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_render_ascii_without_header() -> Result<()> {
+        let tree = create_sample_tree();
+        let renderer = TreeRenderer::with_formatter(|id: &u32, _: Option<&u32>| id.to_string())
+            .glyphs(TreeGlyphs::Ascii)
+            .header(false);
+        assert_eq!(
+            tree.render(&renderer)?,
+            "1\n|-- 2\n|   |-- 3\n|   `-- 4\n`-- 5\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_render_max_depth_and_children() -> Result<()> {
+        let tree = create_sample_tree();
+        let renderer = TreeRenderer::with_formatter(|id: &u32, _: Option<&u32>| id.to_string())
+            .header(false)
+            .max_depth(1);
+        assert_eq!(
+            tree.render(&renderer)?,
+            "1\n├── 2\n│   └── … 2 more\n└── 5\n"
+        );
+        let renderer = renderer.max_depth(0);
+        assert_eq!(tree.render(&renderer)?, "1\n└── … 2 more\n");
+        let renderer = TreeRenderer::with_formatter(|id: &u32, _: Option<&u32>| id.to_string())
+            .header(false)
+            .glyphs(TreeGlyphs::Ascii)
+            .max_children(1);
+        assert_eq!(
+            tree.render(&renderer)?,
+            "1\n|-- 2\n|   |-- 3\n|   `-- ... 1 more\n`-- ... 1 more\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_render_value_without_default() -> Result<()> {
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        struct Label(&'static str);
+
+        let mut tree = Tree::<u32, Label>::new(None);
+        let node_1 = tree.add_node(Node::new(1, Some(Label("root"))), None)?;
+        tree.add_node(Node::new(2, None), Some(&node_1))?;
+        let renderer = TreeRenderer::with_formatter(|id: &u32, value: Option<&Label>| {
+            format!("{id}: {}", value.map_or("-", |x| x.0))
+        });
+        assert_eq!(tree.render(&renderer)?, "1: root\n└── 2: -\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_render_empty_tree() -> Result<()> {
+        let tree = Tree::<u32, u32>::new(Some("Empty"));
+        assert_eq!(tree.render(&TreeRenderer::new())?, "Empty\n*****\n");
+        let renderer = TreeRenderer::<u32, u32>::new().header(false);
+        assert_eq!(tree.render(&renderer)?, "");
+        Ok(())
+    }
+
+    fn get_value_of(tree: &Tree<u32, u32>) -> impl Fn(&u32) -> Option<u32> + '_ {
+        |node_id| tree.get_node_by_id(node_id)?.get_value().ok()?
+    }
+
+    #[test]
+    fn test_tree_materialized_paths() -> Result<()> {
+        let tree = create_sample_tree();
+        tree.get_node_by_id(&4).unwrap().set_value(None)?;
+        let rows = tree.to_materialized_paths("/")?;
+        assert_eq!(
+            rows,
+            vec![
+                (1, "/1".to_string()),
+                (2, "/1/2".to_string()),
+                (3, "/1/2/3".to_string()),
+                (4, "/1/2/4".to_string()),
+                (5, "/1/5".to_string()),
+            ]
+        );
+        // Deepest rows first, keeping the relative order of siblings.
+        let mut shuffled = rows.clone();
+        shuffled.sort_by_key(|(_, path)| core::cmp::Reverse(path.len()));
+        let rebuilt: Tree<u32, u32> =
+            Tree::from_materialized_paths(shuffled, "/", get_value_of(&tree))?;
+        assert_eq!(rebuilt.to_materialized_paths("/")?, rows);
+        assert_eq!(rebuilt.get_node_by_id(&5).unwrap().get_value()?, Some(50));
+        assert_eq!(rebuilt.get_node_by_id(&4).unwrap().get_value()?, None);
+        assert_eq!(
+            tree.to_materialized_paths("::")?[2],
+            (3, "::1::2::3".to_string())
+        );
+        assert!(Tree::<u32, u32>::new(None)
+            .to_materialized_paths("/")?
+            .is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_from_materialized_paths_errors() {
+        let build = |rows: Vec<(u32, &str)>, separator: &str| {
+            let rows = rows.into_iter().map(|(id, path)| (id, path.to_string()));
+            Tree::<u32, u32>::from_materialized_paths(rows, separator, |_| None).unwrap_err()
+        };
+        assert_eq!(
+            build(vec![(1, "/1")], ""),
+            InvalidOperation("The separator must not be empty.".to_string())
+        );
+        assert_eq!(
+            build(vec![(1, "/1"), (2, "/1/3/2")], "/"),
+            InvalidOperation("The parent of node 2 at '/1/3/2' is missing.".to_string())
+        );
+        assert_eq!(
+            build(vec![(1, "/1"), (1, "/1/1")], "/"),
+            InvalidOperation("Duplicate node id 1.".to_string())
+        );
+        assert_eq!(
+            build(vec![(1, "/1"), (2, "/1/a"), (3, "/1/a")], "/"),
+            InvalidOperation("Duplicate path '/1/a'.".to_string())
+        );
+        assert_eq!(
+            build(vec![(1, "/1"), (2, "/2")], "/"),
+            RootNodeAlreadyPresent
+        );
+        assert_eq!(
+            build(vec![(1, "01"), (10, "01010")], "0"),
+            InvalidOperation("Node 10 contains the separator '0'.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_tree_materialized_paths_with_separator_in_ids() -> Result<()> {
+        let mut tree: Tree<&str, u32> = Tree::new(None);
+        let root = tree.add_node(Node::new("root", None), None)?;
+        tree.add_node(Node::new("a", None), Some(&root))?;
+        tree.add_node(Node::new("a/b", None), Some(&root))?;
+        assert_eq!(
+            tree.to_materialized_paths("/"),
+            Err(InvalidOperation(
+                "Node \"a/b\" contains the separator '/'.".to_string()
+            ))
+        );
+        assert_eq!(
+            tree.to_materialized_paths("::")?[2],
+            ("a/b", "::root::a/b".to_string())
+        );
+        assert_eq!(
+            tree.to_materialized_paths(""),
+            Err(InvalidOperation(
+                "The separator must not be empty.".to_string()
+            ))
+        );
+
+        // Split at its last separator, the path of "a/b" would make it a child of "a".
+        let rows = vec![
+            ("root", "/root".to_string()),
+            ("a", "/root/a".to_string()),
+            ("a/b", "/root/a/b".to_string()),
+        ];
+        assert_eq!(
+            Tree::<&str, u32>::from_materialized_paths(rows, "/", |_| None),
+            Err(InvalidOperation(
+                "Node \"a/b\" contains the separator '/'.".to_string()
+            ))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_import_ids_without_display() -> Result<()> {
+        // Tuples implement `Debug` but not `Display`.
+        let tree = Tree::<(u8, u8), u32>::from_closure_table(
+            vec![
+                ((0, 1), (0, 1), 0),
+                ((0, 2), (0, 2), 0),
+                ((0, 1), (0, 2), 1),
+            ],
+            |_| None,
+        )?;
+        assert_eq!(
+            tree.get_node_ref(&(0, 2)).unwrap().get_parent_id()?,
+            Some((0, 1))
+        );
+
+        let tree = Tree::<(u8, u8), u32>::from_nested_sets(
+            vec![((0, 1), 1, 4, 0), ((0, 2), 2, 3, 1)],
+            |_| None,
+        )?;
+        assert_eq!(tree.len(), 2);
+
+        assert_eq!(
+            Tree::<&str, u32>::from_nested_sets(vec![("a", 1, 2, 0), ("a", 3, 4, 0)], |_| None),
+            Err(InvalidOperation("Duplicate node id \"a\".".to_string()))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_subtree_and_render_ids_without_display() -> Result<()> {
+        let mut tree: Tree<(u8, u8), u32> = Tree::new(None);
+        let node_1 = tree.add_node(Node::new((0, 1), Some(1)), None)?;
+        let node_2 = tree.add_node(Node::new((0, 2), Some(2)), Some(&node_1))?;
+        tree.add_node(Node::new((0, 3), Some(3)), Some(&node_2))?;
+
+        let subtree = tree.get_subtree(&node_2, None)?;
+        assert_eq!(subtree.get_name(), Some("(0, 2)"));
+        assert_eq!(subtree.len(), 2);
+        assert_eq!(tree.get_subtree_owned(&node_2, None)?.len(), 2);
+
+        let renderer = TreeRenderer::with_formatter(|id: &(u8, u8), _: Option<&u32>| {
+            format!("{}.{}", id.0, id.1)
+        })
+        .header(false);
+        assert_eq!(tree.render(&renderer)?, "0.1\n└── 0.2\n    └── 0.3\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_nested_sets() -> Result<()> {
+        let tree = create_sample_tree();
+        let rows = tree.to_nested_sets()?;
+        assert_eq!(
+            rows,
+            vec![
+                (1, 1, 10, 0),
+                (2, 2, 7, 1),
+                (3, 3, 4, 2),
+                (4, 5, 6, 2),
+                (5, 8, 9, 1),
+            ]
+        );
+        let mut shuffled = rows.clone();
+        shuffled.reverse();
+        let rebuilt: Tree<u32, u32> = Tree::from_nested_sets(shuffled, get_value_of(&tree))?;
+        assert_eq!(rebuilt.to_nested_sets()?, rows);
+        assert_eq!(rebuilt.get_node_by_id(&5).unwrap().get_value()?, Some(50));
+        assert!(Tree::<u32, u32>::new(None).to_nested_sets()?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_from_nested_sets_errors() {
+        let build = |rows: Vec<(u32, usize, usize, usize)>| {
+            Tree::<u32, u32>::from_nested_sets(rows, |_| None).unwrap_err()
+        };
+        assert_eq!(
+            build(vec![(1, 1, 4, 0), (2, 3, 2, 1)]),
+            InvalidOperation("The bounds of node 2 are invalid.".to_string())
+        );
+        assert_eq!(
+            build(vec![(1, 1, 4, 0), (2, 2, 5, 1)]),
+            InvalidOperation(
+                "The bounds of node 2 overlap with the bounds of another node.".to_string()
+            )
+        );
+        assert_eq!(
+            build(vec![(1, 1, 4, 0), (2, 2, 3, 2)]),
+            InvalidOperation("The depth of node 2 does not match its bounds.".to_string())
+        );
+        assert_eq!(
+            build(vec![(1, 1, 4, 0), (1, 2, 3, 1)]),
+            InvalidOperation("Duplicate node id 1.".to_string())
+        );
+        assert_eq!(
+            build(vec![(1, 1, 2, 0), (2, 3, 4, 0)]),
+            RootNodeAlreadyPresent
+        );
+    }
+
+    #[test]
+    fn test_tree_closure_table() -> Result<()> {
+        let tree = create_sample_tree();
+        let rows = tree.to_closure_table()?;
+        assert_eq!(rows.len(), 5 + 4 + 2);
+        assert_eq!(
+            rows[..6],
+            [
+                (1, 1, 0),
+                (2, 2, 0),
+                (1, 2, 1),
+                (3, 3, 0),
+                (2, 3, 1),
+                (1, 3, 2)
+            ]
+        );
+        let mut shuffled = rows.clone();
+        shuffled.reverse();
+        let rebuilt: Tree<u32, u32> = Tree::from_closure_table(rows.clone(), get_value_of(&tree))?;
+        assert_eq!(rebuilt.to_closure_table()?, rows);
+        let rebuilt: Tree<u32, u32> = Tree::from_closure_table(shuffled, get_value_of(&tree))?;
+        assert_eq!(rebuilt.get_ancestor_ids(&4)?, vec![2, 1]);
+        assert_eq!(rebuilt.get_node_by_id(&3).unwrap().get_value()?, Some(30));
+        assert!(Tree::<u32, u32>::new(None).to_closure_table()?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_from_closure_table_errors() {
+        let build =
+            |rows: Vec<(u32, u32, usize)>| Tree::<u32, u32>::from_closure_table(rows, |_| None);
+        assert_eq!(
+            build(vec![(1, 3, 1), (2, 3, 1)]).unwrap_err(),
+            InvalidOperation("Node 3 has more than one parent.".to_string())
+        );
+        assert_eq!(
+            build(vec![(1, 2, 1), (1, 3, 2)]).unwrap_err(),
+            InvalidOperation("The parent of node 3 is missing.".to_string())
+        );
+        assert_eq!(
+            build(vec![(1, 1, 0), (2, 2, 0)]).unwrap_err(),
+            RootNodeAlreadyPresent
+        );
+    }
+
+    #[test]
+    fn test_local_and_thread_safe_trees_coexist() -> Result<()> {
+        fn assert_send_sync<V: Send + Sync>(_: &V) {}
+
+        let mut local_tree = LocalTree::<u32, u32>::new_in(None, RcStorage);
+        let root = local_tree.add_node(Node::new_in(1, Some(2), RcStorage), None)?;
+        local_tree.add_node(Node::new_in(2, Some(3), RcStorage), Some(&root))?;
+
+        let mut thread_safe_tree = ThreadSafeTree::<u32, u32>::new_in(None, ArcStorage);
+        let root = thread_safe_tree.add_node(Node::new_in(1, Some(2), ArcStorage), None)?;
+        thread_safe_tree.add_node(Node::new_in(2, Some(3), ArcStorage), Some(&root))?;
+        assert_send_sync(&thread_safe_tree);
+
+        assert_eq!(local_tree.to_string(), thread_safe_tree.to_string());
+        Ok(())
+    }
+
+    #[cfg(not(feature = "no_std"))]
+    #[test]
+    fn test_thread_safe_tree_across_threads() {
+        use std::sync::Mutex;
+
+        let tree = Arc::new(Mutex::new(ThreadSafeTree::<String, i32>::new_in(
+            Some("My Tree"),
+            ArcStorage,
+        )));
+        let thread_tree = tree.clone();
+        let handle = std::thread::spawn(move || {
+            let mut tree = thread_tree.lock().unwrap();
+            tree.add_node(Node::new_in("root".to_string(), Some(2), ArcStorage), None)
+                .unwrap()
+        });
+        assert_eq!(handle.join().unwrap(), "root".to_string());
+        assert_eq!(tree.lock().unwrap().get_nodes().len(), 1);
+    }
+
+    #[test]
+    fn test_shared_tree_snapshot_is_isolated_from_updates() {
+        let tree = SharedTree::new(create_sample_tree_in(ArcStorage));
+        let snapshot = tree.snapshot();
+        assert_eq!(snapshot.version(), 0);
+
+        tree.remove_node(&2, NodeRemovalStrategy::RemoveNodeAndChildren)
+            .unwrap();
+        tree.update(|tree| tree.get_node_by_id(&1).unwrap().set_value(Some(100)))
+            .unwrap();
+
+        assert_eq!(snapshot.get_nodes().len(), 5);
+        assert_eq!(
+            snapshot.get_node_by_id(&1).unwrap().get_value().unwrap(),
+            Some(10)
+        );
+        let latest = tree.snapshot();
+        assert_eq!(latest.version(), 2);
+        assert_eq!(tree.version(), 2);
+        assert_eq!(latest.get_name(), Some("Sample Tree"));
+        assert_eq!(
+            latest.traverse(&1, TraversalStrategy::PreOrder).unwrap(),
+            vec![1, 5]
+        );
+        assert_eq!(
+            latest.get_node_by_id(&1).unwrap().get_value().unwrap(),
+            Some(100)
+        );
+    }
+
+    #[test]
+    fn test_shared_tree_failed_update_is_not_published() {
+        let tree = SharedTree::new(create_sample_tree_in(ArcStorage));
+        let result = tree.update(|tree| {
+            tree.remove_node(&3, NodeRemovalStrategy::RetainChildren)?;
+            tree.add_node(Node::new_in(6, Some(60), ArcStorage), Some(&42))
+        });
+        assert_eq!(result, Err(NodeNotFound("42".to_string())));
+        assert_eq!(tree.version(), 0);
+        assert!(tree.snapshot().get_node_by_id(&3).is_some());
+    }
+
+    #[test]
+    fn test_shared_tree_add_subtree() {
+        let tree = SharedTree::new(create_sample_tree_in(ArcStorage));
+        let mut subtree = SubTree::new_in(None, ArcStorage);
+        let root = subtree
+            .add_node(Node::new_in(6, Some(60), ArcStorage), None)
+            .unwrap();
+        subtree
+            .add_node(Node::new_in(7, Some(70), ArcStorage), Some(&root))
+            .unwrap();
+        tree.add_subtree(&5, subtree).unwrap();
+        assert_eq!(
+            tree.snapshot()
+                .traverse(&1, TraversalStrategy::PreOrder)
+                .unwrap(),
+            vec![1, 2, 3, 4, 5, 6, 7]
+        );
+    }
+
+    #[cfg(not(feature = "no_std"))]
+    #[test]
+    fn test_shared_tree_readers_see_consistent_versions() {
+        let tree = Arc::new(SharedTree::new(create_sample_tree_in(ArcStorage)));
+        let writer_tree = tree.clone();
+        let writer = std::thread::spawn(move || {
+            for index in 0..50 {
+                writer_tree
+                    .update(|tree| {
+                        let parent = if index % 2 == 0 { 5 } else { 1 };
+                        tree.remove_node(&2, NodeRemovalStrategy::RemoveNodeAndChildren)?;
+                        tree.add_node(Node::new_in(2, Some(index), ArcStorage), Some(&parent))?;
+                        tree.add_node(Node::new_in(3, Some(30), ArcStorage), Some(&2))?;
+                        tree.add_node(Node::new_in(4, Some(40), ArcStorage), Some(&2))?;
+                        Ok(())
+                    })
+                    .unwrap();
+            }
+        });
+        let readers = (0..4)
+            .map(|_| {
+                let tree = tree.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..50 {
+                        let snapshot = tree.snapshot();
+                        let nodes = snapshot.traverse(&1, TraversalStrategy::PreOrder).unwrap();
+                        assert_eq!(nodes.len(), 5);
+                        assert_eq!(snapshot.get_nodes().len(), 5);
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        writer.join().unwrap();
+        readers.into_iter().for_each(|x| x.join().unwrap());
+        assert_eq!(tree.version(), 50);
+    }
+
+    #[test]
+    fn test_immutable_tree_updates_leave_original_untouched() {
+        let tree = ImmutableTree::from_tree(&create_sample_tree()).unwrap();
+        let copy = tree.clone();
+
+        let inserted = tree.insert(6, Some(60), Some(&5)).unwrap();
+        let updated = tree.set_value(&3, None).unwrap();
+        let moved = tree.move_node(&2, &5).unwrap();
+        let removed = tree
+            .remove(&2, NodeRemovalStrategy::RemoveNodeAndChildren)
+            .unwrap();
+
+        assert_eq!(tree, copy);
+        assert_eq!(tree.len(), 5);
+        assert_eq!(tree.get_value(&3).unwrap(), Some(&30));
+        assert_eq!(tree.get_children_ids(&1).unwrap(), &[2, 5]);
+
+        assert_eq!(inserted.get_children_ids(&5).unwrap(), &[6]);
+        assert_eq!(updated.get_value(&3).unwrap(), None);
+        assert_eq!(
+            moved.traverse(&1, TraversalStrategy::PreOrder).unwrap(),
+            vec![1, 5, 2, 3, 4]
+        );
+        assert_eq!(moved.get_parent_id(&2).unwrap(), Some(&5));
+        assert_eq!(removed.len(), 2);
+        assert!(!removed.contains(&3));
+        assert_ne!(tree, removed);
+    }
+
+    #[test]
+    fn test_immutable_tree_remove_retain_children() {
+        let tree = ImmutableTree::from_tree(&create_sample_tree()).unwrap();
+        let removed = tree
+            .remove(&2, NodeRemovalStrategy::RetainChildren)
+            .unwrap();
+        assert_eq!(removed.get_children_ids(&1).unwrap(), &[5, 3, 4]);
+        assert_eq!(removed.get_parent_id(&3).unwrap(), Some(&1));
+        assert_eq!(
+            tree.remove(&1, NodeRemovalStrategy::RetainChildren),
+            Err(InvalidOperation(String::from(
+                "Cannot remove root node with RetainChildren strategy"
+            )))
+        );
+        let empty = tree
+            .remove(&1, NodeRemovalStrategy::RemoveNodeAndChildren)
+            .unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.get_root_id(), None);
+    }
+
+    #[test]
+    fn test_immutable_tree_errors() {
+        let tree = ImmutableTree::from_tree(&create_sample_tree()).unwrap();
+        assert_eq!(tree.insert(6, None, None), Err(RootNodeAlreadyPresent));
+        assert_eq!(
+            tree.insert(6, None, Some(&42)),
+            Err(NodeNotFound("42".to_string()))
+        );
+        assert_eq!(
+            tree.insert(3, None, Some(&1)),
+            Err(InvalidOperation(
+                "Node 3 is already in the tree".to_string()
+            ))
+        );
+        assert_eq!(
+            tree.move_node(&2, &4),
+            Err(InvalidOperation(
+                "Cannot move node 2 under itself or one of its descendants".to_string()
+            ))
+        );
+        assert_eq!(
+            tree.set_value(&42, None),
+            Err(NodeNotFound("42".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_immutable_tree_matches_tree() {
+        let tree = ImmutableTree::from_tree(&create_sample_tree()).unwrap();
+        let mutable: Tree<u32, u32> = tree.to_tree_in(DefaultStorage::default()).unwrap();
+        for order in [
+            TraversalStrategy::PreOrder,
+            TraversalStrategy::PostOrder,
+            TraversalStrategy::InOrder,
+        ] {
+            assert_eq!(
+                tree.traverse(&1, order).unwrap(),
+                mutable.traverse(&1, order).unwrap()
+            );
+        }
+        assert_eq!(format!("{tree}"), format!("{mutable}"));
+        assert_eq!(ImmutableTree::from_tree(&mutable).unwrap(), tree);
+    }
+
+    #[test]
+    fn test_immutable_tree_keeps_many_versions() {
+        // Insert and remove nodes in a scrambled order to exercise the rebalancing of the node map.
+        let mut versions = vec![ImmutableTree::<u32, u32>::new(None)
+            .insert(0, None, None)
+            .unwrap()];
+        let ids = (1..200u32).map(|x| (x * 73) % 199 + 1).collect::<Vec<_>>();
+        for id in ids.iter() {
+            let tree = versions
+                .last()
+                .unwrap()
+                .insert(*id, Some(*id), Some(&0))
+                .unwrap();
+            versions.push(tree);
+        }
+        for id in ids.iter().step_by(3) {
+            let tree = versions
+                .last()
+                .unwrap()
+                .remove(id, NodeRemovalStrategy::RetainChildren)
+                .unwrap();
+            versions.push(tree);
+        }
+        for (index, tree) in versions.iter().enumerate().take(ids.len() + 1) {
+            assert_eq!(tree.len(), index + 1);
+            assert_eq!(tree.get_children_ids(&0).unwrap(), &ids[..index]);
+        }
+        let last = versions.last().unwrap();
+        let removed = ids.iter().step_by(3).count();
+        assert_eq!(last.len(), ids.len() + 1 - removed);
+        for (index, id) in ids.iter().enumerate() {
+            assert_eq!(last.contains(id), index % 3 != 0);
+        }
+    }
+
+    #[test]
+    fn test_immutable_tree_is_send_and_sync() {
+        fn assert_send_sync<V: Send + Sync>(_: &V) {}
+        assert_send_sync(&ImmutableTree::from_tree(&create_sample_tree()).unwrap());
+    }
+
+    #[test]
+    fn test_tree_clone_shares_nodes() {
+        let tree = create_sample_tree();
+        let mut clone = tree.clone();
+        clone
+            .get_node_by_id(&3)
+            .unwrap()
+            .set_value(Some(300))
+            .unwrap();
+        assert_eq!(
+            tree.get_node_by_id(&3).unwrap().get_value().unwrap(),
+            Some(300)
+        );
+
+        // The list of nodes is not shared.
+        clone.add_node(Node::new(6, Some(60)), Some(&5)).unwrap();
+        assert!(tree.get_node_by_id(&6).is_none());
+
+        let subtree = tree.get_subtree(&2, None).unwrap();
+        subtree.get_node_by_id(&4).unwrap().set_value(None).unwrap();
+        assert_eq!(tree.get_node_by_id(&4).unwrap().get_value().unwrap(), None);
+    }
+
+    #[test]
+    fn test_tree_deep_clone() {
+        let tree = create_sample_tree();
+        let mut copy = tree.deep_clone().unwrap();
+        assert_eq!(copy, tree);
+        assert_eq!(
+            copy.traverse(&1, TraversalStrategy::PreOrder).unwrap(),
+            vec![1, 2, 3, 4, 5]
+        );
+
+        copy.get_node_by_id(&3)
+            .unwrap()
+            .set_value(Some(300))
+            .unwrap();
+        copy.remove_node(&2, NodeRemovalStrategy::RetainChildren)
+            .unwrap();
+        assert_eq!(
+            tree.get_node_by_id(&3).unwrap().get_value().unwrap(),
+            Some(30)
+        );
+        assert_eq!(
+            tree.get_node_by_id(&1).unwrap().get_children_ids().unwrap(),
+            vec![2, 5]
+        );
+        assert_eq!(
+            tree.get_node_by_id(&3).unwrap().get_parent_id().unwrap(),
+            Some(2)
+        );
+        assert_eq!(
+            copy.get_node_by_id(&1).unwrap().get_children_ids().unwrap(),
+            vec![5, 3, 4]
+        );
+    }
+
+    #[test]
+    fn test_tree_get_subtree_owned() {
+        let tree = create_sample_tree();
+        let subtree = tree.get_subtree_owned(&2, None).unwrap();
+        assert_eq!(subtree.get_name(), Some("2"));
+        assert_eq!(subtree.get_root_node(), tree.get_node_by_id(&2));
+        assert_eq!(
+            subtree.get_root_node().unwrap().get_parent_id().unwrap(),
+            None
+        );
+        assert_eq!(subtree.get_height().unwrap(), 1);
+
+        subtree.get_node_by_id(&4).unwrap().set_value(None).unwrap();
+        assert_eq!(
+            tree.get_node_by_id(&4).unwrap().get_value().unwrap(),
+            Some(40)
+        );
+        assert_eq!(
+            tree.get_node_by_id(&2).unwrap().get_parent_id().unwrap(),
+            Some(1)
+        );
+
+        let limited = tree.get_subtree_owned(&1, Some(1)).unwrap();
+        assert_eq!(limited.get_nodes().len(), 3);
+        assert_eq!(
+            limited
+                .get_node_by_id(&2)
+                .unwrap()
+                .get_children_ids()
+                .unwrap(),
+            Vec::<u32>::new()
+        );
+    }
+
+    /// Create a thread safe tree with four levels where each node has `level + 2` children.
+    #[cfg(not(feature = "no_std"))]
+    fn create_parallel_tree() -> ThreadSafeTree<u32, u32> {
+        let mut tree = ThreadSafeTree::new_in(Some("Parallel"), ArcStorage);
+        tree.add_node(Node::new_in(0, Some(0), ArcStorage), None)
+            .unwrap();
+        let mut next_id = 1;
+        let mut level = vec![0];
+        for depth in 0..4 {
+            let mut next_level = vec![];
+            for parent_id in level {
+                for _ in 0..depth + 2 {
+                    let value = (next_id % 2 == 0).then_some(next_id);
+                    tree.add_node(Node::new_in(next_id, value, ArcStorage), Some(&parent_id))
+                        .unwrap();
+                    next_level.push(next_id);
+                    next_id += 1;
+                }
+            }
+            level = next_level;
+        }
+        tree
+    }
+
+    #[cfg(not(feature = "no_std"))]
+    #[test]
+    fn test_tree_par_traverse_matches_traverse() {
+        let tree = create_parallel_tree();
+        for order in [
+            TraversalStrategy::PreOrder,
+            TraversalStrategy::PostOrder,
+            TraversalStrategy::InOrder,
+        ] {
+            let expected = tree.traverse(&0, order).unwrap();
+            for threads in [0, 1, 2, 3, 7, 64] {
+                assert_eq!(tree.par_traverse(&0, order, threads).unwrap(), expected);
+            }
+            assert_eq!(
+                tree.par_traverse(&3, order, 4).unwrap(),
+                tree.traverse(&3, order).unwrap()
+            );
+        }
+        assert_eq!(
+            tree.par_traverse(&1000, TraversalStrategy::PreOrder, 4),
+            Err(NodeNotFound("1000".to_string()))
+        );
+    }
+
+    #[cfg(not(feature = "no_std"))]
+    #[test]
+    fn test_tree_par_fold_up() {
+        let tree = create_parallel_tree();
+        let node_count = tree.get_nodes().len();
+        let expected_sum = tree
+            .get_nodes()
+            .iter()
+            .map(|x| x.get_value().unwrap().unwrap_or_default())
+            .sum::<u32>();
+        for threads in [1, 2, 5, 32] {
+            let (count, sum) = tree
+                .par_fold_up(&0, threads, |_, value, children: Vec<(usize, u32)>| {
+                    children
+                        .iter()
+                        .fold((1, value.copied().unwrap_or_default()), |x, y| {
+                            (x.0 + y.0, x.1 + y.1)
+                        })
+                })
+                .unwrap();
+            assert_eq!((count, sum), (node_count, expected_sum));
+
+            // Folding the ids in child order gives the post-order traversal.
+            let ids = tree
+                .par_fold_up(&0, threads, |id, _, children: Vec<Vec<u32>>| {
+                    let mut ids = children.concat();
+                    ids.push(*id);
+                    ids
+                })
+                .unwrap();
+            assert_eq!(
+                ids,
+                tree.traverse(&0, TraversalStrategy::PostOrder).unwrap()
+            );
+        }
+    }
+
+    #[cfg(not(feature = "no_std"))]
+    #[test]
+    fn test_tree_par_find() {
+        let tree = create_parallel_tree();
+        let pre_order = tree.traverse(&0, TraversalStrategy::PreOrder).unwrap();
+        let is_match = |id: &u32, value: Option<&u32>| value.is_some() && id % 7 == 3;
+        let expected = pre_order
+            .iter()
+            .find(|x| {
+                let value = tree.get_node_by_id(x).unwrap().get_value().unwrap();
+                is_match(x, value.as_ref())
+            })
+            .cloned();
+        assert!(expected.is_some());
+        for threads in [1, 2, 3, 16] {
+            assert_eq!(tree.par_find(&0, threads, is_match).unwrap(), expected);
+            assert_eq!(tree.par_find(&0, threads, |_, _| false).unwrap(), None);
+        }
+    }
+
+    #[test]
+    fn test_tree_get_node_ref() {
+        let tree = create_sample_tree();
+        let node = tree.get_node_ref(&2).unwrap();
+        assert_eq!(node, &tree.get_node_by_id(&2).unwrap());
+        assert_eq!(*node.value_ref(), Some(20));
+        assert_eq!(&*node.children_ref(), &[3, 4]);
+        assert!(tree.get_node_ref(&42).is_none());
+    }
+
+    #[test]
+    fn test_tree_borrowed_id_lookups() -> Result<()> {
+        let mut tree: Tree<String, i32> = Tree::new(None);
+        tree.add_node(Node::new("a".to_string(), Some(1)), None)?;
+        tree.add_node(Node::new("b".to_string(), Some(2)), Some(&"a".to_string()))?;
+        tree.add_node(Node::new("c".to_string(), Some(3)), Some(&"b".to_string()))?;
+
+        assert_eq!(tree.get_node_by_id("b").unwrap().get_value()?, Some(2));
+        assert_eq!(tree.get_node_depth("c")?, 2);
+        assert_eq!(tree.get_ancestor_ids("c")?, vec!["b", "a"]);
+        assert_eq!(
+            tree.traverse("a", TraversalStrategy::PostOrder)?,
+            vec!["c", "b", "a"]
+        );
+        assert_eq!(tree.get_sibling_ids("b", true)?, vec!["b"]);
+        assert_eq!(
+            tree.get_node_height("x"),
+            Err(NodeNotFound("\"x\"".to_string()))
+        );
+        tree.remove_node("b", NodeRemovalStrategy::RetainChildren)?;
+        assert_eq!(
+            tree.get_node_by_id("a").unwrap().get_children_ids()?,
+            vec!["c"]
+        );
+
+        let immutable = ImmutableTree::from_tree(&tree)?;
+        assert!(immutable.contains("c"));
+        assert_eq!(immutable.get_parent_id("c")?, Some(&"a".to_string()));
+        assert_eq!(
+            immutable.traverse("a", TraversalStrategy::PreOrder)?,
+            vec!["a", "c"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_with_ids_without_display() -> Result<()> {
+        #[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        struct Id(u8, u8);
+
+        let mut tree: Tree<Id, i32> = Tree::new(None);
+        let root = tree.add_node(Node::new(Id(0, 0), Some(1)), None)?;
+        let child = tree.add_node(Node::new(Id(1, 0), Some(2)), Some(&root))?;
+        tree.add_node(Node::new(Id(1, 1), Some(3)), Some(&root))?;
+
+        assert_eq!(tree.get_height()?, 1);
+        assert_eq!(tree.get_sibling_ids(&child, false)?, vec![Id(1, 1)]);
+        assert_eq!(
+            tree.traverse(&root, TraversalStrategy::PreOrder)?,
+            vec![Id(0, 0), Id(1, 0), Id(1, 1)]
+        );
+        assert_eq!(
+            tree.get_node_depth(&Id(2, 0)),
+            Err(NodeNotFound("Id(2, 0)".to_string()))
+        );
+        tree.remove_node(&child, NodeRemovalStrategy::RemoveNodeAndChildren)?;
+        assert_eq!(tree.deep_clone()?.get_nodes().len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_attributes() -> Result<()> {
+        let mut tree = create_sample_tree();
+        assert!(tree.attributes::<bool>().is_none());
+        assert_eq!(tree.set_attribute(&2, true)?, None);
+        assert_eq!(tree.set_attribute(&2, false)?, Some(true));
+        tree.set_attribute(&3, true)?;
+        tree.set_attribute(&5, true)?;
+        tree.set_attribute(&3, String::from("selected"))?;
+        assert_eq!(
+            tree.set_attribute(&42, true),
+            Err(NodeNotFound("42".to_string()))
+        );
+
+        let attributes = tree.attributes::<bool>().unwrap();
+        assert_eq!(attributes.len(), 3);
+        assert_eq!(attributes.get(&2), Some(&false));
+        assert_eq!(
+            tree.attributes::<String>()
+                .unwrap()
+                .get(&3)
+                .map(String::as_str),
+            Some("selected")
+        );
+        // Attributes are not part of the identity of a tree.
+        assert_eq!(tree, create_sample_tree());
+
+        let subtree = tree.get_subtree_owned(&2, None)?;
+        let subtree_attributes = subtree.attributes::<bool>().unwrap();
+        assert_eq!(
+            subtree_attributes.iter().collect::<Vec<_>>(),
+            vec![(&2, &false), (&3, &true)]
+        );
+
+        let mut copy = tree.clone();
+        copy.set_attribute(&1, true)?;
+        assert!(!tree.attributes::<bool>().unwrap().contains(&1));
+        assert_eq!(
+            tree.deep_clone()?.attributes::<bool>(),
+            tree.attributes::<bool>()
+        );
+
+        tree.remove_node(&2, NodeRemovalStrategy::RemoveNodeAndChildren)?;
+        assert_eq!(
+            tree.attributes::<bool>()
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>(),
+            vec![(&5, &true)]
+        );
+        assert!(tree.attributes::<String>().unwrap().is_empty());
+
+        tree.add_subtree(&5, subtree)?;
+        assert_eq!(tree.attributes::<bool>().unwrap().len(), 3);
+        assert_eq!(tree.remove_attribute::<bool, _>(&3), Some(true));
+        assert_eq!(tree.remove_attribute::<bool, _>(&3), None);
+
+        let attributes = tree.take_attributes::<bool>().unwrap();
+        assert!(tree.attributes::<bool>().is_none());
+        let mut unknown = NodeAttributes::new();
+        unknown.insert(42, true);
+        assert_eq!(
+            tree.insert_attributes(unknown),
+            Err(NodeNotFound("42".to_string()))
+        );
+        assert_eq!(tree.insert_attributes(attributes.clone())?, None);
+        assert_eq!(tree.attributes::<bool>(), Some(&attributes));
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_attributes_retain_children() -> Result<()> {
+        let mut tree = create_sample_tree();
+        tree.set_attribute(&2, 'a')?;
+        tree.set_attribute(&3, 'b')?;
+        tree.remove_node(&2, NodeRemovalStrategy::RetainChildren)?;
+        let attributes = tree.attributes::<char>().unwrap();
+        assert!(!attributes.contains(&2));
+        assert_eq!(attributes.get(&3), Some(&'b'));
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_edges() -> Result<()> {
+        let mut tree: Tree<u32, u32, DefaultStorage, u32> =
+            create_sample_tree_in(DefaultStorage::default());
+        for node_id in [2, 3, 5] {
+            tree.set_edge(&node_id, node_id)?;
+        }
+        assert_eq!(tree.get_edge(&2)?, Some(&2));
+        assert_eq!(tree.get_edge(&4)?, None);
+        assert_eq!(tree.get_edge(&42), Err(NodeNotFound("42".to_string())));
+        assert_eq!(
+            tree.set_edge(&1, 1),
+            Err(InvalidOperation(
+                "Node 1 has no parent, so it has no edge".to_string()
+            ))
+        );
+        assert_eq!(tree.set_edge(&4, 4)?, None);
+        assert_eq!(tree.set_edge(&4, 6)?, Some(4));
+        assert_eq!(tree.remove_edge(&4)?, Some(6));
+        assert_eq!(tree.remove_edge(&4)?, None);
+
+        assert_eq!(tree.get_weighted_depth(&1)?, 0);
+        assert_eq!(tree.get_weighted_depth(&3)?, 5);
+        assert_eq!(tree.get_weighted_depth(&4)?, 2);
+        assert_eq!(tree.get_weighted_path_length(&3, &5)?, 10);
+        assert_eq!(tree.get_weighted_path_length(&3, &2)?, 3);
+        assert_eq!(tree.get_weighted_path_length(&1, &1)?, 0);
+
+        #[cfg(not(feature = "print_node_id"))]
+        assert_eq!(
+            tree.display_edges().to_string(),
+            "Sample Tree\n***********\n10\n├── 20 [2]\n│   ├── 30 [3]\n│   └── 40\n└── 50 [5]\n"
+        );
+        // The edges are part of the identity of a tree.
+        let mut other: Tree<u32, u32, DefaultStorage, u32> =
+            create_sample_tree_in(DefaultStorage::default());
+        for node_id in [2, 3, 5] {
+            other.set_edge(&node_id, node_id)?;
+        }
+        assert_eq!(other, tree);
+        other.set_edge(&5, 1)?;
+        assert_ne!(other, tree);
+
+        let subtree = tree.get_subtree_owned(&2, None)?;
+        assert_eq!(subtree.get_edge(&2)?, None);
+        assert_eq!(subtree.get_edge(&3)?, Some(&3));
+        assert_eq!(tree.clone().get_edge(&3)?, Some(&3));
+        assert_eq!(tree.deep_clone()?.get_edge(&5)?, Some(&5));
+
+        tree.remove_node(&2, NodeRemovalStrategy::RemoveNodeAndChildren)?;
+        assert_eq!(tree.get_weighted_depth(&5)?, 5);
+        tree.add_subtree(&5, subtree)?;
+        assert_eq!(tree.get_weighted_depth(&3)?, 8);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_edges_retain_children() -> Result<()> {
+        let mut tree: Tree<u32, u32, DefaultStorage, u32> =
+            create_sample_tree_in(DefaultStorage::default());
+        for node_id in [2, 3, 5] {
+            tree.set_edge(&node_id, node_id)?;
+        }
+        tree.remove_node(&2, NodeRemovalStrategy::RetainChildren)?;
+        assert_eq!(tree.get_edge(&3)?, Some(&3));
+        assert_eq!(tree.get_weighted_depth(&3)?, 3);
+        assert_eq!(tree.get_weighted_depth(&4)?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_add_value() -> Result<()> {
+        let mut tree: Tree<u32, u32> = Tree::new(None);
+        assert_eq!(
+            tree.add_value(Some(1), None),
+            Err(InvalidOperation("The tree has no id generator".to_string()))
+        );
+        tree.set_id_generator(SequentialIdGenerator::default());
+        let root = tree.add_value(Some(10), None)?;
+        tree.add_node(Node::new(2, Some(20)), Some(&root))?;
+        // The id 2 is already used, so it is skipped.
+        assert_eq!(tree.add_value(Some(30), Some(&root))?, 3);
+        assert_eq!(
+            tree.add_value(None, Some(&42)),
+            Err(NodeNotFound("42".to_string()))
+        );
+        assert_eq!(tree.add_value(None, None), Err(RootNodeAlreadyPresent));
+        assert_eq!(tree.add_value(None, Some(&root))?, 4);
+
+        // Clones share the generator of the tree, so their ids do not collide.
+        let mut clone = tree.clone();
+        assert_eq!(clone.add_value(Some(50), Some(&root))?, 5);
+        assert_eq!(tree.add_value(Some(60), Some(&root))?, 6);
+        let mut subtree = tree.get_subtree_owned(&3, None)?;
+        assert_eq!(subtree.add_value(Some(70), Some(&3))?, 7);
+
+        // A generator that only repeats used ids is given up on.
+        tree.set_id_generator(SequentialIdGenerator::new(2, 0));
+        assert_eq!(
+            tree.add_value(None, Some(&root)),
+            Err(InvalidOperation(
+                "The id generator of the tree did not produce an unused id".to_string()
+            ))
+        );
+        assert_eq!(tree.get_nodes().len(), 5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_id_generators_are_independent() -> Result<()> {
+        let mut tree: Tree<u64, u32> = Tree::new(None);
+        let mut other: Tree<u64, u32> = Tree::new(None);
+        tree.set_id_generator(SeededIdGenerator::new(7));
+        other.set_id_generator(SeededIdGenerator::new(7));
+        let root = tree.add_value(Some(1), None)?;
+        let child = tree.add_value(Some(2), Some(&root))?;
+        assert_ne!(root, child);
+        assert_eq!(other.add_value(Some(1), None)?, root);
+
+        let mut generator = SequentialIdGenerator::new(10i64, -5);
+        assert_eq!(
+            [
+                generator.next_id(),
+                generator.next_id(),
+                generator.next_id()
+            ],
+            [10, 5, 0]
+        );
+        Ok(())
+    }
+
+    #[cfg(not(feature = "no_std"))]
+    #[test]
+    fn test_tree_random_and_epoch_id_generators() -> Result<()> {
+        let mut tree: Tree<u128, u32> = Tree::new(None);
+        tree.set_id_generator(RandomIdGenerator::new());
+        let root = tree.add_value(Some(1), None)?;
+        tree.set_id_generator(EpochIdGenerator::new());
+        let child = tree.add_value(Some(2), Some(&root))?;
+        let grandchild = tree.add_value(Some(3), Some(&child))?;
+        assert!(grandchild > child);
+        assert_eq!(tree.get_nodes().len(), 3);
+        Ok(())
+    }
+
+    fn create_colliding_subtree() -> SubTree<u32, u32> {
+        let mut subtree = SubTree::new(None);
+        let node_6 = subtree.add_node(Node::new(6, Some(60)), None).unwrap();
+        let node_3 = subtree
+            .add_node(Node::new(3, Some(300)), Some(&node_6))
+            .unwrap();
+        subtree
+            .add_node(Node::new(7, Some(70)), Some(&node_3))
+            .unwrap();
+        subtree
+            .add_node(Node::new(8, Some(80)), Some(&node_6))
+            .unwrap();
+        subtree.set_attribute(&3, true).unwrap();
+        subtree
+    }
+
+    #[test]
+    fn test_tree_add_subtree_with_fail() -> Result<()> {
+        let mut tree = create_sample_tree();
+        assert_eq!(
+            tree.add_subtree_with(&5, create_colliding_subtree(), CollisionPolicy::Fail),
+            Err(InvalidOperation(
+                "Node 3 of the subtree is already in the tree".to_string()
+            ))
+        );
+        assert_eq!(tree, create_sample_tree());
+        assert_eq!(
+            tree.add_subtree_with(&42, create_colliding_subtree(), CollisionPolicy::Fail),
+            Err(NodeNotFound("42".to_string()))
+        );
+
+        let mut subtree = SubTree::new(None);
+        subtree.add_node(Node::new(6, Some(60)), None)?;
+        let ids = tree.add_subtree_with(&5, subtree, CollisionPolicy::Fail)?;
+        assert_eq!(ids.into_iter().collect::<Vec<_>>(), vec![(6, 6)]);
+        assert_eq!(
+            tree.get_node_by_id(&5).unwrap().get_children_ids()?,
+            vec![6]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_add_subtree_with_skip() -> Result<()> {
+        let mut tree = create_sample_tree();
+        let ids = tree.add_subtree_with(&5, create_colliding_subtree(), CollisionPolicy::Skip)?;
+        assert_eq!(ids.into_iter().collect::<Vec<_>>(), vec![(6, 6), (8, 8)]);
+        assert_eq!(
+            tree.traverse(&1, TraversalStrategy::PreOrder)?,
+            vec![1, 2, 3, 4, 5, 6, 8]
+        );
+        assert_eq!(tree.get_node_by_id(&3).unwrap().get_value()?, Some(30));
+        assert!(tree.attributes::<bool>().unwrap().is_empty());
+
+        let mut subtree = SubTree::new(None);
+        subtree.add_node(Node::new(2, Some(200)), None)?;
+        assert_eq!(
+            tree.add_subtree_with(&5, subtree, CollisionPolicy::Skip),
+            Err(InvalidOperation(
+                "Node 2, the root of the subtree, is already in the tree".to_string()
+            ))
+        );
+        assert_eq!(tree.get_node_by_id(&2).unwrap().get_value()?, Some(20));
+        assert_eq!(
+            tree.get_node_by_id(&5).unwrap().get_children_ids()?,
+            vec![6]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_add_subtree_with_overwrite() -> Result<()> {
+        let mut tree = create_sample_tree();
+        let ids =
+            tree.add_subtree_with(&5, create_colliding_subtree(), CollisionPolicy::Overwrite)?;
+        assert_eq!(ids.len(), 4);
+        assert_eq!(tree.get_node_by_id(&3).unwrap().get_value()?, Some(300));
+        assert_eq!(tree.get_node_by_id(&3).unwrap().get_parent_id()?, Some(6));
+        assert_eq!(
+            tree.get_node_by_id(&3).unwrap().get_children_ids()?,
+            vec![7]
+        );
+        assert_eq!(
+            tree.get_node_by_id(&2).unwrap().get_children_ids()?,
+            vec![4]
+        );
+        assert_eq!(tree.get_nodes().len(), 8);
+        assert_eq!(tree.attributes::<bool>().unwrap().get(&3), Some(&true));
+
+        // The children of an overwritten node are removed with it.
+        let mut tree = create_sample_tree();
+        tree.add_node(Node::new(9, Some(90)), Some(&3))?;
+        tree.set_attribute(&9, false)?;
+        tree.add_subtree_with(&5, create_colliding_subtree(), CollisionPolicy::Overwrite)?;
+        assert!(tree.get_node_by_id(&9).is_none());
+        assert_eq!(tree.attributes::<bool>().unwrap().get(&9), None);
+        assert_eq!(
+            tree.get_node_by_id(&3).unwrap().get_children_ids()?,
+            vec![7]
+        );
+        assert_eq!(
+            tree.traverse(&1, TraversalStrategy::PreOrder)?,
+            vec![1, 2, 4, 5, 6, 3, 7, 8]
+        );
+
+        let mut subtree = SubTree::new(None);
+        subtree.add_node(Node::new(2, Some(200)), None)?;
+        assert_eq!(
+            tree.add_subtree_with(&4, subtree, CollisionPolicy::Overwrite),
+            Err(InvalidOperation(
+                "Node 2 cannot be overwritten because the subtree is added below it".to_string()
+            ))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_add_subtree_with_remap() -> Result<()> {
+        let mut tree = create_sample_tree();
+        assert_eq!(
+            tree.add_subtree_with(&5, create_colliding_subtree(), CollisionPolicy::Remap),
+            Err(InvalidOperation("The tree has no id generator".to_string()))
+        );
+        // The generator starts at an id used by the subtree, which is skipped.
+        tree.set_id_generator(SequentialIdGenerator::new(7, 1));
+        let ids = tree.add_subtree_with(&5, create_colliding_subtree(), CollisionPolicy::Remap)?;
+        assert_eq!(
+            ids.into_iter().collect::<Vec<_>>(),
+            vec![(3, 9), (6, 6), (7, 7), (8, 8)]
+        );
+        assert_eq!(
+            tree.traverse(&5, TraversalStrategy::PreOrder)?,
+            vec![5, 6, 9, 7, 8]
+        );
+        assert_eq!(tree.get_node_by_id(&7).unwrap().get_parent_id()?, Some(9));
+        assert_eq!(tree.get_node_by_id(&9).unwrap().get_value()?, Some(300));
+        assert_eq!(tree.get_node_by_id(&3).unwrap().get_value()?, Some(30));
+        let attributes = tree.attributes::<bool>().unwrap();
+        assert_eq!(attributes.iter().collect::<Vec<_>>(), vec![(&9, &true)]);
+
+        let mut tree = create_sample_tree();
+        tree.set_id_generator(SequentialIdGenerator::new(1, 0));
+        assert_eq!(
+            tree.add_subtree_with(&5, create_colliding_subtree(), CollisionPolicy::Remap),
+            Err(InvalidOperation(
+                "The id generator of the tree did not produce an unused id".to_string()
+            ))
+        );
+        assert_eq!(tree, create_sample_tree());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_add_subtree_with_remap_edges() -> Result<()> {
+        let mut tree: Tree<u32, u32, DefaultStorage, u32> =
+            create_sample_tree_in(DefaultStorage::default());
+        for node_id in [2, 3, 5] {
+            tree.set_edge(&node_id, node_id)?;
+        }
+        tree.set_id_generator(SequentialIdGenerator::new(10, 1));
+        let mut subtree = tree.get_subtree_owned(&2, None)?;
+        subtree.set_edge(&4, 4)?;
+        let ids = tree.add_subtree_with(&5, subtree, CollisionPolicy::Remap)?;
+        assert_eq!(
+            ids.into_iter().collect::<Vec<_>>(),
+            vec![(2, 10), (3, 11), (4, 12)]
+        );
+        assert_eq!(tree.get_edge(&10)?, None);
+        assert_eq!(tree.get_edge(&11)?, Some(&3));
+        assert_eq!(tree.get_edge(&12)?, Some(&4));
+        assert_eq!(tree.get_edge(&4)?, None);
+        assert_eq!(tree.get_weighted_depth(&11)?, 8);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_add_subtree_with_keeps_the_source_tree() -> Result<()> {
+        let source = create_sample_tree();
+        let mut tree = create_sample_tree();
+        tree.set_id_generator(SequentialIdGenerator::new(10, 1));
+        let ids =
+            tree.add_subtree_with(&5, source.get_subtree(&1, None)?, CollisionPolicy::Remap)?;
+        assert_eq!(
+            ids.into_iter().collect::<Vec<_>>(),
+            vec![(1, 10), (2, 11), (3, 12), (4, 13), (5, 14)]
+        );
+        assert_eq!(source, create_sample_tree());
+        assert_eq!(
+            source.traverse(&1, TraversalStrategy::PreOrder)?,
+            vec![1, 2, 3, 4, 5]
+        );
+
+        let mut source: Tree<u32, u32> = Tree::new(None);
+        let node_20 = source.add_node(Node::new(20, Some(200)), None)?;
+        source.add_node(Node::new(3, Some(300)), Some(&node_20))?;
+        source.add_node(Node::new(21, Some(210)), Some(&node_20))?;
+        let mut tree = create_sample_tree();
+        tree.add_subtree_with(&5, source.get_subtree(&20, None)?, CollisionPolicy::Skip)?;
+        assert_eq!(
+            source.traverse(&20, TraversalStrategy::PreOrder)?,
+            vec![20, 3, 21]
+        );
+        assert_eq!(
+            source.get_node_by_id(&3).unwrap().get_parent_id()?,
+            Some(20)
+        );
+
+        for policy in [
+            CollisionPolicy::Fail,
+            CollisionPolicy::Skip,
+            CollisionPolicy::Overwrite,
+            CollisionPolicy::Remap,
+        ] {
+            let source = create_sample_tree();
+            let mut tree: Tree<u32, u32> = Tree::new(None);
+            tree.set_id_generator(SequentialIdGenerator::new(10, 1));
+            let node_100 = tree.add_node(Node::new(100, Some(1000)), None)?;
+            let ids = tree.add_subtree_with(&node_100, source.get_subtree(&2, None)?, policy)?;
+            assert_eq!(ids.into_keys().collect::<Vec<_>>(), vec![2, 3, 4]);
+            assert_eq!(
+                tree.traverse(&100, TraversalStrategy::PreOrder)?,
+                vec![100, 2, 3, 4]
+            );
+            assert_eq!(tree.get_node_by_id(&2).unwrap().get_parent_id()?, Some(100));
+            tree.get_node_by_id(&3).unwrap().set_value(Some(0))?;
+            assert_eq!(source, create_sample_tree());
+            assert_eq!(source.get_root_node().unwrap().get_node_id()?, 1);
+            assert_eq!(source.get_node_by_id(&2).unwrap().get_parent_id()?, Some(1));
+        }
+
+        let source = create_sample_tree();
+        let mut tree = create_sample_tree();
+        tree.add_subtree_with(
+            &5,
+            source.get_subtree(&2, None)?,
+            CollisionPolicy::Overwrite,
+        )?;
+        assert_eq!(
+            tree.traverse(&1, TraversalStrategy::PreOrder)?,
+            vec![1, 5, 2, 3, 4]
+        );
+        tree.get_node_by_id(&2).unwrap().set_value(Some(0))?;
+        assert_eq!(source, create_sample_tree());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_rename_node() -> Result<()> {
+        let mut tree: Tree<u32, u32, DefaultStorage, u32> =
+            create_sample_tree_in(DefaultStorage::default());
+        for node_id in [2, 3, 5] {
+            tree.set_edge(&node_id, node_id)?;
+        }
+        tree.set_attribute(&2, 'a')?;
+        tree.rename_node(&2, 20)?;
+        assert!(tree.get_node_by_id(&2).is_none());
+        let node = tree.get_node_by_id(&20).unwrap();
+        assert_eq!(node.get_value()?, Some(20));
+        assert_eq!(node.get_parent_id()?, Some(1));
+        assert_eq!(
+            tree.get_node_by_id(&1).unwrap().get_children_ids()?,
+            vec![20, 5]
+        );
+        for child in [3, 4] {
+            assert_eq!(
+                tree.get_node_by_id(&child).unwrap().get_parent_id()?,
+                Some(20)
+            );
+        }
+        assert_eq!(tree.get_edge(&20)?, Some(&2));
+        assert_eq!(tree.get_weighted_depth(&3)?, 5);
+        assert_eq!(tree.attributes::<char>().unwrap().get(&20), Some(&'a'));
+        assert!(!tree.attributes::<char>().unwrap().contains(&2));
+
+        tree.rename_node(&1, 10)?;
+        assert_eq!(tree.get_root_node().unwrap().get_node_id()?, 10);
+        assert_eq!(
+            tree.traverse(&10, TraversalStrategy::PreOrder)?,
+            vec![10, 20, 3, 4, 5]
+        );
+
+        assert_eq!(
+            tree.rename_node(&3, 4),
+            Err(InvalidOperation(
+                "Node 4 is already in the tree".to_string()
+            ))
+        );
+        assert_eq!(
+            tree.rename_node(&42, 43),
+            Err(NodeNotFound("42".to_string()))
+        );
+        tree.rename_node(&3, 3)?;
+        assert_eq!(tree.get_nodes().len(), 5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_from_parent_map() -> Result<()> {
+        let entries = vec![
+            (4, Some(2), Some(40)),
+            (3, Some(2), Some(30)),
+            (5, Some(1), Some(50)),
+            (2, Some(1), Some(20)),
+            (1, None, Some(10)),
+        ];
+        let tree: Tree<u32, u32> = Tree::from_parent_map(entries)?;
+        assert_eq!(
+            tree.traverse(&1, TraversalStrategy::PreOrder)?,
+            vec![1, 5, 2, 4, 3]
+        );
+        assert_eq!(tree.get_node_by_id(&3).unwrap().get_value()?, Some(30));
+
+        let empty: Tree<u32, u32> = Tree::from_parent_map(vec![])?;
+        assert!(empty.get_nodes().is_empty());
+        assert_eq!(
+            Tree::<u32, u32>::from_parent_map(vec![(1, None, None), (1, None, None)]),
+            Err(InvalidOperation(
+                "Node 1 appears more than once.".to_string()
+            ))
+        );
+        assert_eq!(
+            Tree::<u32, u32>::from_parent_map(vec![(1, None, None), (2, None, None)]),
+            Err(RootNodeAlreadyPresent)
+        );
+        assert_eq!(
+            Tree::<u32, u32>::from_parent_map(vec![(1, None, None), (2, Some(9), None)]),
+            Err(InvalidOperation(
+                "Node 2 is an orphan: its parent 9 is not in the tree.".to_string()
+            ))
+        );
+        assert_eq!(
+            Tree::<u32, u32>::from_parent_map(vec![
+                (1, None, None),
+                (2, Some(3), None),
+                (3, Some(2), None)
+            ]),
+            Err(InvalidOperation("Node 2 is part of a cycle.".to_string()))
+        );
+        assert_eq!(
+            Tree::<u32, u32>::from_parent_map(vec![(1, Some(1), None)]),
+            Err(InvalidOperation("Node 1 is part of a cycle.".to_string()))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_from_edges() -> Result<()> {
+        let tree: Tree<u32, u32> = Tree::from_edges(vec![(2, 3), (1, 2), (2, 4), (1, 5)])?;
+        let mut expected = create_sample_tree();
+        expected.rename(None);
+        assert_eq!(
+            tree.traverse(&1, TraversalStrategy::PreOrder)?,
+            expected.traverse(&1, TraversalStrategy::PreOrder)?
+        );
+        assert_eq!(tree.get_node_by_id(&3).unwrap().get_value()?, None);
+        assert!(Tree::<u32, u32>::from_edges(vec![])?.get_nodes().is_empty());
+
+        assert_eq!(
+            Tree::<u32, u32>::from_edges(vec![(1, 2), (3, 2)]),
+            Err(InvalidOperation(
+                "Node 2 has more than one parent.".to_string()
+            ))
+        );
+        assert_eq!(
+            Tree::<u32, u32>::from_edges(vec![(1, 2), (3, 4)]),
+            Err(RootNodeAlreadyPresent)
+        );
+        assert_eq!(
+            Tree::<u32, u32>::from_edges(vec![(1, 2), (3, 4), (4, 3)]),
+            Err(InvalidOperation("Node 3 is part of a cycle.".to_string()))
+        );
+        assert_eq!(
+            Tree::<u32, u32>::from_edges(vec![(1, 2), (2, 1)]),
+            Err(InvalidOperation("Node 1 is part of a cycle.".to_string()))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_builder() -> Result<()> {
+        let tree = TreeBuilder::new(Some("Sample Tree"))
+            .node(1, 10)
+            .child(2, 20, |b| b.child(3, 30, |b| b).child(4, 40, |b| b))
+            .child(5, 50, |b| b)
+            .build()?;
+        assert_eq!(tree, create_sample_tree());
+
+        let tree: LocalTree<u32, u32> = TreeBuilder::new_in(None, RcStorage).node(1, 10).build()?;
+        assert_eq!(tree.get_nodes().len(), 1);
+
+        assert_eq!(
+            TreeBuilder::<u32, u32>::new(None)
+                .child(2, 20, |b| b)
+                .node(1, 10)
+                .build(),
+            Err(InvalidOperation(
+                "Node 2 was added as a child before the root node.".to_string()
+            ))
+        );
+        assert_eq!(
+            TreeBuilder::<u32, u32>::new(None)
+                .node(1, 10)
+                .child(2, 20, |b| b.node(3, 30))
+                .build(),
+            Err(RootNodeAlreadyPresent)
+        );
+        assert_eq!(
+            TreeBuilder::<u32, u32>::new(None)
+                .node(1, 10)
+                .child(1, 20, |b| b)
+                .build(),
+            Err(InvalidOperation(
+                "Node 1 appears more than once.".to_string()
+            ))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_collection_traits() -> Result<()> {
+        let tree = create_sample_tree();
+        assert_eq!(tree.len(), 5);
+        assert!(!tree.is_empty());
+        assert!(tree.contains(&3));
+        assert!(!tree.contains(&6));
+        assert_eq!(tree[&4].get_value()?, Some(40));
+
+        let ids = tree
+            .iter()
+            .map(|x| x.get_node_id())
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(ids, tree.traverse(&1, TraversalStrategy::PreOrder)?);
+        let mut values = vec![];
+        for node in &tree {
+            values.push(node.get_value()?);
+        }
+        assert_eq!(
+            values,
+            vec![Some(10), Some(20), Some(30), Some(40), Some(50)]
+        );
+
+        let mut extended = Tree::new(Some("Sample Tree"));
+        extended.extend(vec![(None, Node::new(1, Some(10)))]);
+        extended.extend(vec![
+            (Some(1), Node::new(2, Some(20))),
+            (Some(2), Node::new(3, Some(30))),
+            (Some(2), Node::new(4, Some(40))),
+            (Some(1), Node::new(5, Some(50))),
+        ]);
+        assert_eq!(extended, tree);
+
+        let mut collected: Tree<u32, u32> = vec![
+            (1, None, Some(10)),
+            (2, Some(1), Some(20)),
+            (3, Some(2), Some(30)),
+            (4, Some(2), Some(40)),
+            (5, Some(1), Some(50)),
+        ]
+        .into_iter()
+        .collect();
+        collected.rename(Some("Sample Tree"));
+        assert_eq!(collected, tree);
+
+        let empty: Tree<u32, u32> = Tree::new(None);
+        assert!(empty.is_empty());
+        assert_eq!(empty.iter().count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "Error: Node 6 not found in the tree.")]
+    fn test_tree_index_missing_node() {
+        let tree = create_sample_tree();
+        let _ = &tree[&6];
+    }
+
+    #[test]
+    #[should_panic(expected = "Error: Node 6 not found in the tree.")]
+    fn test_tree_extend_missing_parent() {
+        let mut tree = create_sample_tree();
+        tree.extend([(Some(6), Node::new(7, Some(70)))]);
+    }
+
+    #[test]
+    fn test_tree_cursor() -> Result<()> {
+        let tree = create_sample_tree();
+        let mut cursor = tree.cursor(&1)?;
+        assert!(!cursor.parent());
+        assert!(!cursor.next_sibling());
+        assert!(cursor.first_child());
+        assert_eq!(cursor.node().get_node_id()?, 2);
+        assert!(!cursor.prev_sibling());
+        assert!(cursor.last_child());
+        assert_eq!(cursor.node().get_node_id()?, 4);
+        assert!(!cursor.first_child());
+        let saved = cursor.clone();
+        assert!(cursor.prev_sibling());
+        assert_eq!(cursor.node().get_node_id()?, 3);
+        assert!(cursor.parent() && cursor.next_sibling());
+        assert_eq!(cursor.node().get_node_id()?, 5);
+        assert_eq!(saved.node().get_node_id()?, 4);
+        assert_eq!(
+            tree.cursor(&6).map(|x| x.node().get_node_id()),
+            Err(NodeNotFound("6".to_string()))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_cursor_mut() -> Result<()> {
+        let mut tree = create_sample_tree();
+        tree.set_attribute(&3, "three")?;
+        let mut cursor = tree.cursor_mut(&1)?;
+        assert_eq!(
+            cursor.insert_sibling(Node::new(6, None)),
+            Err(InvalidOperation(
+                "Cannot add a sibling to the root node".to_string()
+            ))
+        );
+        assert_eq!(
+            cursor.remove(),
+            Err(InvalidOperation(
+                "Cannot remove the root node with a cursor".to_string()
+            ))
+        );
+        assert_eq!(cursor.insert_child(Node::new(6, Some(60)))?, 6);
+        assert!(cursor.first_child());
+        assert_eq!(cursor.insert_sibling(Node::new(7, Some(70)))?, 7);
+        // The siblings that follow the added node move one position further.
+        assert!(cursor.next_sibling());
+        assert_eq!(cursor.node().get_node_id()?, 7);
+        assert!(cursor.next_sibling() && cursor.next_sibling() && !cursor.next_sibling());
+        assert_eq!(cursor.node().get_node_id()?, 6);
+        assert!(cursor.prev_sibling() && cursor.prev_sibling() && cursor.prev_sibling());
+        assert!(cursor.first_child() && cursor.next_sibling());
+        assert_eq!(cursor.insert_sibling(Node::new(8, Some(80)))?, 8);
+        assert_eq!(cursor.replace_value(None)?, Some(40));
+        assert!(cursor.prev_sibling());
+
+        let removed = cursor.remove()?;
+        assert_eq!(removed.get_node_id()?, 3);
+        assert_eq!(removed.get_parent_id()?, None);
+        assert_eq!(cursor.node().get_node_id()?, 4);
+        assert_eq!(cursor.remove()?.get_node_id()?, 4);
+        assert_eq!(cursor.node().get_node_id()?, 8);
+        assert!(cursor.parent());
+        assert_eq!(cursor.remove()?.get_children_ids()?, Vec::<u32>::new());
+        assert_eq!(cursor.node().get_node_id()?, 7);
+        // The siblings that followed the removed node move one position back.
+        assert!(!cursor.prev_sibling());
+        assert!(cursor.next_sibling() && cursor.next_sibling() && !cursor.next_sibling());
+        assert_eq!(cursor.node().get_node_id()?, 6);
+        assert!(cursor.parent() && cursor.first_child());
+        assert_eq!(cursor.node().get_node_id()?, 7);
+
+        assert_eq!(
+            tree.traverse(&1, TraversalStrategy::PreOrder)?,
+            vec![1, 7, 5, 6]
+        );
+        assert_eq!(tree.len(), 4);
+        assert!(tree.attributes::<&str>().unwrap().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_walk() -> Result<()> {
+        struct Recorder {
+            events: Vec<String>,
+            skip: u32,
+            stop: u32,
+        }
+
+        impl TreeVisitor<u32, u32> for Recorder {
+            fn enter(&mut self, node: &Node<u32, u32>, depth: usize) -> WalkControl {
+                let node_id = node.get_node_id().unwrap();
+                self.events.push(format!("+{node_id}@{depth}"));
+                if node_id == self.skip {
+                    WalkControl::SkipChildren
+                } else {
+                    WalkControl::Continue
+                }
+            }
+
+            fn leave(&mut self, node: &Node<u32, u32>, depth: usize) -> WalkControl {
+                let node_id = node.get_node_id().unwrap();
+                self.events.push(format!("-{node_id}@{depth}"));
+                if node_id == self.stop {
+                    WalkControl::Stop
+                } else {
+                    WalkControl::SkipChildren
+                }
+            }
+        }
+
+        let tree = create_sample_tree();
+        let mut recorder = Recorder {
+            events: vec![],
+            skip: 0,
+            stop: 0,
+        };
+        tree.walk(&1, &mut recorder)?;
+        assert_eq!(
+            recorder.events,
+            vec!["+1@0", "+2@1", "+3@2", "-3@2", "+4@2", "-4@2", "-2@1", "+5@1", "-5@1", "-1@0"]
+        );
+
+        let mut recorder = Recorder {
+            events: vec![],
+            skip: 2,
+            stop: 5,
+        };
+        tree.walk(&1, &mut recorder)?;
+        assert_eq!(
+            recorder.events,
+            vec!["+1@0", "+2@1", "-2@1", "+5@1", "-5@1"]
+        );
+
+        let mut recorder = Recorder {
+            events: vec![],
+            skip: 0,
+            stop: 0,
+        };
+        tree.walk(&2, &mut recorder)?;
+        assert_eq!(
+            recorder.events,
+            vec!["+2@0", "+3@1", "-3@1", "+4@1", "-4@1", "-2@0"]
+        );
+        assert_eq!(
+            tree.walk(&6, &mut recorder),
+            Err(NodeNotFound("6".to_string()))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_walk_events() -> Result<()> {
+        let tree = create_sample_tree();
+        let mut events = tree.walk_events(&1)?;
+        let mut ids = vec![];
+        while let Some(event) = events.next() {
+            match event {
+                WalkEvent::Enter(node) => {
+                    let node_id = node.get_node_id()?;
+                    if node_id == 2 {
+                        events.skip_children();
+                    }
+                    ids.push(node_id as i32);
+                }
+                WalkEvent::Leave(node) => {
+                    // Skipping after a leave event has no effect.
+                    events.skip_children();
+                    ids.push(-(node.get_node_id()? as i32));
+                }
+            }
+        }
+        assert_eq!(ids, vec![1, 2, -2, 5, -5, -1]);
+        assert_eq!(tree.walk_events(&3)?.count(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_sibling_navigation() -> Result<()> {
+        let tree = create_sample_tree();
+        assert_eq!(tree.get_next_sibling_id(&3)?, Some(4));
+        assert_eq!(tree.get_next_sibling_id(&4)?, None);
+        assert_eq!(tree.get_next_sibling_id(&1)?, None);
+        assert_eq!(tree.get_previous_sibling_id(&5)?, Some(2));
+        assert_eq!(tree.get_previous_sibling_id(&2)?, None);
+        assert_eq!(tree.get_child_index(&4)?, Some(1));
+        assert_eq!(tree.get_child_index(&1)?, None);
+        assert_eq!(tree.get_first_child_id(&2)?, Some(3));
+        assert_eq!(tree.get_last_child_id(&1)?, Some(5));
+        assert_eq!(tree.get_first_child_id(&5)?, None);
+        assert_eq!(tree.get_last_child_id(&5)?, None);
+        assert_eq!(tree.get_nth_child_id(&1, 1)?, Some(5));
+        assert_eq!(tree.get_nth_child_id(&1, 2)?, None);
+        assert_eq!(
+            tree.get_next_sibling_id(&6),
+            Err(NodeNotFound("6".to_string()))
+        );
+        assert_eq!(
+            tree.get_nth_child_id(&6, 0),
+            Err(NodeNotFound("6".to_string()))
+        );
+
+        tree.get_node_by_id(&1)
+            .unwrap()
+            .sort_children(|a, b| b.cmp(a))?;
+        assert_eq!(tree.get_first_child_id(&1)?, Some(5));
+        assert_eq!(tree.get_next_sibling_id(&5)?, Some(2));
+        assert_eq!(tree.get_child_index(&2)?, Some(1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_with_float_values() -> Result<()> {
+        let mut tree: Tree<u32, f64> = Tree::new(Some("Floats"));
+        let root = tree.add_node(Node::new(1, Some(0.5)), None)?;
+        let node_2 = tree.add_node(Node::new(2, Some(1.25)), Some(&root))?;
+        tree.add_node(Node::new(3, Some(f64::NAN)), Some(&node_2))?;
+        tree.add_node(Node::new(4, None), Some(&root))?;
+
+        assert_eq!(
+            tree.traverse(&root, TraversalStrategy::PreOrder)?,
+            vec![1, 2, 3, 4]
+        );
+        assert_eq!(tree.get_node_by_id(&2).unwrap().get_value()?, Some(1.25));
+        assert_eq!(tree.get_subtree(&node_2, None)?.get_nodes().len(), 2);
+        #[cfg(not(feature = "print_node_id"))]
+        assert_eq!(
+            format!("{tree}"),
+            "Floats\n******\n0.5\n├── 1.25\n│   └── NaN\n└── \n"
+        );
+        assert!(tree.to_mermaid(MermaidStyle::Flowchart)?.contains("1.25"));
+
+        let copy = tree.deep_clone()?;
+        assert_eq!(copy.get_node_by_id(&2), tree.get_node_by_id(&2));
+        // NaN values are never equal, so neither are the nodes holding them.
+        assert_ne!(copy, tree);
+        let paths = tree.to_materialized_paths("/")?;
+        let rebuilt: Tree<u32, f64> = Tree::from_materialized_paths(paths, "/", |id| {
+            tree.get_node_ref(id)
+                .and_then(|x| x.with_value(|x| x.copied()))
+        })?;
+        assert_eq!(rebuilt.get_node_by_id(&4).unwrap().get_value()?, None);
+
+        tree.remove_node(&node_2, NodeRemovalStrategy::RetainChildren)?;
+        assert_eq!(tree.get_node_by_id(&3).unwrap().get_parent_id()?, Some(1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_with_values_without_traits() -> Result<()> {
+        struct Opaque(u32);
+
+        let mut tree: Tree<u32, Opaque> = Tree::new(None);
+        let root = tree.add_node(Node::new(1, Some(Opaque(10))), None)?;
+        tree.add_node(Node::new(2, Some(Opaque(20))), Some(&root))?;
+        tree.add_node(Node::new(3, None), Some(&root))?;
+
+        let clone = tree.clone();
+        let node = clone.get_node_ref(&2).unwrap();
+        node.update_value(|value| *value = Some(Opaque(21)))?;
+        assert_eq!(
+            tree.get_node_ref(&2)
+                .unwrap()
+                .with_value(|x| x.map(|x| x.0)),
+            Some(21)
+        );
+        let renderer = TreeRenderer::with_formatter(|id: &u32, value: Option<&Opaque>| {
+            format!("{id}={}", value.map(|x| x.0).unwrap_or_default())
+        });
+        assert_eq!(tree.render(&renderer)?, "1=10\n├── 2=21\n└── 3=0\n");
+        assert_eq!(tree.get_height()?, 1);
+        tree.remove_node(&2, NodeRemovalStrategy::RemoveNodeAndChildren)?;
+        assert_eq!(tree.get_nodes().len(), 2);
+
+        let immutable = ImmutableTree::new(None)
+            .insert(1, Some(Opaque(1)), None)?
+            .insert(2, Some(Opaque(2)), Some(&1))?;
+        let updated = immutable.set_value(&2, Some(Opaque(3)))?;
+        assert_eq!(immutable.get_value(&2)?.map(|x| x.0), Some(2));
+        assert_eq!(updated.get_value(&2)?.map(|x| x.0), Some(3));
+        Ok(())
+    }
+
+    #[test]
+    fn compare_tree() {
+        let mut tree = Tree::<u32, u32>::new(Some("Sample Tree"));
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None).unwrap();
+        let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1)).unwrap();
+        let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2)).unwrap();
+        tree.add_node(Node::new(4, Some(5)), Some(&node_2)).unwrap();
+        tree.add_node(Node::new(5, Some(6)), Some(&node_3)).unwrap();
+        let mut tree_2 = Tree::<u32, u32>::new(Some("Sample Tree"));
+        let node_1 = tree_2.add_node(Node::new(1, Some(2)), None).unwrap();
+        let node_2 = tree_2
+            .add_node(Node::new(2, Some(3)), Some(&node_1))
+            .unwrap();
+        let node_3 = tree_2
+            .add_node(Node::new(3, Some(6)), Some(&node_2))
+            .unwrap();
+        tree_2
+            .add_node(Node::new(4, Some(5)), Some(&node_2))
+            .unwrap();
+        tree_2
+            .add_node(Node::new(5, Some(6)), Some(&node_3))
+            .unwrap();
+        assert_eq!(tree, tree_2);
+        let tree_3 = Tree::<u32, u32>::new(Some("Sample Tree"));
+        assert_ne!(tree, tree_3);
+    }
+
+    #[test]
+    fn test_tree_traverse() {
+        let mut tree = Tree::<u32, u32>::new(Some("Sample Tree"));
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None).unwrap();
+        let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1)).unwrap();
+        let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_1)).unwrap();
+        let node_4 = tree.add_node(Node::new(4, Some(5)), Some(&node_2)).unwrap();
+        let node_5 = tree.add_node(Node::new(5, Some(6)), Some(&node_2)).unwrap();
+        let node_6 = tree.add_node(Node::new(6, Some(7)), Some(&node_3)).unwrap();
+        let preorder_nodes = tree.traverse(&node_1, TraversalStrategy::PreOrder).unwrap();
+        let expected_preorder = vec![node_1, node_2, node_4, node_5, node_3, node_6];
+        assert_eq!(preorder_nodes, expected_preorder);
+
+        let in_order_nodes = tree.traverse(&node_1, TraversalStrategy::InOrder).unwrap();
+        let expected_in_order = vec![node_4, node_2, node_5, node_1, node_3, node_6];
+        assert_eq!(in_order_nodes, expected_in_order);
+
+        let post_order_nodes = tree
+            .traverse(&node_1, TraversalStrategy::PostOrder)
+            .unwrap();
+        let expected_post_order = vec![node_4, node_5, node_2, node_6, node_3, node_1];
+        assert_eq!(post_order_nodes, expected_post_order);
+    }
+
+    #[allow(deprecated)] // This is solely for testing hashing in no_std.
+    #[test]
+    fn test_hashing() {
+        let mut tree = Tree::<u32, u32>::new(Some("Sample Tree"));
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None).unwrap();
+        let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1)).unwrap();
+        let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2)).unwrap();
+        tree.add_node(Node::new(4, Some(5)), Some(&node_2)).unwrap();
+        tree.add_node(Node::new(5, Some(6)), Some(&node_3)).unwrap();
+        let mut tree_2 = Tree::<u32, u32>::new(Some("Sample Tree"));
+        let node_1 = tree_2.add_node(Node::new(1, Some(2)), None).unwrap();
+        let node_2 = tree_2
+            .add_node(Node::new(2, Some(3)), Some(&node_1))
+            .unwrap();
+        let node_3 = tree_2
+            .add_node(Node::new(3, Some(6)), Some(&node_2))
+            .unwrap();
+        tree_2
+            .add_node(Node::new(4, Some(5)), Some(&node_2))
+            .unwrap();
+        tree_2
+            .add_node(Node::new(5, Some(6)), Some(&node_3))
+            .unwrap();
+        assert_eq!(tree, tree_2);
+        let mut hasher = DefaultHasher::new();
+        tree.hash(&mut hasher);
+        let tree_hash = hasher.finish();
+        let mut hasher = DefaultHasher::new();
+        tree_2.hash(&mut hasher);
+        let tree_2_hash = hasher.finish();
+        assert_eq!(tree_hash, tree_2_hash);
+    }
+
+    #[test]
+    fn test_sort_children_by_height() -> Result<()> {
+        let mut tree = Tree::<u32, u32>::new(Some("Sample Tree"));
+        let node_1 = tree.add_node(Node::new(1, Some(1)), None)?;
+        let _node_2 = tree.add_node(Node::new(2, Some(2)), Some(&node_1))?;
+        let node_3 = tree.add_node(Node::new(3, Some(3)), Some(&node_1))?;
+        let node_4 = tree.add_node(Node::new(4, Some(4)), Some(&node_3))?;
+        let _node_5 = tree.add_node(Node::new(5, Some(5)), Some(&node_4))?;
+
+        let root = tree.get_node_by_id(&node_1).unwrap();
+        root.sort_children(|a, b| {
+            let a_height = tree.get_node_height(a).unwrap();
+            let b_height = tree.get_node_height(b).unwrap();
+            a_height.cmp(&b_height).reverse()
+        })?;
+
+        assert_eq!(
+            tree.get_node_by_id(&node_1).unwrap().get_children_ids()?,
+            vec![3, 2]
+        );
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use crate::prelude::Node;
+
+    use super::*;
+
+    #[test]
+    fn test_tree_serialize_and_deserialize() {
+        let mut tree = Tree::new(None);
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None).unwrap();
+        let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1)).unwrap();
+        let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2)).unwrap();
+        tree.add_node(Node::new(4, Some(5)), Some(&node_2)).unwrap();
+        tree.add_node(Node::new(5, Some(6)), Some(&node_3)).unwrap();
+        let serialized = serde_json::to_string(&tree).unwrap();
+        let expected = r#"{"nodes":[{"node_id":1,"value":2,"parent":null,"children":[2]},{"node_id":2,"value":3,"parent":1,"children":[3,4]},{"node_id":3,"value":6,"parent":2,"children":[5]},{"node_id":4,"value":5,"parent":2,"children":[]},{"node_id":5,"value":6,"parent":3,"children":[]}]}"#;
+        let deserialized: Tree<u32, u32> = serde_json::from_str(&serialized).unwrap();
+        let expected_tree: Tree<u32, u32> = serde_json::from_str(expected).unwrap();
+        assert_eq!(deserialized, expected_tree);
+    }
+
+    #[test]
+    fn test_tree_serialize_and_deserialize_float_values() {
+        let mut tree: Tree<u32, f64> = Tree::new(Some("Floats"));
+        let node_1 = tree.add_node(Node::new(1, Some(0.5)), None).unwrap();
+        tree.add_node(Node::new(2, Some(-1.25)), Some(&node_1))
+            .unwrap();
+        tree.add_node(Node::new(3, None), Some(&node_1)).unwrap();
+        let serialized = serde_json::to_string(&tree).unwrap();
+        let deserialized: Tree<u32, f64> = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, tree);
+    }
+
+    #[test]
+    fn test_tree_serialize_and_deserialize_edges() {
+        let mut tree: Tree<u32, u32, DefaultStorage, String> =
+            Tree::new_with_edges(None, DefaultStorage::default());
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None).unwrap();
+        let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1)).unwrap();
+        tree.set_edge(&node_2, String::from("manages")).unwrap();
+
+        let serialized = serde_json::to_string(&tree).unwrap();
+        assert!(serialized.ends_with(r#""edges":[[2,"manages"]]}"#));
+        let deserialized: Tree<u32, u32, DefaultStorage, String> =
+            serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, tree);
+
+        tree.remove_edge(&node_2).unwrap();
+        let serialized = serde_json::to_string(&tree).unwrap();
+        assert!(!serialized.contains("edges"));
+        let deserialized: Tree<u32, u32, DefaultStorage, String> =
+            serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, tree);
+    }
+
+    #[test]
+    fn test_tree_serialize_with_attributes() {
+        let mut tree: Tree<u32, u32> = Tree::new(None);
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None).unwrap();
+        tree.add_node(Node::new(2, Some(3)), Some(&node_1)).unwrap();
+        tree.set_attribute(&2, String::from("collapsed")).unwrap();
+        tree.set_attribute(&1, 7_u8).unwrap();
+
+        let serialized = serde_json::to_string(&tree.with_attributes::<String>()).unwrap();
+        assert!(serialized.ends_with(r#""attributes":[[2,"collapsed"]]}"#));
+        let mut deserializer = serde_json::Deserializer::from_str(&serialized);
+        let deserialized =
+            Tree::<u32, u32>::deserialize_with_attributes::<String, _>(&mut deserializer).unwrap();
+        assert_eq!(deserialized, tree);
+        assert_eq!(
+            deserialized.attributes::<String>(),
+            tree.attributes::<String>()
+        );
+        // Only the chosen type is saved.
+        assert!(deserialized.attributes::<u8>().is_none());
+
+        // A plain tree ignores the attributes, and a tree saved without them reads back empty.
+        let plain: Tree<u32, u32> = serde_json::from_str(&serialized).unwrap();
+        assert!(plain.attributes::<String>().is_none());
+        let serialized = serde_json::to_string(&tree).unwrap();
+        let mut deserializer = serde_json::Deserializer::from_str(&serialized);
+        let deserialized =
+            Tree::<u32, u32>::deserialize_with_attributes::<String, _>(&mut deserializer).unwrap();
+        assert!(deserialized.attributes::<String>().is_none());
+
+        let serialized = serde_json::to_string(&tree.with_attributes::<bool>()).unwrap();
+        assert!(serialized.ends_with(r#""attributes":[]}"#));
+
+        // Attributes of nodes that are not in the tree are rejected.
+        let serialized = r#"{"nodes":[],"attributes":[[5,"collapsed"]]}"#;
+        let mut deserializer = serde_json::Deserializer::from_str(serialized);
+        assert!(
+            Tree::<u32, u32>::deserialize_with_attributes::<String, _>(&mut deserializer).is_err()
+        );
+    }
+
+    #[test]
+    fn test_tree_attributes_are_serialized_separately() {
+        let mut tree = Tree::new(None);
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None).unwrap();
+        tree.add_node(Node::new(2, Some(3)), Some(&node_1)).unwrap();
+        tree.set_attribute(&2, String::from("collapsed")).unwrap();
+
+        let serialized = serde_json::to_string(&tree).unwrap();
+        assert!(!serialized.contains("collapsed"));
+        let attributes = serde_json::to_string(tree.attributes::<String>().unwrap()).unwrap();
+        assert_eq!(attributes, r#"[[2,"collapsed"]]"#);
+
+        let mut deserialized: Tree<u32, u32> = serde_json::from_str(&serialized).unwrap();
+        assert!(deserialized.attributes::<String>().is_none());
+        deserialized
+            .insert_attributes::<String>(serde_json::from_str(&attributes).unwrap())
+            .unwrap();
+        assert_eq!(
+            deserialized.attributes::<String>(),
+            tree.attributes::<String>()
+        );
     }
 
     #[test]
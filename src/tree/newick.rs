@@ -0,0 +1,297 @@
+use crate::error::Error::ParseError;
+use crate::lib::*;
+
+/// The value of a node in a phylogenetic tree.
+///
+/// This struct represents the data that the Newick format attaches to a node: an optional label and
+/// an optional branch length. The branch length is the length of the edge connecting the node to its
+/// parent. It is kept exactly as it was written so that parsing and writing a tree does not change
+/// its textual representation.
+///
+/// # Example
+///
+/// ```rust
+/// # use tree_ds::prelude::NewickValue;
+///
+/// let value = NewickValue::new(Some("Homo sapiens"), Some(0.25));
+/// assert_eq!(value.get_label(), Some("Homo sapiens"));
+/// assert_eq!(value.get_branch_length(), Some(0.25));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct NewickValue {
+    label: Option<String>,
+    branch_length: Option<String>,
+}
+
+impl NewickValue {
+    /// Create a new Newick value.
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - The label of the node.
+    /// * `branch_length` - The length of the branch connecting the node to its parent.
+    ///
+    /// # Returns
+    ///
+    /// A new Newick value with the given label and branch length.
+    pub fn new(label: Option<&str>, branch_length: Option<f64>) -> Self {
+        Self {
+            label: label.map(|x| x.to_string()),
+            branch_length: branch_length.map(|x| x.to_string()),
+        }
+    }
+
+    /// Get the label of the node.
+    ///
+    /// # Returns
+    ///
+    /// The label of the node or `None` if the node is unlabelled.
+    pub fn get_label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Get the branch length of the node.
+    ///
+    /// # Returns
+    ///
+    /// The length of the branch connecting the node to its parent or `None` if it was not given.
+    pub fn get_branch_length(&self) -> Option<f64> {
+        self.branch_length.as_ref().and_then(|x| x.parse().ok())
+    }
+
+    /// Write the label and branch length of the node in Newick syntax.
+    pub(crate) fn write_newick(&self, output: &mut String) {
+        if let Some(label) = &self.label {
+            output.push_str(&quote_newick_label(label));
+        }
+        if let Some(branch_length) = &self.branch_length {
+            output.push(':');
+            output.push_str(branch_length);
+        }
+    }
+}
+
+impl Display for NewickValue {
+    /// Display the label of the node.
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.label.as_deref().unwrap_or_default())
+    }
+}
+
+/// Check whether a character ends an unquoted Newick label.
+fn is_newick_delimiter(character: char) -> bool {
+    character.is_whitespace() || "()[]':;,".contains(character)
+}
+
+/// Quote a label if it contains characters that can not appear in an unquoted Newick label.
+///
+/// Underscores are quoted as well since unquoted underscores are read back as spaces.
+fn quote_newick_label(label: &str) -> String {
+    if !label.is_empty() && !label.chars().any(|x| x == '_' || is_newick_delimiter(x)) {
+        return label.to_string();
+    }
+    format!("'{}'", label.replace('\'', "''"))
+}
+
+/// Parse a tree in the Newick format.
+///
+/// The nodes are returned in pre-order together with the position of their parent so that the
+/// tree can be assembled without recursion, which keeps deeply nested inputs from overflowing the
+/// stack.
+pub(crate) fn parse_newick(
+    newick: &str,
+) -> crate::prelude::Result<Vec<(NewickValue, Option<usize>)>> {
+    let mut parser = NewickParser::new(newick);
+    let mut nodes: Vec<(NewickValue, Option<usize>)> = vec![];
+    let mut open_nodes: Vec<usize> = vec![];
+    loop {
+        parser.skip_insignificant()?;
+        let parent = open_nodes.last().copied();
+        if parser.peek() == Some('(') {
+            parser.next();
+            nodes.push((NewickValue::default(), parent));
+            open_nodes.push(nodes.len() - 1);
+            continue;
+        }
+        nodes.push((parser.parse_value()?, parent));
+        loop {
+            parser.skip_insignificant()?;
+            let (line, column) = parser.position();
+            match parser.next() {
+                Some(',') if !open_nodes.is_empty() => break,
+                Some(')') => {
+                    let closed_node = open_nodes.pop().ok_or(ParseError {
+                        line,
+                        column,
+                        message: String::from("Unexpected ')' without a matching '('"),
+                    })?;
+                    nodes[closed_node].0 = parser.parse_value()?;
+                }
+                Some(';') if open_nodes.is_empty() => {
+                    parser.skip_insignificant()?;
+                    if let Some(character) = parser.peek() {
+                        let (line, column) = parser.position();
+                        return Err(ParseError {
+                            line,
+                            column,
+                            message: format!("Unexpected '{character}' after the end of the tree"),
+                        });
+                    }
+                    return Ok(nodes);
+                }
+                Some(';') => {
+                    return Err(ParseError {
+                        line,
+                        column,
+                        message: String::from("Unexpected ';' before all '(' were closed"),
+                    });
+                }
+                Some(character) => {
+                    return Err(ParseError {
+                        line,
+                        column,
+                        message: format!("Unexpected '{character}'"),
+                    });
+                }
+                None => {
+                    return Err(ParseError {
+                        line,
+                        column,
+                        message: String::from("Unexpected end of input, expected ';'"),
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// A character reader that keeps track of the line and column of the input.
+struct NewickParser<'a> {
+    input: core::iter::Peekable<core::str::Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> NewickParser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            input: input.chars().peekable(),
+            line: 1,
+            column: 1,
+        }
+    }
+
+    /// Get the line and column of the next character.
+    fn position(&self) -> (usize, usize) {
+        (self.line, self.column)
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.input.peek().copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let character = self.input.next()?;
+        if character == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(character)
+    }
+
+    /// Skip whitespace and comments.
+    fn skip_insignificant(&mut self) -> crate::prelude::Result<()> {
+        while let Some(character) = self.peek() {
+            if character.is_whitespace() {
+                self.next();
+            } else if character == '[' {
+                let (line, column) = self.position();
+                self.next();
+                loop {
+                    match self.next() {
+                        Some(']') => break,
+                        Some(_) => {}
+                        None => {
+                            return Err(ParseError {
+                                line,
+                                column,
+                                message: String::from("Unterminated comment"),
+                            })
+                        }
+                    }
+                }
+            } else {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse the optional label and branch length that follow a leaf or a closing parenthesis.
+    fn parse_value(&mut self) -> crate::prelude::Result<NewickValue> {
+        self.skip_insignificant()?;
+        let label = if self.peek() == Some('\'') {
+            Some(self.parse_quoted_label()?)
+        } else {
+            let label = self.parse_unquoted();
+            (!label.is_empty()).then(|| label.replace('_', " "))
+        };
+        self.skip_insignificant()?;
+        let mut branch_length = None;
+        if self.peek() == Some(':') {
+            self.next();
+            self.skip_insignificant()?;
+            let (line, column) = self.position();
+            let length = self.parse_unquoted();
+            if length.parse::<f64>().is_err() {
+                return Err(ParseError {
+                    line,
+                    column,
+                    message: format!("Invalid branch length '{length}'"),
+                });
+            }
+            branch_length = Some(length);
+        }
+        Ok(NewickValue {
+            label,
+            branch_length,
+        })
+    }
+
+    fn parse_unquoted(&mut self) -> String {
+        let mut token = String::new();
+        while let Some(character) = self.peek() {
+            if is_newick_delimiter(character) {
+                break;
+            }
+            token.push(character);
+            self.next();
+        }
+        token
+    }
+
+    fn parse_quoted_label(&mut self) -> crate::prelude::Result<String> {
+        let (line, column) = self.position();
+        self.next();
+        let mut label = String::new();
+        loop {
+            match self.next() {
+                Some('\'') if self.peek() == Some('\'') => {
+                    self.next();
+                    label.push('\'');
+                }
+                Some('\'') => return Ok(label),
+                Some(character) => label.push(character),
+                None => {
+                    return Err(ParseError {
+                        line,
+                        column,
+                        message: String::from("Unterminated quoted label"),
+                    })
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use crate::error::Error::ParseError;
+use crate::lib::*;
+
+/// The indentation used by a text outline of a tree.
+///
+/// This enum represents how the depth of each line is expressed in an outline parsed by
+/// `Tree::from_indented`.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum IndentStyle {
+    /// Each level is indented by the given number of spaces.
+    Spaces(usize),
+    /// Each level is indented by a single tab.
+    Tabs,
+    /// Each level is indented by the `├── `, `└── `, `│   ` and blank prefixes used when displaying
+    /// a tree.
+    BoxDrawing,
+}
+
+/// A line of an outline that holds a node.
+pub(crate) struct OutlineEntry<'a> {
+    /// The line of the node, starting from 1.
+    pub(crate) line: usize,
+    /// The column at which the label of the node starts, starting from 1.
+    pub(crate) column: usize,
+    /// The depth of the node. The root node has a depth of `0`.
+    pub(crate) depth: usize,
+    /// The text of the node.
+    pub(crate) label: &'a str,
+}
+
+/// Parse an outline into its optional name and the nodes that it holds.
+///
+/// Blank lines are skipped. If the first two lines are a name followed by a line of `*` of the
+/// same length, as written when displaying a named tree, they are returned as the name of the tree.
+/// The depth of every node is validated so that each node is at most one level deeper than the node
+/// before it and only the first node is at the root level.
+pub(crate) fn parse_outline(
+    outline: &str,
+    style: IndentStyle,
+) -> crate::prelude::Result<(Option<String>, Vec<OutlineEntry<'_>>)> {
+    let mut lines = outline
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim_end_matches('\r')))
+        .filter(|(_, line)| !line.trim().is_empty());
+    let mut name = None;
+    let mut header = lines.clone();
+    if let (Some((_, title)), Some((_, underline))) = (header.next(), header.next()) {
+        if underline.chars().all(|x| x == '*') && underline.chars().count() == title.chars().count()
+        {
+            name = Some(title.to_string());
+            lines = header;
+        }
+    }
+
+    let mut entries: Vec<OutlineEntry> = vec![];
+    for (line_number, line) in lines {
+        let (depth, label_start) = parse_indent(line_number, line, style)?;
+        let column = line[..label_start].chars().count() + 1;
+        let error = |message: &str| ParseError {
+            line: line_number,
+            column,
+            message: message.to_string(),
+        };
+        match entries.last().map(|x| x.depth) {
+            None if depth > 0 => return Err(error("The root node must not be indented")),
+            Some(_) if depth == 0 => return Err(error("Only one root node is allowed")),
+            Some(previous_depth) if depth > previous_depth + 1 => {
+                return Err(error(
+                    "The node is indented more than one level deeper than its parent",
+                ))
+            }
+            _ => {}
+        }
+        entries.push(OutlineEntry {
+            line: line_number,
+            column,
+            depth,
+            label: &line[label_start..],
+        });
+    }
+    Ok((name, entries))
+}
+
+/// Get the depth of a line and the byte offset at which its label starts.
+fn parse_indent(
+    line_number: usize,
+    line: &str,
+    style: IndentStyle,
+) -> crate::prelude::Result<(usize, usize)> {
+    let error = |offset: usize, message: &str| ParseError {
+        line: line_number,
+        column: line[..offset].chars().count() + 1,
+        message: message.to_string(),
+    };
+    match style {
+        IndentStyle::Spaces(width) => {
+            let indent = line.len() - line.trim_start_matches(' ').len();
+            if line[indent..].starts_with(char::is_whitespace) {
+                return Err(error(indent, "Expected spaces for indentation"));
+            }
+            if width == 0 || indent % width != 0 {
+                return Err(error(
+                    indent,
+                    &format!("The indentation is not a multiple of {width} spaces"),
+                ));
+            }
+            Ok((indent / width, indent))
+        }
+        IndentStyle::Tabs => {
+            let indent = line.len() - line.trim_start_matches('\t').len();
+            if line[indent..].starts_with(char::is_whitespace) {
+                return Err(error(indent, "Expected tabs for indentation"));
+            }
+            Ok((indent, indent))
+        }
+        IndentStyle::BoxDrawing => {
+            let mut depth = 0;
+            let mut offset = 0;
+            loop {
+                let rest = &line[offset..];
+                if let Some(prefix) = ["├── ", "└── "].iter().find(|x| rest.starts_with(**x))
+                {
+                    return Ok((depth + 1, offset + prefix.len()));
+                } else if let Some(prefix) = ["│   ", "    "].iter().find(|x| rest.starts_with(**x))
+                {
+                    depth += 1;
+                    offset += prefix.len();
+                } else if depth == 0 && !rest.starts_with(char::is_whitespace) {
+                    return Ok((0, 0));
+                } else {
+                    return Err(error(offset, "Expected '├── ' or '└── ' before the node"));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,330 @@
+use crate::error::Error;
+use crate::lib::*;
+use crate::node::{ArcStorage, Node};
+use crate::tree::{TraversalStrategy, Tree};
+use std::panic::resume_unwind;
+use std::thread::scope;
+
+impl<Q, T, E> Tree<Q, T, ArcStorage, E>
+where
+    Q: PartialEq + Eq + Clone + Debug + Hash + Ord + Send + Sync,
+    T: Send + Sync,
+{
+    /// Traverse the tree using several threads.
+    ///
+    /// This method returns the same nodes in the same order as `traverse`. The subtrees of sibling
+    /// nodes are traversed on separate scoped threads, up to `threads` threads in total, and their
+    /// results are joined in the order of the siblings, so the result does not depend on the
+    /// number of threads.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node to start the traversal from.
+    /// * `order` - The order in which to visit the nodes.
+    /// * `threads` - The maximum number of threads to use, including the current one. A value of
+    ///   `0` is treated as `1`.
+    ///
+    /// # Returns
+    ///
+    /// The ids of the nodes in the order they were visited.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::*;
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: ThreadSafeTree<i32, i32> = ThreadSafeTree::new_in(None, ArcStorage);
+    /// let node_1 = tree.add_node(Node::new_in(1, Some(2), ArcStorage), None)?;
+    /// tree.add_node(Node::new_in(2, Some(3), ArcStorage), Some(&node_1))?;
+    /// tree.add_node(Node::new_in(3, Some(4), ArcStorage), Some(&node_1))?;
+    ///
+    /// let nodes = tree.par_traverse(&node_1, TraversalStrategy::PostOrder, 4)?;
+    /// assert_eq!(nodes, tree.traverse(&node_1, TraversalStrategy::PostOrder)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn par_traverse<K>(
+        &self,
+        node_id: &K,
+        order: TraversalStrategy,
+        threads: usize,
+    ) -> crate::prelude::Result<Vec<Q>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Hash + Eq + ?Sized,
+    {
+        let index = NodeIndex::new(self)?;
+        let node_id = index.get(node_id)?.get_node_id()?;
+        index.fold(&node_id, threads.max(1), &|node, children| {
+            let node_id = node.get_node_id()?;
+            let mut nodes = vec![];
+            match order {
+                TraversalStrategy::PreOrder => {
+                    nodes.push(node_id);
+                    children.into_iter().for_each(|mut x| nodes.append(&mut x));
+                }
+                TraversalStrategy::PostOrder => {
+                    children.into_iter().for_each(|mut x| nodes.append(&mut x));
+                    nodes.push(node_id);
+                }
+                TraversalStrategy::InOrder => {
+                    let children_ids = node.get_children_ids()?;
+                    for (index, (child_id, mut child_nodes)) in
+                        children_ids.into_iter().zip(children).enumerate()
+                    {
+                        if index == 0 {
+                            nodes.append(&mut child_nodes);
+                            if !nodes.contains(&child_id) {
+                                nodes.push(child_id);
+                            }
+                            if !nodes.contains(&node_id) {
+                                nodes.push(node_id.clone());
+                            }
+                        } else {
+                            nodes.push(child_id);
+                            nodes.append(&mut child_nodes);
+                        }
+                    }
+                }
+            }
+            let mut seen = HashSet::new();
+            nodes.retain(|x| seen.insert(x.clone()));
+            Ok(nodes)
+        })
+    }
+
+    /// Aggregate the values of a subtree from the leaves up using several threads.
+    ///
+    /// This method calls `fold` once for every node of the subtree, children before their parent,
+    /// with the id and the value of the node and the results of its children in order. The subtrees
+    /// of sibling nodes are folded on separate scoped threads, up to `threads` threads in total. The
+    /// result does not depend on the number of threads.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the root of the subtree to fold.
+    /// * `threads` - The maximum number of threads to use, including the current one. A value of
+    ///   `0` is treated as `1`.
+    /// * `fold` - A closure that combines a node with the results of its children.
+    ///
+    /// # Returns
+    ///
+    /// The result of `fold` for the node with the given id.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::*;
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: ThreadSafeTree<i32, i32> = ThreadSafeTree::new_in(None, ArcStorage);
+    /// let node_1 = tree.add_node(Node::new_in(1, Some(2), ArcStorage), None)?;
+    /// tree.add_node(Node::new_in(2, Some(3), ArcStorage), Some(&node_1))?;
+    /// tree.add_node(Node::new_in(3, Some(4), ArcStorage), Some(&node_1))?;
+    ///
+    /// let sum = tree.par_fold_up(&node_1, 4, |_, value, children: Vec<i32>| {
+    ///     value.copied().unwrap_or_default() + children.iter().sum::<i32>()
+    /// })?;
+    /// assert_eq!(sum, 9);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn par_fold_up<K, R, F>(
+        &self,
+        node_id: &K,
+        threads: usize,
+        fold: F,
+    ) -> crate::prelude::Result<R>
+    where
+        Q: Borrow<K>,
+        K: Debug + Hash + Eq + ?Sized,
+        R: Send,
+        F: Fn(&Q, Option<&T>, Vec<R>) -> R + Sync,
+    {
+        let index = NodeIndex::new(self)?;
+        let node_id = index.get(node_id)?.get_node_id()?;
+        index.fold(&node_id, threads.max(1), &|node, children| {
+            let node_id = node.get_node_id()?;
+            Ok(node.with_value(|value| fold(&node_id, value, children)))
+        })
+    }
+
+    /// Find a node of a subtree using several threads.
+    ///
+    /// This method searches the subtree of the node with the given id for a node that matches the
+    /// predicate. The subtrees of sibling nodes are searched on separate scoped threads, up to
+    /// `threads` threads in total. When several nodes match, the first one in pre-order is returned,
+    /// whatever the number of threads.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the root of the subtree to search.
+    /// * `threads` - The maximum number of threads to use, including the current one. A value of
+    ///   `0` is treated as `1`.
+    /// * `predicate` - A closure that receives the id and the value of a node.
+    ///
+    /// # Returns
+    ///
+    /// The id of the first matching node in pre-order, or `None` if no node matches.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::*;
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: ThreadSafeTree<i32, i32> = ThreadSafeTree::new_in(None, ArcStorage);
+    /// let node_1 = tree.add_node(Node::new_in(1, Some(2), ArcStorage), None)?;
+    /// tree.add_node(Node::new_in(2, Some(3), ArcStorage), Some(&node_1))?;
+    /// tree.add_node(Node::new_in(3, Some(3), ArcStorage), Some(&node_1))?;
+    ///
+    /// let found = tree.par_find(&node_1, 4, |_, value| value == Some(&3))?;
+    /// assert_eq!(found, Some(2));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn par_find<K, F>(
+        &self,
+        node_id: &K,
+        threads: usize,
+        predicate: F,
+    ) -> crate::prelude::Result<Option<Q>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Hash + Eq + ?Sized,
+        F: Fn(&Q, Option<&T>) -> bool + Sync,
+    {
+        let index = NodeIndex::new(self)?;
+        let node_id = index.get(node_id)?.get_node_id()?;
+        index.find(&node_id, threads.max(1), &predicate)
+    }
+}
+
+/// The nodes of a tree indexed by their ids.
+struct NodeIndex<'a, Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+{
+    nodes: HashMap<Q, &'a Node<Q, T, ArcStorage>>,
+}
+
+impl<'a, Q, T> NodeIndex<'a, Q, T>
+where
+    Q: PartialEq + Eq + Clone + Debug + Hash + Ord + Send + Sync,
+    T: Send + Sync,
+{
+    /// Index the nodes of a tree.
+    fn new<E>(tree: &'a Tree<Q, T, ArcStorage, E>) -> crate::prelude::Result<Self> {
+        let mut nodes = HashMap::new();
+        for node in tree.get_nodes().iter() {
+            nodes.entry(node.get_node_id()?).or_insert(node);
+        }
+        Ok(Self { nodes })
+    }
+
+    /// Get a node by its id.
+    fn get<K>(&self, node_id: &K) -> crate::prelude::Result<&'a Node<Q, T, ArcStorage>>
+    where
+        Q: Borrow<K>,
+        K: Debug + Hash + Eq + ?Sized,
+    {
+        self.nodes
+            .get(node_id)
+            .copied()
+            .ok_or_else(|| Error::node_not_found(node_id))
+    }
+
+    /// Combine a node with the results of its children, folding the children on up to `threads`
+    /// threads.
+    fn fold<R, F>(&self, node_id: &Q, threads: usize, fold: &F) -> crate::prelude::Result<R>
+    where
+        R: Send,
+        F: Fn(&Node<Q, T, ArcStorage>, Vec<R>) -> crate::prelude::Result<R> + Sync,
+    {
+        let node = self.get(node_id)?;
+        let children = self.split(&node.get_children_ids()?, threads, |child_id, threads| {
+            self.fold(child_id, threads, fold)
+        })?;
+        fold(node, children)
+    }
+
+    /// Find the first node in pre-order that matches the predicate, searching the children on up to
+    /// `threads` threads.
+    fn find<F>(
+        &self,
+        node_id: &Q,
+        threads: usize,
+        predicate: &F,
+    ) -> crate::prelude::Result<Option<Q>>
+    where
+        F: Fn(&Q, Option<&T>) -> bool + Sync,
+    {
+        let node = self.get(node_id)?;
+        if node.with_value(|value| predicate(node_id, value)) {
+            return Ok(Some(node_id.clone()));
+        }
+        let children_ids = node.get_children_ids()?;
+        if threads == 1 {
+            for child_id in children_ids.iter() {
+                if let Some(found) = self.find(child_id, 1, predicate)? {
+                    return Ok(Some(found));
+                }
+            }
+            return Ok(None);
+        }
+        let found = self.split(&children_ids, threads, |child_id, threads| {
+            self.find(child_id, threads, predicate)
+        })?;
+        Ok(found.into_iter().flatten().next())
+    }
+
+    /// Run `work` for each id, splitting the ids into contiguous groups that run on separate
+    /// threads.
+    ///
+    /// Each call of `work` receives the number of threads it may use itself. The results are
+    /// returned in the order of the ids, and the error of the first failing id wins.
+    fn split<R, W>(&self, ids: &[Q], threads: usize, work: W) -> crate::prelude::Result<Vec<R>>
+    where
+        R: Send,
+        W: Fn(&Q, usize) -> crate::prelude::Result<R> + Sync,
+    {
+        if threads == 1 || ids.len() <= 1 {
+            return ids.iter().map(|x| work(x, threads)).collect();
+        }
+        let groups = threads.min(ids.len());
+        let chunks = ids
+            .chunks((ids.len() + groups - 1) / groups)
+            .collect::<Vec<_>>();
+        let budget = threads / chunks.len();
+        let work = &work;
+        scope(|scope| {
+            let handles = chunks[1..]
+                .iter()
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|x| work(x, budget))
+                            .collect::<crate::prelude::Result<Vec<_>>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+            let mut results = chunks[0]
+                .iter()
+                .map(|x| work(x, budget))
+                .collect::<crate::prelude::Result<Vec<_>>>();
+            for handle in handles {
+                let chunk_results = handle.join().unwrap_or_else(|x| resume_unwind(x));
+                results = match (results, chunk_results) {
+                    (Ok(mut results), Ok(mut chunk_results)) => {
+                        results.append(&mut chunk_results);
+                        Ok(results)
+                    }
+                    (Err(error), _) | (Ok(_), Err(error)) => Err(error),
+                };
+            }
+            results
+        })
+    }
+}
@@ -0,0 +1,296 @@
+use crate::lib::*;
+
+/// A link to a node of a `PersistentMap`.
+type Link<K, V> = Option<Arc<MapNode<K, V>>>;
+
+/// A node of the balanced binary search tree behind a `PersistentMap`.
+struct MapNode<K, V> {
+    key: K,
+    value: V,
+    height: usize,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+/// An ordered map whose updates return a new map.
+///
+/// The map is an AVL tree whose nodes are shared with `Arc`. Inserting or removing a key copies only
+/// the nodes on the path to that key, so every version of the map shares the rest of its nodes with
+/// the version it was made from. Cloning the map is O(1).
+pub(crate) struct PersistentMap<K, V> {
+    root: Link<K, V>,
+    len: usize,
+}
+
+impl<K, V> PersistentMap<K, V> {
+    /// Get the number of entries in the map.
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Iterate over the entries of the map in the order of their keys.
+    pub(crate) fn iter(&self) -> MapIter<'_, K, V> {
+        let mut iter = MapIter { stack: vec![] };
+        iter.push_left(self.root.as_deref());
+        iter
+    }
+}
+
+impl<K, V> PersistentMap<K, V>
+where
+    K: Ord + Clone,
+    V: Clone,
+{
+    /// Get the value of a key, which can be given in any borrowed form of the key type.
+    pub(crate) fn get<B>(&self, key: &B) -> Option<&V>
+    where
+        K: Borrow<B>,
+        B: Ord + ?Sized,
+    {
+        self.get_key_value(key).map(|(_, value)| value)
+    }
+
+    /// Get the stored key and the value of a key.
+    pub(crate) fn get_key_value<B>(&self, key: &B) -> Option<(&K, &V)>
+    where
+        K: Borrow<B>,
+        B: Ord + ?Sized,
+    {
+        let mut link = self.root.as_deref();
+        while let Some(node) = link {
+            link = match key.cmp(node.key.borrow()) {
+                Ordering::Less => node.left.as_deref(),
+                Ordering::Greater => node.right.as_deref(),
+                Ordering::Equal => return Some((&node.key, &node.value)),
+            };
+        }
+        None
+    }
+
+    /// Return a new map where `key` is set to `value`.
+    pub(crate) fn insert(&self, key: K, value: V) -> Self {
+        let (root, replaced) = insert(&self.root, key, value);
+        Self {
+            root: Some(root),
+            len: if replaced { self.len } else { self.len + 1 },
+        }
+    }
+
+    /// Return a new map without `key`.
+    pub(crate) fn remove(&self, key: &K) -> Self {
+        match remove(&self.root, key) {
+            Some(root) => Self {
+                root,
+                len: self.len - 1,
+            },
+            None => self.clone(),
+        }
+    }
+}
+
+impl<K, V> Clone for PersistentMap<K, V> {
+    /// Clone the map without copying its nodes.
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<K, V> Default for PersistentMap<K, V> {
+    fn default() -> Self {
+        Self { root: None, len: 0 }
+    }
+}
+
+/// An iterator over the entries of a `PersistentMap` in the order of their keys.
+pub(crate) struct MapIter<'a, K, V> {
+    stack: Vec<&'a MapNode<K, V>>,
+}
+
+impl<'a, K, V> MapIter<'a, K, V> {
+    /// Push a node and all of its left descendants.
+    fn push_left(&mut self, mut link: Option<&'a MapNode<K, V>>) {
+        while let Some(node) = link {
+            self.stack.push(node);
+            link = node.left.as_deref();
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for MapIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left(node.right.as_deref());
+        Some((&node.key, &node.value))
+    }
+}
+
+/// Get the height of a subtree.
+fn height<K, V>(link: &Link<K, V>) -> usize {
+    link.as_ref().map_or(0, |x| x.height)
+}
+
+/// Create a node from its entry and subtrees.
+fn make<K, V>(key: K, value: V, left: Link<K, V>, right: Link<K, V>) -> Arc<MapNode<K, V>> {
+    Arc::new(MapNode {
+        key,
+        value,
+        height: height(&left).max(height(&right)) + 1,
+        left,
+        right,
+    })
+}
+
+/// Create a node from its entry and subtrees, rotating it if the subtrees are unbalanced.
+///
+/// The heights of the subtrees must differ by at most two.
+fn balance<K, V>(key: K, value: V, left: Link<K, V>, right: Link<K, V>) -> Arc<MapNode<K, V>>
+where
+    K: Clone,
+    V: Clone,
+{
+    let (left_height, right_height) = (height(&left), height(&right));
+    if left_height > right_height + 1 {
+        let Some(node) = left.as_deref() else {
+            unreachable!("Error: The left subtree can not be empty.");
+        };
+        match node.right.as_deref() {
+            Some(inner) if height(&node.left) < height(&node.right) => make(
+                inner.key.clone(),
+                inner.value.clone(),
+                Some(make(
+                    node.key.clone(),
+                    node.value.clone(),
+                    node.left.clone(),
+                    inner.left.clone(),
+                )),
+                Some(make(key, value, inner.right.clone(), right)),
+            ),
+            _ => make(
+                node.key.clone(),
+                node.value.clone(),
+                node.left.clone(),
+                Some(make(key, value, node.right.clone(), right)),
+            ),
+        }
+    } else if right_height > left_height + 1 {
+        let Some(node) = right.as_deref() else {
+            unreachable!("Error: The right subtree can not be empty.");
+        };
+        match node.left.as_deref() {
+            Some(inner) if height(&node.right) < height(&node.left) => make(
+                inner.key.clone(),
+                inner.value.clone(),
+                Some(make(key, value, left, inner.left.clone())),
+                Some(make(
+                    node.key.clone(),
+                    node.value.clone(),
+                    inner.right.clone(),
+                    node.right.clone(),
+                )),
+            ),
+            _ => make(
+                node.key.clone(),
+                node.value.clone(),
+                Some(make(key, value, left, node.left.clone())),
+                node.right.clone(),
+            ),
+        }
+    } else {
+        make(key, value, left, right)
+    }
+}
+
+/// Insert an entry into a subtree and return the new subtree and whether a value was replaced.
+fn insert<K, V>(link: &Link<K, V>, key: K, value: V) -> (Arc<MapNode<K, V>>, bool)
+where
+    K: Ord + Clone,
+    V: Clone,
+{
+    let Some(node) = link else {
+        return (make(key, value, None, None), false);
+    };
+    match key.cmp(&node.key) {
+        Ordering::Less => {
+            let (left, replaced) = insert(&node.left, key, value);
+            let node = balance(
+                node.key.clone(),
+                node.value.clone(),
+                Some(left),
+                node.right.clone(),
+            );
+            (node, replaced)
+        }
+        Ordering::Greater => {
+            let (right, replaced) = insert(&node.right, key, value);
+            let node = balance(
+                node.key.clone(),
+                node.value.clone(),
+                node.left.clone(),
+                Some(right),
+            );
+            (node, replaced)
+        }
+        Ordering::Equal => (
+            make(key, value, node.left.clone(), node.right.clone()),
+            true,
+        ),
+    }
+}
+
+/// Remove a key from a subtree and return the new subtree, or `None` if the key is not present.
+fn remove<K, V>(link: &Link<K, V>, key: &K) -> Option<Link<K, V>>
+where
+    K: Ord + Clone,
+    V: Clone,
+{
+    let node = link.as_ref()?;
+    let subtree = match key.cmp(&node.key) {
+        Ordering::Less => balance(
+            node.key.clone(),
+            node.value.clone(),
+            remove(&node.left, key)?,
+            node.right.clone(),
+        ),
+        Ordering::Greater => balance(
+            node.key.clone(),
+            node.value.clone(),
+            node.left.clone(),
+            remove(&node.right, key)?,
+        ),
+        Ordering::Equal => match (&node.left, &node.right) {
+            (left, None) => return Some(left.clone()),
+            (None, right) => return Some(right.clone()),
+            (left, Some(right)) => {
+                let (key, value, right) = remove_min(right);
+                balance(key, value, left.clone(), right)
+            }
+        },
+    };
+    Some(Some(subtree))
+}
+
+/// Remove the smallest entry of a subtree and return it with the new subtree.
+fn remove_min<K, V>(node: &Arc<MapNode<K, V>>) -> (K, V, Link<K, V>)
+where
+    K: Clone,
+    V: Clone,
+{
+    match &node.left {
+        None => (node.key.clone(), node.value.clone(), node.right.clone()),
+        Some(left) => {
+            let (key, value, left) = remove_min(left);
+            let node = balance(
+                node.key.clone(),
+                node.value.clone(),
+                left,
+                node.right.clone(),
+            );
+            (key, value, Some(node))
+        }
+    }
+}
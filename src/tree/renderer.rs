@@ -0,0 +1,406 @@
+use crate::lib::*;
+
+/// The characters used to draw the branches of a rendered tree.
+///
+/// This enum represents the set of glyphs used by a `TreeRenderer` to connect the nodes of a tree.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub enum TreeGlyphs {
+    /// Draw the branches with Unicode box drawing characters (`├── `, `└── `, `│   `) and elide
+    /// nodes with `…`. This is the style used when displaying a tree.
+    #[default]
+    Unicode,
+    /// Draw the branches with plain ASCII characters (`|-- `, `` `-- ``, `|   `) and elide nodes
+    /// with `...`.
+    Ascii,
+}
+
+impl TreeGlyphs {
+    /// Get the prefix of a child that has siblings after it.
+    fn branch(&self) -> &'static str {
+        match self {
+            TreeGlyphs::Unicode => "├── ",
+            TreeGlyphs::Ascii => "|-- ",
+        }
+    }
+
+    /// Get the prefix of the last child of a node.
+    fn last_branch(&self) -> &'static str {
+        match self {
+            TreeGlyphs::Unicode => "└── ",
+            TreeGlyphs::Ascii => "`-- ",
+        }
+    }
+
+    /// Get the indentation below a child that has siblings after it.
+    fn vertical(&self) -> &'static str {
+        match self {
+            TreeGlyphs::Unicode => "│   ",
+            TreeGlyphs::Ascii => "|   ",
+        }
+    }
+
+    /// Get the marker written in place of nodes that are not rendered.
+    fn ellipsis(&self) -> &'static str {
+        match self {
+            TreeGlyphs::Unicode => "…",
+            TreeGlyphs::Ascii => "...",
+        }
+    }
+}
+
+/// A closure that labels a node from its id and value.
+type NodeFormatter<'a, Q, T> = Box<dyn Fn(&Q, Option<&T>) -> String + 'a>;
+
+/// A configurable text renderer for trees.
+///
+/// This struct holds the options used by `Tree::render` to write a tree as text. The renderer is
+/// built with chained calls and can be reused for any number of trees. By default it renders the
+/// tree exactly as it is displayed: a header made of the tree name underlined with `*`, Unicode
+/// branches and every node of the tree.
+///
+/// Nodes are labelled by a formatting closure that receives the id and the value of the node, so
+/// ids, values or both can be shown without requiring the value to implement `Default`.
+///
+/// # Type Parameters
+///
+/// * `Q` - The type of the node id.
+/// * `T` - The type of the node value.
+///
+/// # Example
+///
+/// ```rust
+/// # use tree_ds::prelude::{Node, Tree, TreeGlyphs, TreeRenderer};
+///
+/// # fn main() -> tree_ds::prelude::Result<()> {
+/// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+/// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+/// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+/// tree.add_node(Node::new(3, Some(4)), Some(&node_2))?;
+///
+/// let renderer = TreeRenderer::with_formatter(|id: &i32, value: Option<&i32>| match value {
+///     Some(value) => format!("{id} = {value}"),
+///     None => format!("{id}"),
+/// })
+/// .glyphs(TreeGlyphs::Ascii)
+/// .max_depth(1)
+/// .header(false);
+/// assert_eq!(tree.render(&renderer)?, "1 = 2\n`-- 2 = 3\n    `-- ... 1 more\n");
+/// # Ok(())
+/// # }
+/// ```
+pub struct TreeRenderer<'a, Q, T> {
+    glyphs: TreeGlyphs,
+    formatter: NodeFormatter<'a, Q, T>,
+    max_depth: Option<usize>,
+    max_children: Option<usize>,
+    show_header: bool,
+}
+
+impl<Q, T> TreeRenderer<'_, Q, T>
+where
+    Q: Display,
+    T: Display,
+{
+    /// Create a new renderer with the default options.
+    ///
+    /// This method creates a renderer that labels each node with its value, or with nothing if the
+    /// node has no value. When the `print_node_id` feature is enabled, the label is prefixed with
+    /// the id of the node, as when displaying a node.
+    ///
+    /// # Returns
+    ///
+    /// A new renderer.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::TreeRenderer;
+    ///
+    /// let renderer = TreeRenderer::<i32, i32>::new();
+    /// ```
+    pub fn new() -> Self {
+        Self::with_formatter(|_id: &Q, value: Option<&T>| {
+            let value = value.map(|x| x.to_string()).unwrap_or_default();
+            #[cfg(feature = "print_node_id")]
+            return format!("{_id}: {value}");
+            #[cfg(not(feature = "print_node_id"))]
+            value
+        })
+    }
+}
+
+impl<'a, Q, T> TreeRenderer<'a, Q, T> {
+    /// Create a new renderer that labels the nodes with the given closure.
+    ///
+    /// This method creates a renderer with the default options whose node labels are produced by
+    /// `formatter`. The id and the value do not need to implement any trait.
+    ///
+    /// # Arguments
+    ///
+    /// * `formatter` - A closure that receives the id and the value of a node and returns its label.
+    ///
+    /// # Returns
+    ///
+    /// A new renderer.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::TreeRenderer;
+    ///
+    /// let renderer = TreeRenderer::<i32, Vec<u8>>::with_formatter(|id, _| id.to_string());
+    /// ```
+    pub fn with_formatter<F>(formatter: F) -> Self
+    where
+        F: Fn(&Q, Option<&T>) -> String + 'a,
+    {
+        Self {
+            glyphs: TreeGlyphs::Unicode,
+            formatter: Box::new(formatter),
+            max_depth: None,
+            max_children: None,
+            show_header: true,
+        }
+    }
+
+    /// Set the closure used to label the nodes.
+    ///
+    /// # Arguments
+    ///
+    /// * `formatter` - A closure that receives the id and the value of a node and returns its label.
+    ///
+    /// # Returns
+    ///
+    /// The renderer with the new formatter.
+    pub fn formatter<F>(mut self, formatter: F) -> Self
+    where
+        F: Fn(&Q, Option<&T>) -> String + 'a,
+    {
+        self.formatter = Box::new(formatter);
+        self
+    }
+
+    /// Set the glyphs used to draw the branches.
+    ///
+    /// # Arguments
+    ///
+    /// * `glyphs` - The glyphs to use.
+    ///
+    /// # Returns
+    ///
+    /// The renderer with the new glyphs.
+    pub fn glyphs(mut self, glyphs: TreeGlyphs) -> Self {
+        self.glyphs = glyphs;
+        self
+    }
+
+    /// Limit the depth of the rendered nodes.
+    ///
+    /// Nodes deeper than `max_depth` are not rendered. A node at `max_depth` that has children gets
+    /// a single `… N more` line instead, where `N` is the number of its children. The root node has
+    /// a depth of `0`.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_depth` - The depth of the deepest nodes to render.
+    ///
+    /// # Returns
+    ///
+    /// The renderer with the new maximum depth.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Limit the number of rendered children of each node.
+    ///
+    /// Only the first `max_children` children of a node are rendered. The remaining children are
+    /// replaced by a single `… N more` line, where `N` is the number of children left out.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_children` - The number of children to render for each node.
+    ///
+    /// # Returns
+    ///
+    /// The renderer with the new maximum number of children.
+    pub fn max_children(mut self, max_children: usize) -> Self {
+        self.max_children = Some(max_children);
+        self
+    }
+
+    /// Set whether the name of the tree is rendered.
+    ///
+    /// When enabled, the name of a named tree is written first and underlined with `*`. It is
+    /// enabled by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `show_header` - Whether to render the name of the tree.
+    ///
+    /// # Returns
+    ///
+    /// The renderer with the new header option.
+    pub fn header(mut self, show_header: bool) -> Self {
+        self.show_header = show_header;
+        self
+    }
+
+    /// Label a node from its id and value with the formatter of the renderer.
+    pub(crate) fn label(&self, node_id: &Q, value: Option<&T>) -> String {
+        (self.formatter)(node_id, value)
+    }
+
+    /// Render a tree from its name, its root node and a closure that looks up nodes.
+    ///
+    /// The nodes are laid out with `layout` and written one per line after the header.
+    pub(crate) fn render_tree<F>(
+        &self,
+        name: Option<&str>,
+        root_id: Option<Q>,
+        get_node: F,
+    ) -> crate::prelude::Result<String>
+    where
+        F: FnMut(&Q) -> crate::prelude::Result<(String, Vec<Q>)>,
+    {
+        let mut output = String::new();
+        if let (true, Some(name)) = (self.show_header, name) {
+            output.push_str(name);
+            output.push('\n');
+            output.extend(name.chars().map(|_| '*'));
+            output.push('\n');
+        }
+        for line in self.layout(root_id, get_node)? {
+            output.push_str(&line.prefix);
+            output.push_str(&line.label);
+            output.push('\n');
+        }
+        Ok(output)
+    }
+
+    /// Lay out the lines of a tree from its root node and a closure that looks up nodes.
+    ///
+    /// The `get_node` closure returns the label and the ids of the children of the node with the
+    /// given id, where the label is usually built with `label`. The tree is walked in pre-order
+    /// without recursion and only the nodes that are rendered are looked up. This is the traversal
+    /// shared by the text output and the diagram exporters of a tree.
+    pub(crate) fn layout<F>(
+        &self,
+        root_id: Option<Q>,
+        mut get_node: F,
+    ) -> crate::prelude::Result<Vec<TreeLine>>
+    where
+        F: FnMut(&Q) -> crate::prelude::Result<(String, Vec<Q>)>,
+    {
+        enum Line<Q> {
+            Node(Q),
+            More(usize),
+        }
+
+        let mut lines = vec![];
+        let Some(root_id) = root_id else {
+            return Ok(lines);
+        };
+
+        // Each entry holds the prefix of its parent, whether it is the last child, its depth and
+        // the index of the line of its parent.
+        let mut stack = vec![(Line::Node(root_id), String::new(), None, 0, None)];
+        while let Some((line, parent_prefix, is_last_child, depth, parent)) = stack.pop() {
+            let (prefix, child_prefix) = match is_last_child {
+                None => (parent_prefix.clone(), parent_prefix),
+                Some(true) => (
+                    format!("{parent_prefix}{}", self.glyphs.last_branch()),
+                    format!("{parent_prefix}    "),
+                ),
+                Some(false) => (
+                    format!("{parent_prefix}{}", self.glyphs.branch()),
+                    format!("{parent_prefix}{}", self.glyphs.vertical()),
+                ),
+            };
+            let id = match line {
+                Line::Node(id) => id,
+                Line::More(count) => {
+                    lines.push(TreeLine {
+                        label: format!("{} {count} more", self.glyphs.ellipsis()),
+                        prefix,
+                        depth,
+                        parent,
+                    });
+                    continue;
+                }
+            };
+            let index = lines.len();
+            let (label, children_ids) = get_node(&id)?;
+            lines.push(TreeLine {
+                label,
+                prefix,
+                depth,
+                parent,
+            });
+            if children_ids.is_empty() {
+                continue;
+            }
+
+            let mut children = vec![];
+            if self.max_depth.is_some_and(|x| depth >= x) {
+                children.push(Line::More(children_ids.len()));
+            } else {
+                let shown = self
+                    .max_children
+                    .unwrap_or(usize::MAX)
+                    .min(children_ids.len());
+                let hidden = children_ids.len() - shown;
+                children.extend(children_ids.into_iter().take(shown).map(Line::Node));
+                if hidden > 0 {
+                    children.push(Line::More(hidden));
+                }
+            }
+            let last_index = children.len() - 1;
+            for (child_index, child) in children.into_iter().enumerate().rev() {
+                stack.push((
+                    child,
+                    child_prefix.clone(),
+                    Some(child_index == last_index),
+                    depth + 1,
+                    Some(index),
+                ));
+            }
+        }
+        Ok(lines)
+    }
+}
+
+/// A line of a tree laid out by a `TreeRenderer`.
+pub(crate) struct TreeLine {
+    /// The label of the node, or the `… N more` marker of the nodes that are not rendered.
+    pub(crate) label: String,
+    /// The branches written before the label.
+    pub(crate) prefix: String,
+    /// The depth of the line. The root node has a depth of `0`.
+    pub(crate) depth: usize,
+    /// The index of the line of the parent node.
+    pub(crate) parent: Option<usize>,
+}
+
+impl<Q, T> Default for TreeRenderer<'_, Q, T>
+where
+    Q: Display,
+    T: Display,
+{
+    /// Create a new renderer with the default options.
+    fn default() -> Self {
+        TreeRenderer::new()
+    }
+}
+
+impl<Q, T> Debug for TreeRenderer<'_, Q, T> {
+    /// Debug the renderer options.
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("TreeRenderer")
+            .field("glyphs", &self.glyphs)
+            .field("max_depth", &self.max_depth)
+            .field("max_children", &self.max_children)
+            .field("show_header", &self.show_header)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,248 @@
+use crate::lib::*;
+use crate::node::{ArcStorage, Node};
+use crate::tree::{NodeRemovalStrategy, SubTree, ThreadSafeTree};
+
+/// A thread safe tree that readers see as a series of consistent versions.
+///
+/// This struct keeps the current version of a `ThreadSafeTree` and lets any number of threads read
+/// it while others write to it. Readers take a `TreeSnapshot` of the current version and keep
+/// reading that version for as long as they hold the snapshot, so they never observe a half applied
+/// change such as a `remove_node` or an `add_subtree` in progress.
+///
+/// Writers never modify a published version. Each update copies the current version, applies its
+/// changes to the copy and then publishes the copy as the new current version in a single step. If
+/// the update fails, nothing is published. Updates are applied one at a time, and taking a snapshot
+/// only waits for the publication of a version, not for an update to finish.
+///
+/// # Type Parameters
+///
+/// * `Q` - The type of the node id.
+/// * `T` - The type of the node value.
+///
+/// # Example
+///
+/// ```rust
+/// # use tree_ds::prelude::*;
+///
+/// # fn main() -> tree_ds::prelude::Result<()> {
+/// let tree: SharedTree<i32, i32> = SharedTree::new(ThreadSafeTree::new_in(None, ArcStorage));
+/// tree.add_node(Node::new_in(1, Some(2), ArcStorage), None)?;
+///
+/// let snapshot = tree.snapshot();
+/// tree.add_node(Node::new_in(2, Some(3), ArcStorage), Some(&1))?;
+///
+/// assert_eq!(snapshot.get_nodes().len(), 1);
+/// assert_eq!(tree.snapshot().get_nodes().len(), 2);
+/// # Ok(())
+/// # }
+/// ```
+pub struct SharedTree<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+{
+    current: RwLock<TreeSnapshot<Q, T>>,
+    writer: Mutex<()>,
+}
+
+/// A consistent, read only version of a `SharedTree`.
+///
+/// A snapshot dereferences to the tree of the version it was taken from. The version stays alive
+/// and unchanged for as long as the snapshot exists, even after newer versions are published.
+/// Snapshots are cheap to clone and can be sent to other threads.
+///
+/// The nodes of a snapshot share their data with the published version, so changes must be made
+/// through `SharedTree::update` and never through the nodes of a snapshot.
+pub struct TreeSnapshot<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+{
+    version: u64,
+    tree: Arc<ThreadSafeTree<Q, T>>,
+}
+
+impl<Q, T> SharedTree<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+{
+    /// Create a new shared tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The tree to publish as the first version.
+    ///
+    /// # Returns
+    ///
+    /// A new shared tree whose current version is `0`.
+    pub fn new(tree: ThreadSafeTree<Q, T>) -> Self {
+        Self {
+            current: RwLock::new(TreeSnapshot {
+                version: 0,
+                tree: Arc::new(tree),
+            }),
+            writer: Mutex::new(()),
+        }
+    }
+
+    /// Take a snapshot of the current version.
+    ///
+    /// # Returns
+    ///
+    /// A snapshot of the latest published version.
+    pub fn snapshot(&self) -> TreeSnapshot<Q, T> {
+        self.current.read().clone()
+    }
+
+    /// Get the number of the current version.
+    ///
+    /// The first version is `0` and each successful update increments it by one.
+    pub fn version(&self) -> u64 {
+        self.current.read().version
+    }
+}
+
+impl<Q, T> SharedTree<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Debug + Hash + Ord,
+    T: Clone,
+{
+    /// Apply changes to the tree and publish them as a new version.
+    ///
+    /// This method waits for any other update to finish, copies the current version and calls
+    /// `update` with the copy. If `update` succeeds, the copy becomes the current version. If it
+    /// fails, the error is returned and the current version is left as it was. Readers keep seeing
+    /// the previous version until the new one is published. The copy is made with
+    /// `Tree::deep_clone`, in `O(n log n)` time for a tree of `n` nodes.
+    ///
+    /// # Arguments
+    ///
+    /// * `update` - A closure that applies the changes to the new version.
+    ///
+    /// # Returns
+    ///
+    /// The result of `update`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::*;
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let tree: SharedTree<i32, i32> = SharedTree::new(ThreadSafeTree::new_in(None, ArcStorage));
+    /// tree.update(|tree| {
+    ///     let root = tree.add_node(Node::new_in(1, Some(2), ArcStorage), None)?;
+    ///     tree.add_node(Node::new_in(2, Some(3), ArcStorage), Some(&root))?;
+    ///     Ok(())
+    /// })?;
+    /// assert_eq!(tree.version(), 1);
+    /// assert_eq!(tree.snapshot().get_height()?, 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn update<R, F>(&self, update: F) -> crate::prelude::Result<R>
+    where
+        F: FnOnce(&mut ThreadSafeTree<Q, T>) -> crate::prelude::Result<R>,
+    {
+        let _writer = self.writer.lock();
+        let current = self.snapshot();
+        let mut tree = current.tree.deep_clone()?;
+        let result = update(&mut tree)?;
+        *self.current.write() = TreeSnapshot {
+            version: current.version + 1,
+            tree: Arc::new(tree),
+        };
+        Ok(result)
+    }
+
+    /// Add a node to the tree and publish the change as a new version.
+    ///
+    /// This method behaves like `Tree::add_node`.
+    pub fn add_node(
+        &self,
+        node: Node<Q, T, ArcStorage>,
+        parent_id: Option<&Q>,
+    ) -> crate::prelude::Result<Q> {
+        self.update(|tree| tree.add_node(node, parent_id))
+    }
+
+    /// Remove a node from the tree and publish the change as a new version.
+    ///
+    /// This method behaves like `Tree::remove_node`.
+    pub fn remove_node(
+        &self,
+        node_id: &Q,
+        strategy: NodeRemovalStrategy,
+    ) -> crate::prelude::Result<()> {
+        self.update(|tree| tree.remove_node(node_id, strategy))
+    }
+
+    /// Add a subtree to the tree and publish the change as a new version.
+    ///
+    /// This method behaves like `Tree::add_subtree`.
+    pub fn add_subtree(
+        &self,
+        node_id: &Q,
+        subtree: SubTree<Q, T, ArcStorage>,
+    ) -> crate::prelude::Result<()> {
+        self.update(|tree| tree.add_subtree(node_id, subtree))
+    }
+}
+
+impl<Q, T> Debug for SharedTree<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Debug,
+    T: Debug,
+{
+    /// Debug the current version of the tree.
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("SharedTree")
+            .field("current", &self.snapshot())
+            .finish()
+    }
+}
+
+impl<Q, T> TreeSnapshot<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+{
+    /// Get the number of the version of this snapshot.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+}
+
+impl<Q, T> Clone for TreeSnapshot<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+{
+    /// Clone the snapshot without copying the tree.
+    fn clone(&self) -> Self {
+        Self {
+            version: self.version,
+            tree: self.tree.clone(),
+        }
+    }
+}
+
+impl<Q, T> Deref for TreeSnapshot<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+{
+    type Target = ThreadSafeTree<Q, T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.tree
+    }
+}
+
+impl<Q, T> Debug for TreeSnapshot<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Debug,
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("TreeSnapshot")
+            .field("version", &self.version)
+            .field("tree", &*self.tree)
+            .finish()
+    }
+}
@@ -0,0 +1,179 @@
+use crate::lib::*;
+use crate::node::{DefaultStorage, Node, NodeStorage, Nodes};
+
+/// An event of a walk over a tree.
+///
+/// A walk enters a node before its children and leaves it after them, so the events of a node
+/// surround the events of its descendants. See `Tree::walk_events`.
+#[derive(Debug, PartialEq, Eq)]
+pub enum WalkEvent<'a, Q, T, P = DefaultStorage>
+where
+    Q: PartialEq + Eq + Clone,
+    P: NodeStorage,
+{
+    /// The walk reached the node, before its children.
+    Enter(&'a Node<Q, T, P>),
+    /// The walk is done with the node and its children.
+    Leave(&'a Node<Q, T, P>),
+}
+
+impl<Q, T, P> Clone for WalkEvent<'_, Q, T, P>
+where
+    Q: PartialEq + Eq + Clone,
+    P: NodeStorage,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Q, T, P> Copy for WalkEvent<'_, Q, T, P>
+where
+    Q: PartialEq + Eq + Clone,
+    P: NodeStorage,
+{
+}
+
+/// What a walk does after a callback of a `TreeVisitor`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum WalkControl {
+    /// Go on with the walk.
+    #[default]
+    Continue,
+    /// Do not walk the children of the node that was entered. The node is still left. This has the
+    /// same effect as `Continue` when returned by `TreeVisitor::leave`.
+    SkipChildren,
+    /// End the walk. No other callback is called.
+    Stop,
+}
+
+/// A visitor that is called when a walk enters and leaves the nodes of a tree.
+///
+/// Both callbacks get the depth of the node relative to the node the walk started from, whose depth
+/// is `0`, and do nothing by default. See `Tree::walk`.
+///
+/// # Type Parameters
+///
+/// * `Q` - The type of the node id.
+/// * `T` - The type of the node value.
+/// * `P` - The storage used to share the data of the nodes. See `NodeStorage`.
+///
+/// # Example
+///
+/// ```rust
+/// # use tree_ds::prelude::{Node, Tree, TreeVisitor, WalkControl};
+///
+/// # fn main() -> tree_ds::prelude::Result<()> {
+/// struct Xml(String);
+///
+/// impl TreeVisitor<i32, &str> for Xml {
+///     fn enter(&mut self, node: &Node<i32, &str>, _depth: usize) -> WalkControl {
+///         self.0 += &format!("<{}>", node.get_value().unwrap().unwrap());
+///         WalkControl::Continue
+///     }
+///
+///     fn leave(&mut self, node: &Node<i32, &str>, _depth: usize) -> WalkControl {
+///         self.0 += &format!("</{}>", node.get_value().unwrap().unwrap());
+///         WalkControl::Continue
+///     }
+/// }
+///
+/// let mut tree: Tree<i32, &str> = Tree::new(Some("Sample Tree"));
+/// let node_1 = tree.add_node(Node::new(1, Some("a")), None)?;
+/// tree.add_node(Node::new(2, Some("b")), Some(&node_1))?;
+/// tree.add_node(Node::new(3, Some("c")), Some(&node_1))?;
+///
+/// let mut xml = Xml(String::new());
+/// tree.walk(&node_1, &mut xml)?;
+/// assert_eq!(xml.0, "<a><b></b><c></c></a>");
+/// # Ok(())
+/// # }
+/// ```
+pub trait TreeVisitor<Q, T, P = DefaultStorage>
+where
+    Q: PartialEq + Eq + Clone,
+    P: NodeStorage,
+{
+    /// Called when the walk reaches a node, before its children.
+    fn enter(&mut self, _node: &Node<Q, T, P>, _depth: usize) -> WalkControl {
+        WalkControl::Continue
+    }
+
+    /// Called when the walk is done with a node and its children.
+    fn leave(&mut self, _node: &Node<Q, T, P>, _depth: usize) -> WalkControl {
+        WalkControl::Continue
+    }
+}
+
+/// An iterator over the events of a walk over a tree.
+///
+/// This struct is returned by `Tree::walk_events`.
+#[derive(Debug)]
+pub struct WalkEvents<'a, Q, T, P = DefaultStorage>
+where
+    Q: PartialEq + Eq + Clone,
+    P: NodeStorage,
+{
+    nodes: &'a Nodes<Q, T, P>,
+    start: Option<&'a Node<Q, T, P>>,
+    stack: Vec<(&'a Node<Q, T, P>, usize)>,
+    entered: bool,
+}
+
+impl<'a, Q, T, P> WalkEvents<'a, Q, T, P>
+where
+    Q: PartialEq + Eq + Clone,
+    P: NodeStorage,
+{
+    /// Create a walk that starts from a node.
+    pub(crate) fn new(nodes: &'a Nodes<Q, T, P>, node: &'a Node<Q, T, P>) -> Self {
+        Self {
+            nodes,
+            start: Some(node),
+            stack: vec![],
+            entered: false,
+        }
+    }
+
+    /// Do not walk the children of the node that was just entered.
+    ///
+    /// The next event is the `Leave` event of the node. This has no effect unless the last event
+    /// was an `Enter` event.
+    pub fn skip_children(&mut self) {
+        if self.entered {
+            if let Some((node, index)) = self.stack.last_mut() {
+                *index = node.children_ref().len();
+            }
+        }
+    }
+}
+
+impl<'a, Q, T, P> Iterator for WalkEvents<'a, Q, T, P>
+where
+    Q: PartialEq + Eq + Clone,
+    P: NodeStorage,
+{
+    type Item = WalkEvent<'a, Q, T, P>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(node) = self.start.take() {
+            self.stack.push((node, 0));
+            self.entered = true;
+            return Some(WalkEvent::Enter(node));
+        }
+        loop {
+            let (node, index) = self.stack.last_mut()?;
+            let Some(child_id) = node.children_ref().get(*index).cloned() else {
+                let (node, _) = self.stack.pop()?;
+                self.entered = false;
+                return Some(WalkEvent::Leave(node));
+            };
+            *index += 1;
+            if let Some(child) = self.nodes.get_by_node_id(&child_id) {
+                self.stack.push((child, 0));
+                self.entered = true;
+                return Some(WalkEvent::Enter(child));
+            }
+        }
+    }
+}